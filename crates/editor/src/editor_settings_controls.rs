@@ -1,55 +1,483 @@
-use std::sync::Arc;
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeSet},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
 
-use gpui::{AppContext, FontFeatures, FontWeight};
+use collections::HashSet;
+use gpui::{
+    AccessibilityNode, AccessibilityRole, AppContext, ClipboardItem, FontFeatures, FontWeight,
+    ScrollHandle,
+};
 use project::project_settings::{InlineBlameSettings, ProjectSettings};
-use settings::{EditableSettingControl, Settings};
+use serde::{Deserialize, Serialize};
+use settings::{EditableSettingControl, Settings, SettingsDocument};
 use theme::{FontFamilyCache, ThemeSettings};
 use ui::{
-    prelude::*, CheckboxWithLabel, ContextMenu, DropdownMenu, NumericStepper, SettingsContainer,
-    SettingsGroup,
+    prelude::*, right_click_menu, Badge, BadgeColor, CheckboxWithLabel, ContextMenu, DropdownMenu,
+    HighlightedLabel, NumericStepper, PopoverMenuHandle, RightClickMenu, SettingsContainer,
+    SettingsFocus, SettingsGroup, SettingsGroupOrder, SettingsItem, Slider, Switch, Tooltip,
 };
 
 use crate::EditorSettings;
 
 #[derive(IntoElement)]
-pub struct EditorSettingsControls {}
+pub struct EditorSettingsControls {
+    query: SharedString,
+    collapsed_groups: HashSet<SharedString>,
+    highlighted_group: Option<SharedString>,
+    revealed_group: Option<(SharedString, ScrollHandle)>,
+    on_toggle_group: Option<Arc<dyn Fn(&SharedString, &mut WindowContext) + 'static>>,
+    group_order: Option<SettingsGroupOrder>,
+    focus: Option<SettingsFocus>,
+    state_hash: Option<u64>,
+}
 
 impl EditorSettingsControls {
+    /// The headers and IDs of the groups, in the order they're shown.
+    const GROUPS: [(&'static str, &'static str); 4] = [
+        ("Font", "editor-font"),
+        ("Editor", "editor-editor"),
+        ("Gutter", "editor-gutter"),
+        ("Scrollbar", "editor-scrollbar"),
+    ];
+
     pub fn new() -> Self {
-        Self {}
+        Self {
+            query: SharedString::default(),
+            collapsed_groups: HashSet::default(),
+            highlighted_group: None,
+            revealed_group: None,
+            on_toggle_group: None,
+            group_order: None,
+            focus: None,
+            state_hash: None,
+        }
+    }
+
+    /// Returns the document for saving and loading the settings edited by these controls, in the
+    /// order they're shown.
+    pub fn document() -> SettingsDocument {
+        SettingsDocument::new()
+            .control::<BufferFontFamilyControl>("buffer-font-family")
+            .control::<BufferFontWeightControl>("buffer-font-weight")
+            .control::<BufferFontSizeControl>("buffer-font-size")
+            .control::<BufferFontLigaturesControl>("buffer-font-ligatures")
+            .control::<InlineGitBlameControl>("inline-git-blame")
+            .control::<InlineGitBlameDelayControl>("inline-git-blame-delay")
+            .control::<ScrollSensitivityControl>("scroll-sensitivity")
+            .control::<LineNumbersControl>("line-numbers")
+            .control::<RelativeLineNumbersControl>("relative-line-numbers")
+            .control::<ScrollbarMarkersControl>("scrollbar-markers")
+    }
+
+    /// Returns whether one of the groups has the given ID.
+    pub fn has_group(group_id: &str) -> bool {
+        Self::GROUPS.iter().any(|(_, id)| *id == group_id)
+    }
+
+    /// Only shows the settings that fuzzily match the given search query, with the groups that
+    /// match best shown first and the matching characters highlighted.
+    ///
+    /// When the query matches the name of a group, every setting in that group is shown.
+    pub fn query(mut self, query: impl Into<SharedString>) -> Self {
+        self.query = query.into();
+        self
+    }
+
+    /// Sets the IDs of the groups that are collapsed.
+    ///
+    /// Groups are always expanded while a search query is active.
+    pub fn collapsed_groups(mut self, collapsed_groups: HashSet<SharedString>) -> Self {
+        self.collapsed_groups = collapsed_groups;
+        self
+    }
+
+    /// Highlights the group with the given ID, such as after it has been scrolled to.
+    pub fn highlighted_group(mut self, group_id: impl Into<SharedString>) -> Self {
+        self.highlighted_group = Some(group_id.into());
+        self
+    }
+
+    /// Scrolls the element tracked by the given handle so that the group with the given ID is at
+    /// its top.
+    pub fn reveal_group(
+        mut self,
+        group_id: impl Into<SharedString>,
+        scroll_handle: ScrollHandle,
+    ) -> Self {
+        self.revealed_group = Some((group_id.into(), scroll_handle));
+        self
+    }
+
+    /// Sets the handler called with the group ID when a group header is clicked.
+    pub fn on_toggle_group(
+        mut self,
+        on_toggle_group: impl Fn(&SharedString, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.on_toggle_group = Some(Arc::new(on_toggle_group));
+        self
+    }
+
+    /// Lets the groups be reordered by dragging their headers, keeping their order in the given
+    /// [`SettingsGroupOrder`].
+    ///
+    /// Groups can't be reordered while a search query is active, as some of them may be hidden.
+    pub fn group_order(mut self, group_order: SettingsGroupOrder) -> Self {
+        self.group_order = Some(group_order);
+        self
+    }
+
+    /// Lets the settings and group headers be focused from the keyboard with the given focus.
+    pub fn focus(mut self, focus: SettingsFocus) -> Self {
+        self.focus = Some(focus);
+        self
+    }
+
+    /// Reuses what each group drew in the previous frame while the given hash is unchanged,
+    /// instead of rendering its settings again. The hash should change whenever the settings do.
+    pub fn cache(mut self, state_hash: u64) -> Self {
+        self.state_hash = Some(state_hash);
+        self
+    }
+
+    fn reorderable_group_order(&self) -> Option<SettingsGroupOrder> {
+        self.group_order.clone().filter(|_| self.query.is_empty())
+    }
+
+    /// Returns whether any of the settings match the current query.
+    pub fn has_matches(&self) -> bool {
+        self.matches("Font", &BufferFontFamilyControl::default())
+            || self.matches("Font", &BufferFontWeightControl::default())
+            || self.matches("Font", &BufferFontSizeControl)
+            || self.matches("Font", &BufferFontLigaturesControl(self.query.clone()))
+            || self.matches("Editor", &InlineGitBlameControl(self.query.clone()))
+            || self.matches("Editor", &InlineGitBlameDelayControl(self.query.clone()))
+            || self.matches("Editor", &ScrollSensitivityControl(self.query.clone()))
+            || self.matches("Gutter", &LineNumbersControl(self.query.clone()))
+            || self.matches("Gutter", &RelativeLineNumbersControl::default())
+            || self.matches(
+                "Scrollbar",
+                &ScrollbarMarkersControl::new(self.query.clone()),
+            )
+    }
+
+    /// Returns the ID of the group with the given header, which is static so that rendering the
+    /// group doesn't allocate it.
+    fn group_id(header: &str) -> SharedString {
+        let (_, id) = Self::GROUPS
+            .iter()
+            .find(|(group_header, _)| *group_header == header)
+            .expect("unknown settings group");
+        SharedString::from(*id)
+    }
+
+    /// Returns the group with the given header, ranked among the others by the given search score.
+    fn group(&self, header: &'static str, score: f64) -> SettingsGroup {
+        let id = Self::group_id(header);
+        let collapsed = self.query.is_empty() && self.collapsed_groups.contains(&id);
+        let header_highlights = fuzzy::match_string(&self.query, header, false)
+            .map(|mat| mat.positions)
+            .unwrap_or_default();
+
+        SettingsGroup::new(header)
+            .header_highlights(header_highlights)
+            .search_score(score)
+            .when(self.reorderable_group_order().is_some(), |this| {
+                this.draggable(id.clone())
+            })
+            .collapsible(true)
+            .collapsed(collapsed)
+            .highlighted(self.highlighted_group.as_ref() == Some(&id))
+            .when_some(
+                self.revealed_group
+                    .as_ref()
+                    .filter(|(group_id, _)| *group_id == id),
+                |this, (_, scroll_handle)| this.reveal(scroll_handle.clone()),
+            )
+            .when_some(self.focus.clone(), |this, focus| {
+                this.focus(focus, id.clone())
+            })
+            .when_some(self.state_hash, |this, state_hash| {
+                // The settings in the group highlight the query in their names.
+                let mut hasher = DefaultHasher::new();
+                (state_hash, &self.query).hash(&mut hasher);
+                this.cache(hasher.finish())
+            })
+            .when_some(self.on_toggle_group.clone(), |this, on_toggle_group| {
+                this.on_toggle(move |_, cx| on_toggle_group(&id, cx))
+            })
+    }
+
+    /// Returns the item for the setting with the given ID, which can be focused from the
+    /// keyboard.
+    fn item(&self, id: &'static str, control: impl IntoElement) -> SettingsItem {
+        SettingsItem::new(id, control)
+            .when_some(self.focus.clone(), |this, focus| this.focus(focus))
+    }
+
+    /// Returns how well the setting in the given group matches the current query, or `None` if
+    /// it doesn't.
+    ///
+    /// When the query matches the header of the group, the setting matches at least as well as
+    /// the header does.
+    fn score(&self, group: &str, control: &impl EditableSettingControl) -> Option<f64> {
+        let group_score = fuzzy::match_string(&self.query, group, false).map(|mat| mat.score);
+        let control_score = control.query_match(&self.query).map(|mat| mat.score);
+        best_score([group_score, control_score])
+    }
+
+    fn matches(&self, group: &str, control: &impl EditableSettingControl) -> bool {
+        self.score(group, control).is_some()
     }
 }
 
+/// Returns the best of the given search scores, or `None` if none of them matched.
+fn best_score(scores: impl IntoIterator<Item = Option<f64>>) -> Option<f64> {
+    scores.into_iter().flatten().reduce(f64::max)
+}
+
 impl RenderOnce for EditorSettingsControls {
     fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+        // The controls that show the name of their setting are given the query, so that they can
+        // highlight the characters of the name that match it.
+        let font_family_control = BufferFontFamilyControl::default();
+        let font_weight_control = BufferFontWeightControl::default();
+        let relative_line_numbers_control = RelativeLineNumbersControl::default();
+        let scrollbar_markers_control = ScrollbarMarkersControl::new(self.query.clone());
+        let font_family = self.score("Font", &font_family_control);
+        let font_weight = self.score("Font", &font_weight_control);
+        let font_size = self.score("Font", &BufferFontSizeControl);
+        let font_ligatures = self.score("Font", &BufferFontLigaturesControl(self.query.clone()));
+        let inline_git_blame = self.score("Editor", &InlineGitBlameControl(self.query.clone()));
+        let inline_git_blame_delay =
+            self.score("Editor", &InlineGitBlameDelayControl(self.query.clone()));
+        let scroll_sensitivity =
+            self.score("Editor", &ScrollSensitivityControl(self.query.clone()));
+        let line_numbers = self.score("Gutter", &LineNumbersControl(self.query.clone()));
+        let relative_line_numbers = self.score("Gutter", &relative_line_numbers_control);
+        let scrollbar_markers = self.score("Scrollbar", &scrollbar_markers_control);
+
         SettingsContainer::new()
-            .child(
-                SettingsGroup::new("Font")
-                    .child(
-                        h_flex()
-                            .gap_2()
-                            .justify_between()
-                            .child(BufferFontFamilyControl)
-                            .child(BufferFontWeightControl),
+            .when_some(self.reorderable_group_order(), |this, group_order| {
+                this.reorderable(group_order)
+            })
+            .when_some(
+                best_score([font_family, font_weight, font_size, font_ligatures]),
+                |this, score| {
+                    this.child_group(
+                        self.group("Font", score)
+                            .when(font_family.is_some() || font_weight.is_some(), |this| {
+                                this.child(
+                                    h_flex()
+                                        .gap_2()
+                                        .justify_between()
+                                        .when(font_family.is_some(), |this| {
+                                            this.child({
+                                                let menu_handle =
+                                                    font_family_control.menu_handle.clone();
+                                                self.item(
+                                                    "buffer-font-family",
+                                                    setting_context_menu(
+                                                        "buffer-font-family-context-menu",
+                                                        font_family_control,
+                                                    ),
+                                                )
+                                                .on_activate(move |cx| menu_handle.show(cx))
+                                            })
+                                        })
+                                        .when(font_weight.is_some(), |this| {
+                                            this.child({
+                                                let menu_handle =
+                                                    font_weight_control.menu_handle.clone();
+                                                self.item(
+                                                    "buffer-font-weight",
+                                                    setting_context_menu(
+                                                        "buffer-font-weight-context-menu",
+                                                        font_weight_control,
+                                                    ),
+                                                )
+                                                .on_activate(move |cx| menu_handle.show(cx))
+                                            })
+                                        }),
+                                )
+                            })
+                            .when(font_size.is_some(), |this| {
+                                this.child(self.item(
+                                    "buffer-font-size",
+                                    setting_context_menu(
+                                        "buffer-font-size-context-menu",
+                                        BufferFontSizeControl,
+                                    ),
+                                ))
+                            })
+                            .when(font_ligatures.is_some(), |this| {
+                                this.child(
+                                    self.item(
+                                        "buffer-font-ligatures",
+                                        setting_context_menu(
+                                            "buffer-font-ligatures-context-menu",
+                                            BufferFontLigaturesControl(self.query.clone()),
+                                        ),
+                                    )
+                                    .on_activate(toggle::<BufferFontLigaturesControl>),
+                                )
+                            }),
                     )
-                    .child(BufferFontSizeControl)
-                    .child(BufferFontLigaturesControl),
+                },
             )
-            .child(SettingsGroup::new("Editor").child(InlineGitBlameControl))
-            .child(
-                SettingsGroup::new("Gutter").child(
-                    h_flex()
-                        .gap_2()
-                        .justify_between()
-                        .child(LineNumbersControl)
-                        .child(RelativeLineNumbersControl),
-                ),
+            .when_some(
+                best_score([inline_git_blame, inline_git_blame_delay, scroll_sensitivity]),
+                |this, score| {
+                    this.child_group(
+                        self.group("Editor", score)
+                            .when(inline_git_blame.is_some(), |this| {
+                                this.child(
+                                    self.item(
+                                        "inline-git-blame",
+                                        setting_context_menu(
+                                            "inline-git-blame-context-menu",
+                                            InlineGitBlameControl(self.query.clone()),
+                                        ),
+                                    )
+                                    .on_activate(toggle::<InlineGitBlameControl>),
+                                )
+                            })
+                            .when(inline_git_blame_delay.is_some(), |this| {
+                                this.child(self.item(
+                                    "inline-git-blame-delay",
+                                    setting_context_menu(
+                                        "inline-git-blame-delay-context-menu",
+                                        InlineGitBlameDelayControl(self.query.clone()),
+                                    ),
+                                ))
+                            })
+                            .when(scroll_sensitivity.is_some(), |this| {
+                                this.child(self.item(
+                                    "scroll-sensitivity",
+                                    setting_context_menu(
+                                        "scroll-sensitivity-context-menu",
+                                        ScrollSensitivityControl(self.query.clone()),
+                                    ),
+                                ))
+                            }),
+                    )
+                },
             )
+            .when_some(
+                best_score([line_numbers, relative_line_numbers]),
+                |this, score| {
+                    this.child_group(
+                        self.group("Gutter", score)
+                            .description(
+                                "Controls what is shown alongside each line of the buffer.",
+                            )
+                            .child(
+                                div()
+                                    .grid()
+                                    .grid_cols(2)
+                                    .gap_2()
+                                    .when(line_numbers.is_some(), |this| {
+                                        this.child(
+                                            self.item(
+                                                "line-numbers",
+                                                setting_context_menu(
+                                                    "line-numbers-context-menu",
+                                                    LineNumbersControl(self.query.clone()),
+                                                ),
+                                            )
+                                            .on_activate(toggle::<LineNumbersControl>),
+                                        )
+                                    })
+                                    .when(relative_line_numbers.is_some(), |this| {
+                                        this.child({
+                                            let menu_handle =
+                                                relative_line_numbers_control.menu_handle.clone();
+                                            self.item(
+                                                "relative-line-numbers",
+                                                setting_context_menu(
+                                                    "relative-line-numbers-context-menu",
+                                                    relative_line_numbers_control,
+                                                ),
+                                            )
+                                            .on_activate(move |cx| menu_handle.show(cx))
+                                        })
+                                    }),
+                            ),
+                    )
+                },
+            )
+            .when_some(scrollbar_markers, |this, score| {
+                this.child_group(self.group("Scrollbar", score).child({
+                    let menu_handle = scrollbar_markers_control.menu_handle.clone();
+                    self.item(
+                        "scrollbar-markers",
+                        setting_context_menu(
+                            "scrollbar-markers-context-menu",
+                            scrollbar_markers_control,
+                        ),
+                    )
+                    .on_activate(move |cx| menu_handle.show(cx))
+                }))
+            })
     }
 }
 
-#[derive(IntoElement)]
-struct BufferFontFamilyControl;
+/// Toggles the setting edited by `C`, for activating its control from the keyboard.
+fn toggle<C: EditableSettingControl<Value = bool>>(cx: &mut WindowContext) {
+    C::write(!C::read(cx), cx);
+}
+
+/// Returns a button that resets the setting edited by `C` to its default value, or `None` if the
+/// setting already has its default value.
+fn reset_button<C>(id: &'static str, cx: &WindowContext) -> Option<IconButton>
+where
+    C: EditableSettingControl,
+    C::Value: 'static,
+{
+    let default_value = C::reset_value(cx)?;
+
+    Some(
+        IconButton::new(id, IconName::RotateCcw)
+            .icon_size(IconSize::Small)
+            .icon_color(Color::Muted)
+            .tooltip(|cx| Tooltip::text("Reset to Default", cx))
+            .on_click(move |_, cx| C::write(default_value.clone(), cx)),
+    )
+}
+
+/// Wraps `control` in a menu opened by right-clicking it, for copying the name of its setting or
+/// resetting the setting to its default value.
+fn setting_context_menu<C>(id: &'static str, control: C) -> RightClickMenu<ContextMenu>
+where
+    C: EditableSettingControl + IntoElement,
+    C::Value: 'static,
+{
+    let name = control.name();
+
+    right_click_menu(id).trigger(control).menu(move |cx| {
+        let name = name.clone();
+        let reset_value = C::reset_value(cx);
+
+        ContextMenu::build(cx, move |menu, _cx| {
+            let menu = menu.entry("Copy Setting Name", None, move |cx| {
+                cx.write_to_clipboard(ClipboardItem::new(name.to_string()))
+            });
+
+            match reset_value {
+                Some(reset_value) => menu.entry("Reset to Default", None, move |cx| {
+                    C::write(reset_value.clone(), cx)
+                }),
+                None => menu,
+            }
+        })
+    })
+}
+
+#[derive(IntoElement, Default)]
+struct BufferFontFamilyControl {
+    menu_handle: PopoverMenuHandle<ContextMenu>,
+}
 
 impl EditableSettingControl for BufferFontFamilyControl {
     type Value = SharedString;
@@ -77,10 +505,8 @@ impl RenderOnce for BufferFontFamilyControl {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         let value = Self::read(cx);
 
-        h_flex()
-            .gap_2()
-            .child(Icon::new(IconName::Font))
-            .child(DropdownMenu::new(
+        h_flex().gap_2().child(Icon::new(IconName::Font)).child(
+            DropdownMenu::new(
                 "buffer-font-family",
                 value.clone(),
                 ContextMenu::build(cx, |mut menu, cx| {
@@ -103,7 +529,9 @@ impl RenderOnce for BufferFontFamilyControl {
 
                     menu
                 }),
-            ))
+            )
+            .with_handle(self.menu_handle),
+        )
     }
 }
 
@@ -152,8 +580,10 @@ impl RenderOnce for BufferFontSizeControl {
     }
 }
 
-#[derive(IntoElement)]
-struct BufferFontWeightControl;
+#[derive(IntoElement, Default)]
+struct BufferFontWeightControl {
+    menu_handle: PopoverMenuHandle<ContextMenu>,
+}
 
 impl EditableSettingControl for BufferFontWeightControl {
     type Value = FontWeight;
@@ -168,6 +598,11 @@ impl EditableSettingControl for BufferFontWeightControl {
         settings.buffer_font.weight
     }
 
+    fn default_value(cx: &AppContext) -> Option<Self::Value> {
+        let settings = ThemeSettings::get_default(cx);
+        Some(settings.buffer_font.weight)
+    }
+
     fn apply(
         settings: &mut <Self::Settings as Settings>::FileContent,
         value: Self::Value,
@@ -184,29 +619,33 @@ impl RenderOnce for BufferFontWeightControl {
         h_flex()
             .gap_2()
             .child(Icon::new(IconName::FontWeight))
-            .child(DropdownMenu::new(
-                "buffer-font-weight",
-                value.0.to_string(),
-                ContextMenu::build(cx, |mut menu, _cx| {
-                    for weight in FontWeight::ALL {
-                        menu = menu.custom_entry(
-                            move |_cx| Label::new(weight.0.to_string()).into_any_element(),
-                            {
-                                move |cx| {
-                                    Self::write(weight, cx);
-                                }
-                            },
-                        )
-                    }
+            .child(
+                DropdownMenu::new(
+                    "buffer-font-weight",
+                    value.0.to_string(),
+                    ContextMenu::build(cx, |mut menu, _cx| {
+                        for weight in FontWeight::ALL {
+                            menu = menu.custom_entry(
+                                move |_cx| Label::new(weight.0.to_string()).into_any_element(),
+                                {
+                                    move |cx| {
+                                        Self::write(weight, cx);
+                                    }
+                                },
+                            )
+                        }
 
-                    menu
-                }),
-            ))
+                        menu
+                    }),
+                )
+                .with_handle(self.menu_handle),
+            )
+            .children(reset_button::<Self>("buffer-font-weight-reset", cx))
     }
 }
 
 #[derive(IntoElement)]
-struct BufferFontLigaturesControl;
+struct BufferFontLigaturesControl(SharedString);
 
 impl EditableSettingControl for BufferFontLigaturesControl {
     type Value = bool;
@@ -272,11 +711,12 @@ impl RenderOnce for BufferFontLigaturesControl {
                 );
             },
         )
+        .label_highlights(self.query_highlights(&self.0))
     }
 }
 
 #[derive(IntoElement)]
-struct InlineGitBlameControl;
+struct InlineGitBlameControl(SharedString);
 
 impl EditableSettingControl for InlineGitBlameControl {
     type Value = bool;
@@ -291,6 +731,11 @@ impl EditableSettingControl for InlineGitBlameControl {
         settings.git.inline_blame_enabled()
     }
 
+    fn default_value(cx: &AppContext) -> Option<Self::Value> {
+        let settings = ProjectSettings::get_default(cx);
+        Some(settings.git.inline_blame_enabled())
+    }
+
     fn apply(
         settings: &mut <Self::Settings as Settings>::FileContent,
         value: Self::Value,
@@ -311,25 +756,143 @@ impl RenderOnce for InlineGitBlameControl {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         let value = Self::read(cx);
 
-        CheckboxWithLabel::new(
-            "inline-git-blame",
-            Label::new(self.name()),
-            value.into(),
-            |selection, cx| {
-                Self::write(
-                    match selection {
-                        Selection::Selected => true,
-                        Selection::Unselected | Selection::Indeterminate => false,
+        h_flex()
+            .gap_2()
+            .accessibility(
+                AccessibilityNode::new(AccessibilityRole::Switch, self.name()).checked(Some(value)),
+            )
+            .child(
+                Switch::new("inline-git-blame", value).on_click(|selection, cx| {
+                    Self::write(
+                        match selection {
+                            Selection::Selected => true,
+                            Selection::Unselected | Selection::Indeterminate => false,
+                        },
+                        cx,
+                    );
+                }),
+            )
+            .child(HighlightedLabel::new(
+                self.name(),
+                self.query_highlights(&self.0),
+            ))
+            .children(reset_button::<Self>("inline-git-blame-reset", cx))
+    }
+}
+
+#[derive(IntoElement)]
+struct InlineGitBlameDelayControl(SharedString);
+
+impl EditableSettingControl for InlineGitBlameDelayControl {
+    type Value = u64;
+    type Settings = ProjectSettings;
+
+    fn name(&self) -> SharedString {
+        "Inline Git Blame Delay".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = ProjectSettings::get_global(cx);
+        settings
+            .git
+            .inline_blame_delay()
+            .map_or(0, |delay| delay.as_millis() as u64)
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        if let Some(inline_blame) = settings.git.inline_blame.as_mut() {
+            inline_blame.delay_ms = Some(value);
+        } else {
+            settings.git.inline_blame = Some(InlineBlameSettings {
+                enabled: true,
+                delay_ms: Some(value),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+impl RenderOnce for InlineGitBlameDelayControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+        // The delay only applies while inline git blame is shown.
+        let enabled = InlineGitBlameControl::read(cx);
+
+        h_flex()
+            .gap_2()
+            .child(
+                HighlightedLabel::new(self.name(), self.query_highlights(&self.0)).color(
+                    if enabled {
+                        Color::Default
+                    } else {
+                        Color::Disabled
                     },
-                    cx,
-                );
-            },
-        )
+                ),
+            )
+            .child(
+                Slider::new("inline-git-blame-delay", value as f32, 0., 2000., 100.)
+                    .disabled(!enabled)
+                    .on_change(|value, cx| Self::write(value as u64, cx)),
+            )
+            .child(Label::new("ms").color(Color::Muted))
     }
 }
 
 #[derive(IntoElement)]
-struct LineNumbersControl;
+struct ScrollSensitivityControl(SharedString);
+
+impl EditableSettingControl for ScrollSensitivityControl {
+    type Value = f32;
+    type Settings = EditorSettings;
+
+    fn name(&self) -> SharedString {
+        "Scroll Sensitivity".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = EditorSettings::get_global(cx);
+        settings.scroll_sensitivity
+    }
+
+    fn default_value(cx: &AppContext) -> Option<Self::Value> {
+        let settings = EditorSettings::get_default(cx);
+        Some(settings.scroll_sensitivity)
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.scroll_sensitivity = Some(value);
+    }
+}
+
+impl RenderOnce for ScrollSensitivityControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        h_flex()
+            .gap_2()
+            .child(HighlightedLabel::new(
+                self.name(),
+                self.query_highlights(&self.0),
+            ))
+            .child(
+                Slider::new("scroll-sensitivity", value, 0.1, 5., 0.1)
+                    .precision(1)
+                    .on_change(|value, cx| Self::write(value, cx)),
+            )
+            .children(reset_button::<Self>("scroll-sensitivity-reset", cx))
+    }
+}
+
+#[derive(IntoElement)]
+struct LineNumbersControl(SharedString);
 
 impl EditableSettingControl for LineNumbersControl {
     type Value = bool;
@@ -378,11 +941,15 @@ impl RenderOnce for LineNumbersControl {
                 );
             },
         )
+        .icon(IconName::Hash)
+        .label_highlights(self.query_highlights(&self.0))
     }
 }
 
-#[derive(IntoElement)]
-struct RelativeLineNumbersControl;
+#[derive(IntoElement, Default)]
+struct RelativeLineNumbersControl {
+    menu_handle: PopoverMenuHandle<ContextMenu>,
+}
 
 impl EditableSettingControl for RelativeLineNumbersControl {
     type Value = bool;
@@ -409,20 +976,180 @@ impl EditableSettingControl for RelativeLineNumbersControl {
 impl RenderOnce for RelativeLineNumbersControl {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         let value = Self::read(cx);
+        // Relative line numbers have no effect while line numbers are hidden, so we disable the
+        // control without touching its value. Turning line numbers back on restores it as-is.
+        let disabled = !LineNumbersControl::read(cx);
 
-        DropdownMenu::new(
-            "relative-line-numbers",
-            if value { "Relative" } else { "Ascending" },
-            ContextMenu::build(cx, |menu, _cx| {
-                menu.custom_entry(
-                    |_cx| Label::new("Ascending").into_any_element(),
-                    move |cx| Self::write(false, cx),
+        div()
+            .id("relative-line-numbers-container")
+            .accessibility(
+                AccessibilityNode::new(AccessibilityRole::ComboBox, self.name())
+                    .value(if value { "Relative" } else { "Ascending" })
+                    .disabled(disabled),
+            )
+            .when(disabled, |this| {
+                this.tooltip(|cx| Tooltip::text("Requires line numbers to be shown", cx))
+            })
+            .child(
+                DropdownMenu::new(
+                    "relative-line-numbers",
+                    if value { "Relative" } else { "Ascending" },
+                    ContextMenu::build(cx, |menu, _cx| {
+                        menu.custom_entry(
+                            |_cx| Label::new("Ascending").into_any_element(),
+                            move |cx| Self::write(false, cx),
+                        )
+                        .custom_entry(
+                            |_cx| Label::new("Relative").into_any_element(),
+                            move |cx| Self::write(true, cx),
+                        )
+                    }),
                 )
-                .custom_entry(
-                    |_cx| Label::new("Relative").into_any_element(),
-                    move |cx| Self::write(true, cx),
+                .with_handle(self.menu_handle)
+                .disabled(disabled),
+            )
+    }
+}
+
+/// An indicator that can be shown in the editor's scrollbar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ScrollbarMarker {
+    GitDiff,
+    SearchResults,
+    SelectedSymbol,
+    Diagnostics,
+    Cursors,
+}
+
+impl ScrollbarMarker {
+    const ALL: [Self; 5] = [
+        Self::GitDiff,
+        Self::SearchResults,
+        Self::SelectedSymbol,
+        Self::Diagnostics,
+        Self::Cursors,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::GitDiff => "Git Diff",
+            Self::SearchResults => "Search Results",
+            Self::SelectedSymbol => "Selected Symbol",
+            Self::Diagnostics => "Diagnostics",
+            Self::Cursors => "Cursors",
+        }
+    }
+
+    fn is_shown(&self, scrollbar: &crate::editor_settings::Scrollbar) -> bool {
+        match self {
+            Self::GitDiff => scrollbar.git_diff,
+            Self::SearchResults => scrollbar.search_results,
+            Self::SelectedSymbol => scrollbar.selected_symbol,
+            Self::Diagnostics => scrollbar.diagnostics,
+            Self::Cursors => scrollbar.cursors,
+        }
+    }
+}
+
+#[derive(IntoElement)]
+struct ScrollbarMarkersControl {
+    query: SharedString,
+    menu_handle: PopoverMenuHandle<ContextMenu>,
+}
+
+impl ScrollbarMarkersControl {
+    fn new(query: SharedString) -> Self {
+        Self {
+            query,
+            menu_handle: PopoverMenuHandle::default(),
+        }
+    }
+}
+
+impl EditableSettingControl for ScrollbarMarkersControl {
+    // Ordered, so that the markers are saved in the same order every time.
+    type Value = BTreeSet<ScrollbarMarker>;
+    type Settings = EditorSettings;
+
+    fn name(&self) -> SharedString {
+        "Scrollbar Markers".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = EditorSettings::get_global(cx);
+        ScrollbarMarker::ALL
+            .into_iter()
+            .filter(|marker| marker.is_shown(&settings.scrollbar))
+            .collect()
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        let scrollbar =
+            settings
+                .scrollbar
+                .get_or_insert(crate::editor_settings::ScrollbarContent {
+                    show: None,
+                    git_diff: None,
+                    search_results: None,
+                    selected_symbol: None,
+                    diagnostics: None,
+                    cursors: None,
+                });
+        scrollbar.git_diff = Some(value.contains(&ScrollbarMarker::GitDiff));
+        scrollbar.search_results = Some(value.contains(&ScrollbarMarker::SearchResults));
+        scrollbar.selected_symbol = Some(value.contains(&ScrollbarMarker::SelectedSymbol));
+        scrollbar.diagnostics = Some(value.contains(&ScrollbarMarker::Diagnostics));
+        scrollbar.cursors = Some(value.contains(&ScrollbarMarker::Cursors));
+    }
+}
+
+impl RenderOnce for ScrollbarMarkersControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        h_flex()
+            .gap_2()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(HighlightedLabel::new(
+                        self.name(),
+                        self.query_highlights(&self.query),
+                    ))
+                    .child(Badge::new("New").color(BadgeColor::Success)),
+            )
+            .child(
+                DropdownMenu::new(
+                    "scrollbar-markers",
+                    format!("{} selected", value.len()),
+                    ContextMenu::build(cx, |mut menu, _cx| {
+                        for marker in ScrollbarMarker::ALL {
+                            menu = menu.toggleable_entry(
+                                marker.label(),
+                                value.contains(&marker),
+                                None,
+                                {
+                                    let value = value.clone();
+                                    move |cx| {
+                                        let mut value = value.clone();
+                                        if !value.remove(&marker) {
+                                            value.insert(marker);
+                                        }
+                                        Self::write(value, cx);
+                                    }
+                                },
+                            )
+                        }
+
+                        menu
+                    }),
                 )
-            }),
-        )
+                .with_handle(self.menu_handle),
+            )
     }
 }
@@ -7,4 +7,4 @@ pub use char_bag::CharBag;
 pub use paths::{
     match_fixed_path_set, match_path_sets, PathMatch, PathMatchCandidate, PathMatchCandidateSet,
 };
-pub use strings::{match_strings, StringMatch, StringMatchCandidate};
+pub use strings::{match_string, match_strings, StringMatch, StringMatchCandidate};
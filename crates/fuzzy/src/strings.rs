@@ -105,6 +105,43 @@ impl Ord for StringMatch {
     }
 }
 
+/// Matches the query against a single string, returning `None` if the string doesn't contain
+/// every character of the query in order.
+///
+/// An empty query matches every string, with a score of zero.
+pub fn match_string(query: &str, string: &str, smart_case: bool) -> Option<StringMatch> {
+    if query.is_empty() {
+        return Some(StringMatch {
+            candidate_id: 0,
+            score: 0.,
+            positions: Vec::new(),
+            string: string.to_string(),
+        });
+    }
+
+    let lowercase_query = query.to_lowercase().chars().collect::<Vec<_>>();
+    let query = query.chars().collect::<Vec<_>>();
+    let query_char_bag = CharBag::from(&lowercase_query[..]);
+    let candidate = StringMatchCandidate::new(0, string.to_string());
+
+    let mut matcher = Matcher::new(&query, &lowercase_query, query_char_bag, smart_case, 1);
+    let mut results = Vec::new();
+    matcher.match_candidates(
+        &[],
+        &[],
+        iter::once(&candidate),
+        &mut results,
+        &AtomicBool::new(false),
+        |candidate, score| StringMatch {
+            candidate_id: candidate.id,
+            score,
+            positions: Vec::new(),
+            string: candidate.string.clone(),
+        },
+    );
+    results.pop()
+}
+
 pub async fn match_strings(
     candidates: &[StringMatchCandidate],
     query: &str,
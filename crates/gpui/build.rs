@@ -103,6 +103,9 @@ mod macos {
             "Underline".into(),
             "UnderlineInputIndex".into(),
             "Quad".into(),
+            "GradientInputIndex".into(),
+            "GradientStop".into(),
+            "Gradient".into(),
             "SpriteInputIndex".into(),
             "MonochromeSprite".into(),
             "PolychromeSprite".into(),
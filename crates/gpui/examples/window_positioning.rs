@@ -58,6 +58,8 @@ fn build_window_options(display_id: DisplayId, bounds: Bounds<Pixels>) -> Window
         app_id: None,
         window_min_size: None,
         window_decorations: None,
+        offscreen: false,
+        parent: None,
     }
 }
 
@@ -0,0 +1,85 @@
+use crate::{Bounds, Pixels, SharedString};
+
+/// The kind of control an element presents itself as to assistive technologies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessibilityRole {
+    /// A control that performs an action when clicked.
+    Button,
+    /// A control that can be checked, unchecked, or indeterminate.
+    Checkbox,
+    /// A control that can be toggled on or off.
+    Switch,
+    /// A control that picks one value from a list of options.
+    ComboBox,
+    /// A control that picks a value from a continuous range.
+    Slider,
+    /// A text field that can be edited.
+    TextInput,
+}
+
+impl AccessibilityRole {
+    /// Returns the name of this role, as used by the ARIA specification.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Button => "button",
+            Self::Checkbox => "checkbox",
+            Self::Switch => "switch",
+            Self::ComboBox => "combobox",
+            Self::Slider => "slider",
+            Self::TextInput => "textbox",
+        }
+    }
+}
+
+/// Semantic information describing an element to assistive technologies, such as screen readers.
+///
+/// Attach a node to an element with [`InteractiveElement::accessibility`](crate::InteractiveElement::accessibility).
+/// The nodes painted in the last frame can be read back with
+/// [`WindowContext::accessibility_snapshot`](crate::WindowContext::accessibility_snapshot).
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityNode {
+    /// The kind of control the element presents itself as.
+    pub role: AccessibilityRole,
+    /// The name of the element, as it would be read out.
+    pub label: SharedString,
+    /// The current value of the element, such as the selected option of a combo box.
+    pub value: Option<SharedString>,
+    /// Whether the element is checked, for checkboxes and switches. `None` means indeterminate.
+    pub checked: Option<bool>,
+    /// Whether the element is disabled.
+    pub disabled: bool,
+    /// The bounds of the element in window coordinates, as of the frame it was painted in.
+    pub bounds: Bounds<Pixels>,
+}
+
+impl AccessibilityNode {
+    /// Creates a node with the given role and label.
+    pub fn new(role: AccessibilityRole, label: impl Into<SharedString>) -> Self {
+        Self {
+            role,
+            label: label.into(),
+            value: None,
+            checked: None,
+            disabled: false,
+            bounds: Bounds::default(),
+        }
+    }
+
+    /// Sets the current value of the element.
+    pub fn value(mut self, value: impl Into<SharedString>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Sets whether the element is checked.
+    pub fn checked(mut self, checked: Option<bool>) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// Sets whether the element is disabled.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
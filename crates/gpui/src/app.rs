@@ -1,6 +1,6 @@
 use std::{
     any::{type_name, TypeId},
-    cell::{Ref, RefCell, RefMut},
+    cell::{Cell, Ref, RefCell, RefMut},
     marker::PhantomData,
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
@@ -28,8 +28,8 @@ use crate::{
     current_platform, init_app_menus, Action, ActionRegistry, Any, AnyView, AnyWindowHandle,
     AssetCache, AssetSource, BackgroundExecutor, ClipboardItem, Context, DispatchPhase, DisplayId,
     Entity, EventEmitter, ForegroundExecutor, Global, KeyBinding, Keymap, Keystroke, LayoutId,
-    Menu, MenuItem, OwnedMenu, PathPromptOptions, Pixels, Platform, PlatformDisplay, Point,
-    PromptBuilder, PromptHandle, PromptLevel, Render, RenderablePromptHandle, Reservation,
+    Menu, MenuItem, NoAction, OwnedMenu, PathPromptOptions, Pixels, Platform, PlatformDisplay,
+    Point, PromptBuilder, PromptHandle, PromptLevel, Render, RenderablePromptHandle, Reservation,
     SharedString, SubscriberSet, Subscription, SvgRenderer, Task, TextSystem, View, ViewContext,
     Window, WindowAppearance, WindowContext, WindowHandle, WindowId,
 };
@@ -235,6 +235,11 @@ pub struct AppContext {
     pending_effects: VecDeque<Effect>,
     pub(crate) pending_notifications: FxHashSet<EntityId>,
     pub(crate) pending_global_notifications: FxHashSet<TypeId>,
+    /// The view being drawn, to which reads of globals are attributed.
+    pub(crate) drawing_view: Cell<Option<EntityId>>,
+    /// The globals read by the views being drawn, which haven't been added to their window's frame
+    /// yet.
+    pub(crate) global_reads: RefCell<Vec<(EntityId, TypeId)>>,
     pub(crate) observers: SubscriberSet<EntityId, Handler>,
     // TypeId is the type of the event that the listener callback expects
     pub(crate) event_listeners: SubscriberSet<EntityId, (TypeId, Listener)>,
@@ -290,6 +295,8 @@ impl AppContext {
                 pending_effects: VecDeque::new(),
                 pending_notifications: FxHashSet::default(),
                 pending_global_notifications: FxHashSet::default(),
+                drawing_view: Cell::new(None),
+                global_reads: RefCell::new(Vec::new()),
                 observers: SubscriberSet::new(),
                 event_listeners: SubscriberSet::new(),
                 release_listeners: SubscriberSet::new(),
@@ -511,6 +518,20 @@ impl AppContext {
         })
     }
 
+    /// Closes the windows anchored to the given window, which has been closed.
+    fn remove_child_windows(&mut self, parent: AnyWindowHandle) {
+        let children = self
+            .windows
+            .values()
+            .flatten()
+            .filter(|window| window.parent == Some(parent))
+            .map(|window| window.handle)
+            .collect::<Vec<_>>();
+        for child in children {
+            child.update(self, |_, cx| cx.remove_window()).log_err();
+        }
+    }
+
     /// Instructs the platform to activate the application by bringing it to the foreground.
     pub fn activate(&self, ignoring_other_apps: bool) {
         self.platform.activate(ignoring_other_apps);
@@ -831,6 +852,23 @@ impl AppContext {
         self.global_observers
             .clone()
             .retain(&type_id, |observer| observer(self));
+
+        // Redraw the views that read this global when they were last drawn.
+        for window in self.windows.values_mut().flatten() {
+            let readers = window
+                .rendered_frame
+                .global_reads
+                .iter()
+                .filter(|(_, global_types)| global_types.contains(&type_id))
+                .map(|(view_id, _)| *view_id)
+                .collect::<Vec<_>>();
+            if !readers.is_empty() {
+                for view_id in readers {
+                    window.mark_view_dirty(view_id);
+                }
+                window.dirty.set(true);
+            }
+        }
     }
 
     fn apply_defer_effect(&mut self, callback: Box<dyn FnOnce(&mut Self) + 'static>) {
@@ -891,8 +929,11 @@ impl AppContext {
     }
 
     /// Access the global of the given type. Panics if a global for that type has not been assigned.
+    ///
+    /// Views that read a global while they're drawn are redrawn whenever the global is updated.
     #[track_caller]
     pub fn global<G: Global>(&self) -> &G {
+        self.record_global_access(TypeId::of::<G>());
         self.globals_by_type
             .get(&TypeId::of::<G>())
             .map(|any_state| any_state.downcast_ref::<G>().unwrap())
@@ -902,11 +943,18 @@ impl AppContext {
 
     /// Access the global of the given type if a value has been assigned.
     pub fn try_global<G: Global>(&self) -> Option<&G> {
+        self.record_global_access(TypeId::of::<G>());
         self.globals_by_type
             .get(&TypeId::of::<G>())
             .map(|any_state| any_state.downcast_ref::<G>().unwrap())
     }
 
+    fn record_global_access(&self, global_type: TypeId) {
+        if let Some(view_id) = self.drawing_view.get() {
+            self.global_reads.borrow_mut().push((view_id, global_type));
+        }
+    }
+
     /// Access the global of the given type mutably. Panics if a global for that type has not been assigned.
     #[track_caller]
     pub fn global_mut<G: Global>(&mut self) -> &mut G {
@@ -1063,6 +1111,20 @@ impl AppContext {
         self.pending_effects.push_back(Effect::Refresh);
     }
 
+    /// Returns the key bindings triggered by exactly the given keystrokes, in any context, such as
+    /// to warn about conflicts when the user records a new shortcut.
+    pub fn bindings_for_keystrokes(&self, keystrokes: &[Keystroke]) -> Vec<KeyBinding> {
+        self.keymap
+            .borrow()
+            .bindings()
+            .filter(|binding| {
+                binding.keystrokes() == keystrokes
+                    && binding.action().as_any().type_id() != (NoAction {}).type_id()
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Clear all key bindings in the app.
     pub fn clear_key_bindings(&mut self) {
         self.keymap.borrow_mut().clear();
@@ -1347,6 +1409,7 @@ impl Context for AppContext {
             if window.removed {
                 cx.window_handles.remove(&handle.id);
                 cx.windows.remove(handle.id);
+                cx.remove_child_windows(handle);
             } else {
                 cx.windows
                     .get_mut(handle.id)
@@ -1464,3 +1527,429 @@ pub struct KeystrokeEvent {
     /// The action that was resolved for the keystroke, if any
     pub action: Option<Box<dyn Action>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+        time::Duration,
+    };
+
+    use crate::{
+        self as gpui, div, AnyView, AnyWindowHandle, ClipboardItem, Context, Empty, Global,
+        IntoElement, ParentElement, Render, StyleRefinement, TestAppContext, ViewContext,
+        VisualContext, WindowOptions,
+    };
+
+    struct Counter(usize);
+
+    impl Global for Counter {}
+
+    struct ObservingView {
+        notifications: usize,
+    }
+
+    impl Render for ObservingView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            Empty
+        }
+    }
+
+    #[gpui::test]
+    fn test_global_observers_across_windows(cx: &mut TestAppContext) {
+        cx.set_global(Counter(0));
+
+        // Each observer holds onto the token, so we can tell when its closure has been dropped.
+        let token = Rc::new(());
+        let open_window = |cx: &mut TestAppContext| {
+            let token = token.clone();
+            cx.add_window(move |cx| {
+                cx.observe_global::<Counter>(move |view: &mut ObservingView, _| {
+                    let _ = &token;
+                    view.notifications += 1;
+                })
+                .detach();
+                ObservingView { notifications: 0 }
+            })
+        };
+        let first_window = open_window(cx);
+        let second_window = open_window(cx);
+        cx.run_until_parked();
+
+        cx.update_global::<Counter, _>(|counter, _| counter.0 += 1);
+        assert_eq!(
+            first_window
+                .update(cx, |view, _| view.notifications)
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            second_window
+                .update(cx, |view, _| view.notifications)
+                .unwrap(),
+            1
+        );
+
+        second_window
+            .update(cx, |_, cx| cx.remove_window())
+            .unwrap();
+        cx.update_global::<Counter, _>(|counter, _| counter.0 += 1);
+        assert_eq!(
+            first_window
+                .update(cx, |view, _| view.notifications)
+                .unwrap(),
+            2
+        );
+        assert_eq!(Rc::strong_count(&token), 2);
+    }
+
+    #[gpui::test]
+    fn test_throttled_observers(cx: &mut TestAppContext) {
+        let counter = cx.new_model(|_| Counter(0));
+        let window = cx.add_window({
+            let counter = counter.clone();
+            move |cx| {
+                cx.observe_throttled(
+                    &counter,
+                    Duration::from_millis(100),
+                    |view: &mut ObservingView, _, _| view.notifications += 1,
+                )
+                .detach();
+                ObservingView { notifications: 0 }
+            }
+        });
+        let notify = |cx: &mut TestAppContext| {
+            counter.update(cx, |counter, cx| {
+                counter.0 += 1;
+                cx.notify();
+            })
+        };
+        let notifications =
+            |cx: &mut TestAppContext| window.update(cx, |view, _| view.notifications).unwrap();
+
+        // The first notification is handled immediately, and the following ones are coalesced.
+        notify(cx);
+        notify(cx);
+        notify(cx);
+        assert_eq!(notifications(cx), 1);
+
+        // The coalesced notifications are handled once the interval elapses.
+        cx.executor().advance_clock(Duration::from_millis(100));
+        cx.run_until_parked();
+        assert_eq!(notifications(cx), 2);
+
+        cx.executor().advance_clock(Duration::from_millis(200));
+        cx.run_until_parked();
+        assert_eq!(notifications(cx), 2);
+        notify(cx);
+        assert_eq!(notifications(cx), 3);
+    }
+
+    #[gpui::test]
+    fn test_notifications_within_an_update_redraw_once(cx: &mut TestAppContext) {
+        struct RenderCountingView {
+            renders: usize,
+        }
+
+        impl Render for RenderCountingView {
+            fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+                self.renders += 1;
+                Empty
+            }
+        }
+
+        let counters = (0..50)
+            .map(|_| cx.new_model(|_| Counter(0)))
+            .collect::<Vec<_>>();
+        let observations = Rc::new(Cell::new(0));
+        let window = cx.add_window({
+            let counters = counters.clone();
+            let observations = observations.clone();
+            move |cx| {
+                for counter in &counters {
+                    let observations = observations.clone();
+                    cx.observe(counter, move |_: &mut RenderCountingView, _, cx| {
+                        observations.set(observations.get() + 1);
+                        cx.notify();
+                    })
+                    .detach();
+                }
+                RenderCountingView { renders: 0 }
+            }
+        });
+        cx.run_until_parked();
+        let renders = |cx: &mut TestAppContext| window.update(cx, |view, _| view.renders).unwrap();
+        let initial_renders = renders(cx);
+
+        // Effects are only flushed once the outermost update returns, so updating many entities
+        // within a single update needs no batching of its own.
+        cx.update(|cx| {
+            for counter in &counters {
+                for _ in 0..2 {
+                    counter.update(cx, |counter, cx| {
+                        counter.0 += 1;
+                        cx.notify();
+                    });
+                }
+            }
+        });
+
+        // Each counter's observer only runs once, and the window is only redrawn once.
+        assert_eq!(observations.get(), 50);
+        assert_eq!(renders(cx), initial_renders + 1);
+    }
+
+    #[gpui::test]
+    fn test_clipboard_round_trip(cx: &mut TestAppContext) {
+        let window = cx.add_window(|_| ObservingView { notifications: 0 });
+        assert_eq!(cx.update(|cx| cx.read_from_clipboard()), None);
+
+        window
+            .update(cx, |_, cx| {
+                cx.write_to_clipboard(ClipboardItem::new("Buffer Font Size".to_string()))
+            })
+            .unwrap();
+        assert_eq!(
+            cx.update(|cx| cx.read_from_clipboard())
+                .map(|item| item.text().clone()),
+            Some("Buffer Font Size".to_string())
+        );
+    }
+
+    #[gpui::test]
+    async fn test_spawned_task_outliving_its_view(cx: &mut TestAppContext) {
+        let window = cx.add_window(|_| ObservingView { notifications: 0 });
+        let task = window
+            .update(cx, |_, cx| {
+                cx.spawn(|this, mut cx| async move {
+                    cx.background_executor().timer(Duration::from_secs(1)).await;
+                    this.update(&mut cx, |view, _| view.notifications += 1)
+                        .is_ok()
+                })
+            })
+            .unwrap();
+
+        // Updating the view once it has been released fails instead of panicking.
+        window.update(cx, |_, cx| cx.remove_window()).unwrap();
+        cx.executor().advance_clock(Duration::from_secs(1));
+        assert!(!task.await);
+    }
+
+    struct GlobalReadingView {
+        reads_counter: bool,
+        renders: Rc<Cell<usize>>,
+    }
+
+    impl Render for GlobalReadingView {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            if self.reads_counter {
+                cx.global::<Counter>();
+            }
+            self.renders.set(self.renders.get() + 1);
+            Empty
+        }
+    }
+
+    struct CachingView {
+        children: Vec<AnyView>,
+    }
+
+    impl Render for CachingView {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div().children(
+                self.children
+                    .iter()
+                    .map(|child| child.clone().cached(StyleRefinement::default())),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_updating_a_global_redraws_views_that_read_it(cx: &mut TestAppContext) {
+        cx.set_global(Counter(0));
+        let reading_renders = Rc::new(Cell::new(0));
+        let other_renders = Rc::new(Cell::new(0));
+        let mut other_view = None;
+        cx.add_window(|cx| {
+            let reading_view = cx.new_view(|_| GlobalReadingView {
+                reads_counter: true,
+                renders: reading_renders.clone(),
+            });
+            let view = cx.new_view(|_| GlobalReadingView {
+                reads_counter: false,
+                renders: other_renders.clone(),
+            });
+            other_view = Some(view.clone());
+            CachingView {
+                children: vec![reading_view.into(), view.into()],
+            }
+        });
+        let other_view = other_view.unwrap();
+        cx.run_until_parked();
+        let (reading_before, other_before) = (reading_renders.get(), other_renders.get());
+
+        // Only the view that read the global is redrawn.
+        cx.update_global::<Counter, _>(|counter, _| counter.0 += 1);
+        cx.run_until_parked();
+        assert_eq!(reading_renders.get(), reading_before + 1);
+        assert_eq!(other_renders.get(), other_before);
+
+        // Views reused from the previous frame are still redrawn when a global they read is
+        // updated.
+        other_view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        assert_eq!(reading_renders.get(), reading_before + 1);
+        assert_eq!(other_renders.get(), other_before + 1);
+
+        cx.update_global::<Counter, _>(|counter, _| counter.0 += 1);
+        cx.run_until_parked();
+        assert_eq!(reading_renders.get(), reading_before + 2);
+        assert_eq!(other_renders.get(), other_before + 1);
+    }
+
+    struct RedrawingView {
+        renders: Rc<Cell<usize>>,
+    }
+
+    impl Render for RedrawingView {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            // Frames can't be drawn from within another.
+            assert_eq!(cx.draw_frame(), None);
+            self.renders.set(self.renders.get() + 1);
+            Empty
+        }
+    }
+
+    #[gpui::test]
+    fn test_drawing_a_frame_synchronously(cx: &mut TestAppContext) {
+        let renders = Rc::new(Cell::new(0));
+        let window = cx.add_window({
+            let renders = renders.clone();
+            move |_| RedrawingView { renders }
+        });
+        cx.run_until_parked();
+        let renders_before = renders.get();
+
+        window
+            .update(cx, |_, cx| {
+                cx.notify();
+                assert!(cx.draw_frame().is_some());
+                assert_eq!(renders.get(), renders_before + 1);
+
+                // Frame metrics aren't left on once the frame is drawn.
+                assert_eq!(cx.last_frame_metrics(), None);
+            })
+            .unwrap();
+    }
+
+    struct RetainingView {
+        ids: Vec<&'static str>,
+        renders: Rc<RefCell<Vec<(&'static str, usize)>>>,
+    }
+
+    impl Render for RetainingView {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            for &id in &self.ids {
+                let count = cx.with_retained_state(id, |count: &mut Option<usize>, _| {
+                    let count = count.get_or_insert(0);
+                    *count += 1;
+                    *count
+                });
+                self.renders.borrow_mut().push((id, count));
+            }
+            Empty
+        }
+    }
+
+    #[gpui::test]
+    fn test_retained_state_is_dropped_when_its_id_is_not_drawn(cx: &mut TestAppContext) {
+        let renders = Rc::new(RefCell::new(Vec::new()));
+        let window = cx.add_window({
+            let renders = renders.clone();
+            move |_| RetainingView {
+                ids: vec!["a", "b"],
+                renders,
+            }
+        });
+        cx.run_until_parked();
+        renders.borrow_mut().clear();
+
+        window
+            .update(cx, |view, cx| {
+                view.ids = vec!["a"];
+                cx.notify();
+            })
+            .unwrap();
+        cx.run_until_parked();
+        let (_, a_count) = renders.borrow_mut().drain(..).last().unwrap();
+        assert!(a_count > 1);
+
+        window
+            .update(cx, |view, cx| {
+                view.ids = vec!["a", "b"];
+                cx.notify();
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(
+            renders.borrow_mut().drain(..).collect::<Vec<_>>(),
+            vec![("a", a_count + 1), ("b", 1)]
+        );
+    }
+
+    #[gpui::test]
+    fn test_release_callbacks_run_after_entity_is_dropped(cx: &mut TestAppContext) {
+        let released = Rc::new(Cell::new(false));
+        let window = cx.add_window({
+            let released = released.clone();
+            move |cx| {
+                cx.on_release(move |_: &mut ObservingView, _, cx| {
+                    released.set(true);
+                    // Release callbacks can update the app, as they're not run during the drop.
+                    cx.set_global(Counter(1));
+                })
+                .detach();
+                ObservingView { notifications: 0 }
+            }
+        });
+        cx.run_until_parked();
+
+        window
+            .update(cx, |_, cx| {
+                cx.remove_window();
+                assert!(!released.get());
+            })
+            .unwrap();
+        assert!(released.get());
+        assert_eq!(cx.read_global::<Counter, _>(|counter, _| counter.0), 1);
+    }
+
+    #[gpui::test]
+    fn test_closing_a_window_closes_the_windows_anchored_to_it(cx: &mut TestAppContext) {
+        let open_window = |parent: Option<AnyWindowHandle>, cx: &mut TestAppContext| {
+            cx.update(|cx| {
+                cx.open_window(
+                    WindowOptions {
+                        parent,
+                        ..Default::default()
+                    },
+                    |cx| cx.new_view(|_| ObservingView { notifications: 0 }),
+                )
+                .unwrap()
+            })
+        };
+        let parent = open_window(None, cx);
+        let child = open_window(Some(parent.into()), cx);
+        let grandchild = open_window(Some(child.into()), cx);
+        let unrelated = open_window(None, cx);
+        cx.run_until_parked();
+
+        child.update(cx, |_, cx| cx.remove_window()).unwrap();
+        assert!(grandchild.update(cx, |_, _| ()).is_err());
+        assert!(parent.update(cx, |_, _| ()).is_ok());
+
+        parent.update(cx, |_, cx| cx.remove_window()).unwrap();
+        assert!(cx.update(|cx| cx.windows()) == [AnyWindowHandle::from(unrelated)]);
+    }
+}
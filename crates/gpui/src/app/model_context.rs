@@ -90,6 +90,10 @@ impl<'a, T: 'static> ModelContext<'a, T> {
     }
 
     /// Register a callback to be invoked when GPUI releases this model.
+    ///
+    /// The callback runs once the last handle to the model has been dropped, when
+    /// effects are next flushed rather than during the drop, so it can safely update
+    /// the app.
     pub fn on_release(
         &mut self,
         on_release: impl FnOnce(&mut T, &mut AppContext) + 'static,
@@ -272,6 +272,13 @@ impl TestAppContext {
         self.test_platform.simulate_prompt_answer(button_ix);
     }
 
+    /// Simulates system UI such as the menu bar and dock covering the display, leaving only
+    /// `visible_bounds` of it visible.
+    pub fn simulate_display_visible_bounds(&self, visible_bounds: Bounds<Pixels>) {
+        self.test_platform
+            .set_display_visible_bounds(visible_bounds);
+    }
+
     /// Returns true if there's an alert dialog open.
     pub fn has_pending_prompt(&self) -> bool {
         self.test_platform.has_pending_prompt()
@@ -767,6 +774,12 @@ impl VisualTestContext {
         self.update(|cx| cx.window.rendered_frame.debug_bounds.get(selector).copied())
     }
 
+    /// Returns the primitives painted in the window's last frame as JSON, so that tests can
+    /// compare what a view paints against a snapshot.
+    pub fn scene_snapshot(&mut self) -> serde_json::Value {
+        self.update(|cx| serde_json::to_value(&cx.window.rendered_frame.scene).unwrap())
+    }
+
     /// Draw an element to the window. Useful for simulating events or actions
     pub fn draw<E>(
         &mut self,
@@ -1,10 +1,16 @@
 use anyhow::{bail, Context};
-use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::{
+    de::{self, Deserialize, Deserializer, Visitor},
+    Serialize, Serializer,
+};
+use smallvec::SmallVec;
 use std::{
     fmt,
     hash::{Hash, Hasher},
 };
 
+use crate::{Radians, MAX_GRADIENT_STOPS};
+
 /// Convert an RGB hex color code number to a color type
 pub fn rgb(hex: u32) -> Rgba {
     let r = ((hex >> 16) & 0xFF) as f32 / 255.0;
@@ -57,6 +63,18 @@ impl Rgba {
             };
         }
     }
+
+    /// Formats this color as a `#rrggbbaa` hex string, which can be parsed back into an [`Rgba`].
+    pub fn to_hex_string(&self) -> String {
+        let component = |value: f32| (value.clamp(0., 1.) * 255.).round() as u8;
+        format!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            component(self.r),
+            component(self.g),
+            component(self.b),
+            component(self.a)
+        )
+    }
 }
 
 impl From<Rgba> for u32 {
@@ -83,6 +101,15 @@ impl<'de> Visitor<'de> for RgbaVisitor {
     }
 }
 
+impl Serialize for Rgba {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_hex_string())
+    }
+}
+
 impl<'de> Deserialize<'de> for Rgba {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         deserializer.deserialize_str(RgbaVisitor)
@@ -456,6 +483,15 @@ impl Hsla {
         }
     }
 
+    /// Returns a new HSLA color with its alpha multiplied by the given factor, which is clamped
+    /// between 0.0 (fully transparent) and 1.0 (unchanged).
+    pub fn opacity(&self, factor: f32) -> Self {
+        Hsla {
+            a: self.a * factor.clamp(0., 1.),
+            ..*self
+        }
+    }
+
     /// Fade out the color by a given factor. This factor should be between 0.0 and 1.0.
     /// Where 0.0 will leave the color unchanged, and 1.0 will completely fade out the color.
     pub fn fade_out(&mut self, factor: f32) {
@@ -501,6 +537,15 @@ impl From<Rgba> for Hsla {
     }
 }
 
+impl Serialize for Hsla {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Rgba::from(*self).serialize(serializer)
+    }
+}
+
 impl<'de> Deserialize<'de> for Hsla {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -514,6 +559,54 @@ impl<'de> Deserialize<'de> for Hsla {
     }
 }
 
+/// A gradient that blends between colors along a straight line, for use with
+/// [`WindowContext::paint_gradient`](crate::WindowContext::paint_gradient).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinearGradient {
+    angle: Radians,
+    stops: SmallVec<[(f32, Hsla); MAX_GRADIENT_STOPS]>,
+}
+
+impl LinearGradient {
+    /// Creates a gradient with no stops along a line at the given angle.
+    ///
+    /// Like in CSS, an angle of zero paints from bottom to top, and angles increase clockwise.
+    pub fn new(angle: Radians) -> Self {
+        Self {
+            angle,
+            stops: SmallVec::new(),
+        }
+    }
+
+    /// Adds a stop with the given color, where an offset of zero is the start of the gradient
+    /// and an offset of one is its end.
+    ///
+    /// Up to [`MAX_GRADIENT_STOPS`] stops are painted, and any others are ignored.
+    pub fn stop(mut self, offset: f32, color: impl Into<Hsla>) -> Self {
+        self.stops.push((offset, color.into()));
+        self
+    }
+
+    /// Returns the angle of the line along which the colors are blended.
+    pub fn angle(&self) -> Radians {
+        self.angle
+    }
+
+    /// Returns the offsets and colors of the gradient's stops, in the order they were added.
+    pub fn stops(&self) -> &[(f32, Hsla)] {
+        &self.stops
+    }
+}
+
+/// Creates a gradient at the given angle that blends from one color to another.
+pub fn linear_gradient(
+    angle: Radians,
+    from: impl Into<Hsla>,
+    to: impl Into<Hsla>,
+) -> LinearGradient {
+    LinearGradient::new(angle).stop(0., from).stop(1., to)
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -561,4 +654,15 @@ mod tests {
 
         assert_eq!(actual, rgba(0xdeadbeef))
     }
+
+    #[test]
+    fn test_rgba_to_hex_string_round_trips() {
+        let color = rgba(0xdeadbeef);
+
+        assert_eq!(color.to_hex_string(), "#deadbeef");
+        assert_eq!(
+            Rgba::try_from(color.to_hex_string().as_str()).unwrap(),
+            color
+        );
+    }
 }
@@ -125,10 +125,8 @@ impl Element for Anchored {
             bounds,
         );
 
-        let limits = Bounds {
-            origin: Point::default(),
-            size: cx.viewport_size(),
-        };
+        // Keep clear of system UI such as the dock where the window extends underneath it.
+        let limits = cx.visible_viewport_bounds();
 
         if self.fit_mode == AnchoredFitMode::SwitchAnchor {
             let mut anchor_corner = self.anchor_corner;
@@ -310,3 +308,59 @@ impl AnchorCorner {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as gpui, anchored, div, point, px, size, Bounds, InteractiveElement, IntoElement,
+        ParentElement, Pixels, Point, Render, Styled, TestAppContext, ViewContext,
+    };
+
+    struct MenuView {
+        position: Point<Pixels>,
+    }
+
+    impl Render for MenuView {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size_full().child(
+                anchored()
+                    .position(self.position)
+                    .snap_to_window()
+                    .child(div().id("menu").size(px(50.))),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn test_anchored_element_stays_within_the_visible_bounds_of_the_display(
+        cx: &mut TestAppContext,
+    ) {
+        // A menu bar covers the top of the 1920x1080 display and a dock its bottom, while the
+        // window covers all of it.
+        cx.simulate_display_visible_bounds(Bounds::from_corners(
+            point(px(0.), px(25.)),
+            point(px(1920.), px(1000.)),
+        ));
+        let (view, cx) = cx.add_window_view(|_| MenuView {
+            position: point(px(10.), px(980.)),
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            cx.update(|cx| cx.measured_bounds("menu")),
+            Some(Bounds::new(
+                point(px(10.), px(950.)),
+                size(px(50.), px(50.))
+            ))
+        );
+
+        view.update(cx, |view, cx| {
+            view.position = point(px(10.), px(5.));
+            cx.notify();
+        });
+        cx.run_until_parked();
+        assert_eq!(
+            cx.update(|cx| cx.measured_bounds("menu")),
+            Some(Bounds::new(point(px(10.), px(25.)), size(px(50.), px(50.))))
+        );
+    }
+}
@@ -0,0 +1,383 @@
+use crate::{
+    AnyElement, Bounds, ContentMask, Element, ElementId, GlobalElementId, IntoElement, LayoutId,
+    PaintIndex, Pixels, Point, PrepaintStateIndex, Size, Style, TextStyle, WindowContext,
+};
+use std::ops::Range;
+
+/// Builds a `Cached` element, which renders its child with the given closure only when the child
+/// can't be reused from the previous frame.
+///
+/// The child is reused, skipping its rendering, layout, prepaint and paint, as long as
+/// `state_hash` is unchanged and the element is given the same size as in the previous frame.
+/// The hash should therefore account for all of the state the child is rendered from.
+///
+/// When only the element's position has changed, such as when it's scrolled, what the child drew
+/// is moved with it. This needs the element to be entirely within its content mask in both
+/// frames and to have moved by a whole number of device pixels, so that text stays sharp.
+/// Otherwise the child is drawn again. State that elements inside the child keep between frames,
+/// such as the bounds recorded by a [`ScrollHandle`](crate::ScrollHandle), isn't moved with it.
+pub fn cached<E: IntoElement>(
+    id: impl Into<ElementId>,
+    state_hash: u64,
+    render: impl FnOnce(&mut WindowContext) -> E + 'static,
+) -> Cached {
+    Cached {
+        id: id.into(),
+        state_hash,
+        render: Some(Box::new(move |cx| render(cx).into_any_element())),
+    }
+}
+
+/// An element that reuses the layout and painting of its child from the previous frame, while
+/// the state it was rendered from is unchanged.
+///
+/// While it's reused, the child is laid out as a leaf with the size it had in the previous frame,
+/// so it should be sized by its own contents or by its parent rather than grown by flex layout.
+pub struct Cached {
+    id: ElementId,
+    state_hash: u64,
+    render: Option<Box<dyn FnOnce(&mut WindowContext) -> AnyElement>>,
+}
+
+/// How often the [`Cached`] elements in a window have been reused from the previous frame.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ElementCacheMetrics {
+    /// The number of times a cached element was reused.
+    pub hits: usize,
+    /// The number of times a cached element had to be drawn again.
+    pub misses: usize,
+}
+
+struct CachedState {
+    state_hash: u64,
+    /// The size of the child when it was last laid out.
+    size: Size<Pixels>,
+    bounds: Bounds<Pixels>,
+    /// How far the child has moved since it was last painted, when it's reused at a new position.
+    paint_offset: Point<Pixels>,
+    content_mask: ContentMask<Pixels>,
+    text_style: TextStyle,
+    prepaint_range: Range<PrepaintStateIndex>,
+    paint_range: Range<PaintIndex>,
+}
+
+impl Cached {
+    fn render(&mut self, cx: &mut WindowContext) -> AnyElement {
+        (self.render.take().expect("cached element rendered twice"))(cx)
+    }
+}
+
+impl Element for Cached {
+    type RequestLayoutState = Option<AnyElement>;
+    type PrepaintState = Option<AnyElement>;
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn request_layout(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let cached_size =
+            cx.with_element_state::<Option<CachedState>, _>(id.unwrap(), |state, cx| {
+                let state = state.flatten();
+                let size = state
+                    .as_ref()
+                    .filter(|state| state.state_hash == self.state_hash && !cx.window.refreshing)
+                    .map(|state| state.size);
+                (size, state)
+            });
+
+        match cached_size {
+            Some(size) => {
+                let layout_id =
+                    cx.request_measured_layout(Style::default(), move |known_dimensions, _, _| {
+                        Size {
+                            width: known_dimensions.width.unwrap_or(size.width),
+                            height: known_dimensions.height.unwrap_or(size.height),
+                        }
+                    });
+                (layout_id, None)
+            }
+            None => {
+                let mut element = self.render(cx);
+                let layout_id = element.request_layout(cx);
+                (layout_id, Some(element))
+            }
+        }
+    }
+
+    fn prepaint(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        element: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        cx.with_element_state::<Option<CachedState>, _>(id.unwrap(), |state, cx| {
+            let content_mask = cx.content_mask();
+            let text_style = cx.text_style();
+
+            if let Some(mut state) = state.flatten() {
+                if element.is_none()
+                    && state.content_mask == content_mask
+                    && state.text_style == text_style
+                {
+                    let prepaint_start = cx.prepaint_index();
+                    let reused = if state.bounds == bounds {
+                        cx.reuse_prepaint(state.prepaint_range.clone());
+                        state.paint_offset = Point::default();
+                        true
+                    } else if can_move(&state, bounds, cx) {
+                        let offset = bounds.origin - state.bounds.origin;
+                        cx.reuse_prepaint_with_offset(
+                            state.prepaint_range.clone(),
+                            offset,
+                            &content_mask,
+                        );
+                        state.bounds = bounds;
+                        state.paint_offset = offset;
+                        true
+                    } else {
+                        false
+                    };
+
+                    if reused {
+                        let prepaint_end = cx.prepaint_index();
+                        state.prepaint_range = prepaint_start..prepaint_end;
+                        cx.window.element_cache_metrics.hits += 1;
+                        return (None, Some(state));
+                    }
+                }
+            }
+
+            cx.window.element_cache_metrics.misses += 1;
+            let prepaint_start = cx.prepaint_index();
+            let (element, size) = match element.take() {
+                Some(mut element) => {
+                    element.prepaint(cx);
+                    (element, bounds.size)
+                }
+                None => {
+                    // The child was laid out with its previous size, but it's been given different
+                    // bounds, so it's laid out again within them. If it no longer fits, the next
+                    // frame lays it out with its new size.
+                    let mut element = self.render(cx);
+                    let size = element.layout_as_root(bounds.size.into(), cx);
+                    element.prepaint_at(bounds.origin, cx);
+                    if size != bounds.size {
+                        cx.request_animation_frame();
+                    }
+                    (element, size)
+                }
+            };
+            let prepaint_end = cx.prepaint_index();
+
+            (
+                Some(element),
+                Some(CachedState {
+                    state_hash: self.state_hash,
+                    size,
+                    bounds,
+                    paint_offset: Point::default(),
+                    content_mask,
+                    text_style,
+                    prepaint_range: prepaint_start..prepaint_end,
+                    paint_range: PaintIndex::default()..PaintIndex::default(),
+                }),
+            )
+        })
+    }
+
+    fn paint(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        element: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        cx.with_element_state::<Option<CachedState>, _>(id.unwrap(), |state, cx| {
+            let mut state = state.flatten().unwrap();
+            let paint_start = cx.paint_index();
+
+            if let Some(element) = element {
+                element.paint(cx);
+            } else if state.paint_offset == Point::default() {
+                cx.reuse_paint(state.paint_range.clone());
+            } else {
+                cx.reuse_paint_with_offset(
+                    state.paint_range.clone(),
+                    state.paint_offset,
+                    &state.content_mask,
+                );
+            }
+
+            let paint_end = cx.paint_index();
+            state.paint_range = paint_start..paint_end;
+            ((), Some(state))
+        })
+    }
+}
+
+/// Returns whether the child drawn with the given state can be reused after moving it to `bounds`.
+fn can_move(state: &CachedState, bounds: Bounds<Pixels>, cx: &WindowContext) -> bool {
+    let mask = &state.content_mask.bounds;
+    let within_mask = |bounds: &Bounds<Pixels>| bounds.intersect(mask) == *bounds;
+    let offset = (bounds.origin - state.bounds.origin).scale(cx.scale_factor());
+    let is_whole = |pixels: f32| (pixels - pixels.round()).abs() < 0.01;
+
+    state.bounds.size == bounds.size
+        && within_mask(&state.bounds)
+        && within_mask(&bounds)
+        && is_whole(offset.x.0)
+        && is_whole(offset.y.0)
+        && cx.can_reuse_with_offset(&state.prepaint_range, &state.paint_range)
+}
+
+impl IntoElement for Cached {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use crate::{
+        self as gpui, cached, div, point, px, size, ElementCacheMetrics, InteractiveElement,
+        IntoElement, Modifiers, MouseButton, ParentElement, Render, ScrollHandle,
+        StatefulInteractiveElement, Styled, TestAppContext, ViewContext,
+    };
+
+    struct CachingView {
+        state_hash: u64,
+        renders: Rc<Cell<usize>>,
+    }
+
+    impl Render for CachingView {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let renders = self.renders.clone();
+            div()
+                .size_full()
+                .child(cached("cached", self.state_hash, move |_| {
+                    renders.set(renders.get() + 1);
+                    div().w(px(50.)).h(px(20.))
+                }))
+        }
+    }
+
+    #[gpui::test]
+    fn test_cached_element_is_reused_while_its_hash_is_unchanged(cx: &mut TestAppContext) {
+        let renders = Rc::new(Cell::new(0));
+        let (view, cx) = cx.add_window_view(|_| CachingView {
+            state_hash: 1,
+            renders: renders.clone(),
+        });
+        cx.simulate_resize(size(px(100.), px(100.)));
+        cx.run_until_parked();
+        let initial_renders = renders.get();
+        let metrics = cx.update(|cx| cx.element_cache_metrics());
+
+        // Redrawing the view with the same hash reuses the cached child.
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        assert_eq!(renders.get(), initial_renders);
+        assert_eq!(
+            cx.update(|cx| cx.element_cache_metrics()),
+            ElementCacheMetrics {
+                hits: metrics.hits + 1,
+                misses: metrics.misses,
+            }
+        );
+
+        // Changing the hash renders it again.
+        view.update(cx, |view, cx| {
+            view.state_hash = 2;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        assert_eq!(renders.get(), initial_renders + 1);
+        assert_eq!(
+            cx.update(|cx| cx.element_cache_metrics()),
+            ElementCacheMetrics {
+                hits: metrics.hits + 1,
+                misses: metrics.misses + 1,
+            }
+        );
+    }
+
+    struct ScrollingView {
+        scroll_handle: ScrollHandle,
+        renders: Rc<Cell<usize>>,
+        clicks: Rc<Cell<usize>>,
+    }
+
+    impl Render for ScrollingView {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let renders = self.renders.clone();
+            let clicks = self.clicks.clone();
+            div()
+                .id("list")
+                .size_full()
+                .overflow_y_scroll()
+                .track_scroll(&self.scroll_handle)
+                .child(div().h(px(40.)))
+                .child(cached("cached", 1, move |_| {
+                    renders.set(renders.get() + 1);
+                    div()
+                        .id("item")
+                        .w(px(50.))
+                        .h(px(20.))
+                        .on_mouse_down(MouseButton::Left, move |_, _| clicks.set(clicks.get() + 1))
+                }))
+                .child(div().h(px(200.)))
+        }
+    }
+
+    #[gpui::test]
+    fn test_cached_element_is_reused_when_scrolled(cx: &mut TestAppContext) {
+        let scroll_handle = ScrollHandle::new();
+        let renders = Rc::new(Cell::new(0));
+        let clicks = Rc::new(Cell::new(0));
+        let (view, cx) = cx.add_window_view(|_| ScrollingView {
+            scroll_handle: scroll_handle.clone(),
+            renders: renders.clone(),
+            clicks: clicks.clone(),
+        });
+        cx.simulate_resize(size(px(100.), px(100.)));
+        cx.run_until_parked();
+        let initial_renders = renders.get();
+        let metrics = cx.update(|cx| cx.element_cache_metrics());
+
+        // Scrolling moves the cached child from 40px down to 10px down without drawing it again.
+        scroll_handle.set_offset(point(px(0.), px(-30.)));
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        assert_eq!(renders.get(), initial_renders);
+        assert_eq!(
+            cx.update(|cx| cx.element_cache_metrics()),
+            ElementCacheMetrics {
+                hits: metrics.hits + 1,
+                misses: metrics.misses,
+            }
+        );
+
+        // Its hitbox moved with it, so it's clicked where it's now shown, and not where it was.
+        cx.simulate_click(point(px(25.), px(50.)), Modifiers::none());
+        assert_eq!(clicks.get(), 0);
+        cx.simulate_click(point(px(25.), px(20.)), Modifiers::none());
+        assert_eq!(clicks.get(), 1);
+
+        // Scrolling it partly out of view draws it again, as it's no longer entirely visible.
+        scroll_handle.set_offset(point(px(0.), px(-50.)));
+        view.update(cx, |_, cx| cx.notify());
+        cx.run_until_parked();
+        assert_eq!(renders.get(), initial_renders + 1);
+    }
+}
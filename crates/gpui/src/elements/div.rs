@@ -16,10 +16,10 @@
 //! constructed by combining these two systems into an all-in-one element.
 
 use crate::{
-    point, px, size, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, AppContext, Bounds,
-    ClickEvent, DispatchPhase, Element, ElementId, FocusHandle, Global, GlobalElementId, Hitbox,
-    HitboxId, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, LayoutId,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    point, px, size, AccessibilityNode, Action, AnyDrag, AnyElement, AnyTooltip, AnyView,
+    AppContext, Bounds, ClickEvent, DispatchPhase, Element, ElementId, FocusHandle, Global,
+    GlobalElementId, Hitbox, HitboxId, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent,
+    LayoutId, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
     ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size, Style,
     StyleRefinement, Styled, Task, TooltipId, View, Visibility, WindowContext,
 };
@@ -416,6 +416,23 @@ impl Interactivity {
         ));
     }
 
+    /// Bind the given callback to the active drag of the given type being cancelled, such as by
+    /// pressing escape or by the window being deactivated, whether or not the drag started on this element
+    /// The imperative API equivalent to [`InteractiveElement::on_drag_cancel`]
+    ///
+    /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to the view state from this callback.
+    pub fn on_drag_cancel<T: 'static>(
+        &mut self,
+        listener: impl Fn(&T, &mut WindowContext) + 'static,
+    ) {
+        self.drag_cancel_listeners.push((
+            TypeId::of::<T>(),
+            Box::new(move |dragged_value, cx| {
+                listener(dragged_value.downcast_ref().unwrap(), cx);
+            }),
+        ));
+    }
+
     /// Use the given predicate to determine whether or not a drop event should be dispatched to this element
     /// The imperative API equivalent to [`InteractiveElement::can_drop`]
     pub fn can_drop(&mut self, predicate: impl Fn(&dyn Any, &mut WindowContext) -> bool + 'static) {
@@ -558,6 +575,13 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Sets whether keystrokes dispatched to this element skip the keymap, so that its key down
+    /// listeners receive every keystroke instead of the actions bound to them.
+    fn capture_keystrokes(mut self, capture: bool) -> Self {
+        self.interactivity().captures_keystrokes = capture;
+        self
+    }
+
     /// Apply the given style to this element when the mouse hovers over it
     fn hover(mut self, f: impl FnOnce(StyleRefinement) -> StyleRefinement) -> Self {
         debug_assert!(
@@ -863,6 +887,19 @@ pub trait InteractiveElement: Sized {
         self
     }
 
+    /// Bind the given callback to the active drag of the given type being cancelled, such as by
+    /// pressing escape or by the window being deactivated, whether or not the drag started on this element
+    /// The fluent API equivalent to [`Interactivity::on_drag_cancel`]
+    ///
+    /// See [`ViewContext::listener`](crate::ViewContext::listener) to get access to the view state from this callback.
+    fn on_drag_cancel<T: 'static>(
+        mut self,
+        listener: impl Fn(&T, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.interactivity().on_drag_cancel(listener);
+        self
+    }
+
     /// Use the given predicate to determine whether or not a drop event should be dispatched to this element
     /// The fluent API equivalent to [`Interactivity::can_drop`]
     fn can_drop(
@@ -879,6 +916,35 @@ pub trait InteractiveElement: Sized {
         self.interactivity().occlude_mouse();
         self
     }
+
+    /// Extend the area in which this element receives mouse events by the given amount on each
+    /// side of its bounds, such as to make a small control easier to click. The element is still
+    /// painted within its bounds.
+    ///
+    /// Like any other hitbox, the extended area is covered by the elements painted after this one,
+    /// so it doesn't take clicks from neighboring elements that block the mouse.
+    fn hit_area_outset(mut self, outset: Pixels) -> Self {
+        self.interactivity().hit_area_outset = outset;
+        self
+    }
+
+    /// Describe this element to assistive technologies. The node is recorded with the element's
+    /// bounds each time it's painted, see [`WindowContext::accessibility_snapshot`].
+    fn accessibility(mut self, node: AccessibilityNode) -> Self {
+        self.interactivity().accessibility = Some(node);
+        self
+    }
+
+    /// Skip painting this element and its children while its bounds fall entirely outside the
+    /// current content mask, such as when it's scrolled out of view in a long list. The element
+    /// still takes part in layout, so its space stays reserved and scroll positions are unaffected.
+    ///
+    /// A culled element doesn't register its mouse or keyboard listeners, so this should only be
+    /// used for elements that don't paint outside of their bounds or handle input while hidden.
+    fn cull_offscreen(mut self) -> Self {
+        self.interactivity().cull_offscreen = true;
+        self
+    }
 }
 
 /// A trait for elements that want to use the standard GPUI interactivity features
@@ -1048,6 +1114,8 @@ pub(crate) type DragListener = Box<dyn Fn(&dyn Any, &mut WindowContext) -> AnyVi
 
 type DropListener = Box<dyn Fn(&dyn Any, &mut WindowContext) + 'static>;
 
+type DragCancelListener = Box<dyn Fn(&dyn Any, &mut WindowContext) + 'static>;
+
 type CanDropPredicate = Box<dyn Fn(&dyn Any, &mut WindowContext) -> bool + 'static>;
 
 pub(crate) struct TooltipBuilder {
@@ -1242,6 +1310,7 @@ pub struct Interactivity {
     pub(crate) tooltip_id: Option<TooltipId>,
     pub(crate) content_size: Size<Pixels>,
     pub(crate) key_context: Option<KeyContext>,
+    pub(crate) captures_keystrokes: bool,
     pub(crate) focusable: bool,
     pub(crate) tracked_focus_handle: Option<FocusHandle>,
     pub(crate) tracked_scroll_handle: Option<ScrollHandle>,
@@ -1270,12 +1339,16 @@ pub struct Interactivity {
     pub(crate) modifiers_changed_listeners: Vec<ModifiersChangedListener>,
     pub(crate) action_listeners: Vec<(TypeId, ActionListener)>,
     pub(crate) drop_listeners: Vec<(TypeId, DropListener)>,
+    pub(crate) drag_cancel_listeners: Vec<(TypeId, DragCancelListener)>,
     pub(crate) can_drop_predicate: Option<CanDropPredicate>,
     pub(crate) click_listeners: Vec<ClickListener>,
     pub(crate) drag_listener: Option<(Box<dyn Any>, DragListener)>,
     pub(crate) hover_listener: Option<Box<dyn Fn(&bool, &mut WindowContext)>>,
     pub(crate) tooltip_builder: Option<TooltipBuilder>,
     pub(crate) occlude_mouse: bool,
+    pub(crate) hit_area_outset: Pixels,
+    pub(crate) accessibility: Option<AccessibilityNode>,
+    pub(crate) cull_offscreen: bool,
 
     #[cfg(debug_assertions)]
     pub(crate) location: Option<core::panic::Location<'static>>,
@@ -1387,7 +1460,9 @@ impl Interactivity {
                 cx.with_text_style(style.text_style().cloned(), |cx| {
                     cx.with_content_mask(style.overflow_mask(bounds, cx.rem_size()), |cx| {
                         let hitbox = if self.should_insert_hitbox(&style) {
-                            Some(cx.insert_hitbox(bounds, self.occlude_mouse))
+                            let mut hit_bounds = bounds;
+                            hit_bounds.dilate(self.hit_area_outset);
+                            Some(cx.insert_hitbox(hit_bounds, self.occlude_mouse))
                         } else {
                             None
                         };
@@ -1416,6 +1491,7 @@ impl Interactivity {
             || !self.scroll_wheel_listeners.is_empty()
             || self.drag_listener.is_some()
             || !self.drop_listeners.is_empty()
+            || !self.drag_cancel_listeners.is_empty()
             || self.tooltip_builder.is_some()
     }
 
@@ -1450,6 +1526,9 @@ impl Interactivity {
                         .to_pixels(bounds.size.height.into(), rem_size),
             );
             let scroll_max = (self.content_size + padding_size - bounds.size).max(&Size::default());
+            if let Some(scroll_handle) = &self.tracked_scroll_handle {
+                scroll_handle.0.borrow_mut().max_offset = scroll_max;
+            }
             // Clamp scroll offset in case scroll max is smaller now (e.g., if children
             // were removed or the bounds became larger).
             let mut scroll_offset = scroll_offset.borrow_mut();
@@ -1478,6 +1557,10 @@ impl Interactivity {
         f: impl FnOnce(&Style, &mut WindowContext),
     ) {
         self.hovered = hitbox.map(|hitbox| hitbox.is_hovered(cx));
+        if self.cull_offscreen && !bounds.intersects(&cx.content_mask().bounds) {
+            return;
+        }
+
         cx.with_optional_element_state::<InteractiveElementState, _>(
             global_id,
             |element_state, cx| {
@@ -1494,12 +1577,24 @@ impl Interactivity {
                         .insert(debug_selector.clone(), bounds);
                 }
 
+                #[cfg(any(feature = "test-support", test))]
+                if let Some(element_id) = &self.element_id {
+                    cx.window
+                        .next_frame
+                        .measured_bounds
+                        .push((element_id.clone(), bounds));
+                }
+
                 self.paint_hover_group_handler(cx);
 
                 if style.visibility == Visibility::Hidden {
                     return ((), element_state);
                 }
 
+                if let Some(node) = self.accessibility.take() {
+                    cx.insert_accessibility_node(node, bounds);
+                }
+
                 style.paint(bounds, cx, |cx: &mut WindowContext| {
                     cx.with_text_style(style.text_style().cloned(), |cx| {
                         cx.with_content_mask(style.overflow_mask(bounds, cx.rem_size()), |cx| {
@@ -1752,6 +1847,22 @@ impl Interactivity {
             });
         }
 
+        let drag_cancel_listeners = mem::take(&mut self.drag_cancel_listeners);
+        if !drag_cancel_listeners.is_empty() {
+            cx.on_drag_cancel(move |cx| {
+                let Some(drag) = cx.active_drag.take() else {
+                    return;
+                };
+                let drag_state_type = drag.value.as_ref().type_id();
+                for (cancel_state_type, listener) in &drag_cancel_listeners {
+                    if *cancel_state_type == drag_state_type {
+                        listener(drag.value.as_ref(), cx);
+                    }
+                }
+                cx.active_drag = Some(drag);
+            });
+        }
+
         if let Some(element_state) = element_state {
             if !click_listeners.is_empty() || drag_listener.is_some() {
                 let pending_mouse_down = element_state
@@ -2006,6 +2117,9 @@ impl Interactivity {
         if let Some(context) = self.key_context.clone() {
             cx.set_key_context(context);
         }
+        if self.captures_keystrokes {
+            cx.capture_keystrokes();
+        }
 
         for listener in key_down_listeners {
             cx.on_key_event(move |event: &KeyDownEvent, phase, cx| {
@@ -2447,6 +2561,7 @@ struct ScrollHandleState {
     offset: Rc<RefCell<Point<Pixels>>>,
     bounds: Bounds<Pixels>,
     child_bounds: Vec<Bounds<Pixels>>,
+    max_offset: Size<Pixels>,
     requested_scroll_top: Option<(usize, Pixels)>,
     overflow: Point<Overflow>,
 }
@@ -2498,6 +2613,12 @@ impl ScrollHandle {
         self.0.borrow().bounds
     }
 
+    /// Get how far the content can be scrolled in each direction, which is zero along the axes
+    /// where it doesn't overflow the element.
+    pub fn max_offset(&self) -> Size<Pixels> {
+        self.0.borrow().max_offset
+    }
+
     /// Set the bounds into which this child is painted
     pub(super) fn set_bounds(&self, bounds: Bounds<Pixels>) {
         self.0.borrow_mut().bounds = bounds;
@@ -1,5 +1,6 @@
 mod anchored;
 mod animation;
+mod cached;
 mod canvas;
 mod deferred;
 mod div;
@@ -11,6 +12,7 @@ mod uniform_list;
 
 pub use anchored::*;
 pub use animation::*;
+pub use cached::*;
 pub use canvas::*;
 pub use deferred::*;
 pub use div::*;
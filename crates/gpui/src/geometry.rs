@@ -58,7 +58,9 @@ pub trait Along {
 /// let point = Point { x: 10, y: 20 };
 /// println!("{:?}", point); // Outputs: Point { x: 10, y: 20 }
 /// ```
-#[derive(Refineable, Default, Add, AddAssign, Sub, SubAssign, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(
+    Refineable, Default, Add, AddAssign, Sub, SubAssign, Copy, Debug, PartialEq, Eq, Hash, Serialize,
+)]
 #[refineable(Debug)]
 #[repr(C)]
 pub struct Point<T: Default + Clone + Debug> {
@@ -694,7 +696,7 @@ impl Size<Length> {
 /// assert_eq!(bounds.origin, origin);
 /// assert_eq!(bounds.size, size);
 /// ```
-#[derive(Refineable, Clone, Default, Debug, Eq, PartialEq, Hash)]
+#[derive(Refineable, Clone, Default, Debug, Eq, PartialEq, Hash, Serialize)]
 #[refineable(Debug)]
 #[repr(C)]
 pub struct Bounds<T: Clone + Default + Debug> {
@@ -1436,7 +1438,7 @@ impl<T: Clone + Debug + Copy + Default> Copy for Bounds<T> {}
 /// assert_eq!(edges.bottom, 30.0);
 /// assert_eq!(edges.left, 40.0);
 /// ```
-#[derive(Refineable, Clone, Default, Debug, Eq, PartialEq)]
+#[derive(Refineable, Clone, Default, Debug, Eq, PartialEq, Serialize)]
 #[refineable(Debug)]
 #[repr(C)]
 pub struct Edges<T: Clone + Default + Debug> {
@@ -1848,7 +1850,7 @@ impl From<f32> for Edges<Pixels> {
 /// Represents the corners of a box in a 2D space, such as border radius.
 ///
 /// Each field represents the size of the corner on one side of the box: `top_left`, `top_right`, `bottom_right`, and `bottom_left`.
-#[derive(Refineable, Clone, Default, Debug, Eq, PartialEq)]
+#[derive(Refineable, Clone, Default, Debug, Eq, PartialEq, Serialize)]
 #[refineable(Debug)]
 #[repr(C)]
 pub struct Corners<T: Clone + Default + Debug> {
@@ -1997,6 +1999,26 @@ impl Corners<Pixels> {
             .max(self.bottom_right)
             .max(self.bottom_left)
     }
+
+    /// Clamps each corner radius to half of the smaller side of a quad with the given size,
+    /// preventing adjacent corners from overlapping.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The size of the quad the corner radii apply to.
+    ///
+    /// # Returns
+    ///
+    /// Returns a new `Corners<Pixels>` where no radius exceeds half the quad's smaller side.
+    pub fn clamp_radii_for_quad_size(self, size: Size<Pixels>) -> Corners<Pixels> {
+        let max = size.width.min(size.height) / 2.;
+        Corners {
+            top_left: self.top_left.min(max),
+            top_right: self.top_right.min(max),
+            bottom_right: self.bottom_right.min(max),
+            bottom_left: self.bottom_left.min(max),
+        }
+    }
 }
 
 impl<T: Clone + Default + Debug> Corners<T> {
@@ -2447,7 +2469,20 @@ impl From<usize> for Pixels {
 /// affected by the device's scale factor, `DevicePixels` always correspond to real pixels on the
 /// display.
 #[derive(
-    Add, AddAssign, Clone, Copy, Default, Div, Eq, Hash, Ord, PartialEq, PartialOrd, Sub, SubAssign,
+    Add,
+    AddAssign,
+    Clone,
+    Copy,
+    Default,
+    Div,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Sub,
+    SubAssign,
+    Serialize,
 )]
 #[repr(transparent)]
 pub struct DevicePixels(pub(crate) i32);
@@ -2541,7 +2576,9 @@ impl From<usize> for DevicePixels {
 /// a single logical pixel may correspond to multiple physical pixels. By using `ScaledPixels`,
 /// dimensions and positions can be specified in a way that scales appropriately across different
 /// display resolutions.
-#[derive(Clone, Copy, Default, Add, AddAssign, Sub, SubAssign, Div, PartialEq, PartialOrd)]
+#[derive(
+    Clone, Copy, Default, Add, AddAssign, Sub, SubAssign, Div, PartialEq, PartialOrd, Serialize,
+)]
 #[repr(transparent)]
 pub struct ScaledPixels(pub(crate) f32);
 
@@ -3127,4 +3164,20 @@ mod tests {
         // Test Case 3: Bounds intersecting with themselves
         assert_eq!(bounds1.intersects(&bounds1), true);
     }
+
+    #[test]
+    fn test_corners_clamp_radii_for_quad_size() {
+        let corners = Corners {
+            top_left: px(4.),
+            top_right: px(10.),
+            bottom_right: px(30.),
+            bottom_left: px(100.),
+        };
+        let clamped = corners.clamp_radii_for_quad_size(size(px(100.), px(20.)));
+
+        assert_eq!(clamped.top_left, px(4.));
+        assert_eq!(clamped.top_right, px(10.));
+        assert_eq!(clamped.bottom_right, px(10.));
+        assert_eq!(clamped.bottom_left, px(10.));
+    }
 }
@@ -466,9 +466,13 @@ impl PlatformInput {
 #[cfg(test)]
 mod test {
 
+    use crate::util::FluentBuilder;
     use crate::{
-        self as gpui, div, FocusHandle, InteractiveElement, IntoElement, KeyBinding, Keystroke,
-        ParentElement, Render, TestAppContext, VisualContext,
+        self as gpui, black, canvas, div, point, px, size, AccessibilityNode, AccessibilityRole,
+        Bounds, DevicePixels, DragMoveEvent, EmptyView, FocusHandle, InteractiveElement,
+        IntoElement, KeyBinding, KeyDownEvent, Keystroke, Modifiers, MouseButton, ParentElement,
+        Render, ScrollHandle, StatefulInteractiveElement, Styled, TestAppContext, VisualContext,
+        VisualTestContext, CURSOR_BLINK_INTERVAL,
     };
 
     struct TestView {
@@ -535,4 +539,420 @@ mod test {
             })
             .unwrap();
     }
+
+    struct CapturingView {
+        keystrokes: Vec<Keystroke>,
+        saw_action: bool,
+        focus_handle: FocusHandle,
+    }
+
+    impl Render for CapturingView {
+        fn render(&mut self, cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
+            div()
+                .key_context("parent")
+                .on_action(
+                    cx.listener(|this: &mut CapturingView, _: &TestAction, _| {
+                        this.saw_action = true
+                    }),
+                )
+                .child(
+                    div()
+                        .track_focus(&self.focus_handle)
+                        .capture_keystrokes(true)
+                        .on_key_down(cx.listener(|this, event: &KeyDownEvent, _| {
+                            this.keystrokes.push(event.keystroke.clone())
+                        })),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn test_capture_keystrokes(cx: &mut TestAppContext) {
+        let window = cx.update(|cx| {
+            cx.open_window(Default::default(), |cx| {
+                cx.new_view(|cx| CapturingView {
+                    keystrokes: Vec::new(),
+                    saw_action: false,
+                    focus_handle: cx.focus_handle(),
+                })
+            })
+            .unwrap()
+        });
+
+        cx.update(|cx| {
+            cx.bind_keys(vec![
+                KeyBinding::new("ctrl-g", TestAction, Some("parent")),
+                KeyBinding::new("ctrl-k ctrl-g", TestAction, None),
+            ]);
+        });
+
+        window
+            .update(cx, |view, cx| cx.focus(&view.focus_handle))
+            .unwrap();
+
+        // Bound keystrokes, including the prefixes of multi-keystroke bindings, reach the key
+        // listeners instead of dispatching their actions.
+        cx.dispatch_keystroke(*window, Keystroke::parse("ctrl-g").unwrap());
+        cx.dispatch_keystroke(*window, Keystroke::parse("ctrl-k").unwrap());
+
+        window
+            .update(cx, |view, _| {
+                assert!(!view.saw_action);
+                assert_eq!(
+                    view.keystrokes
+                        .iter()
+                        .map(Keystroke::unparse)
+                        .collect::<Vec<_>>(),
+                    ["ctrl-g", "ctrl-k"]
+                );
+            })
+            .unwrap();
+    }
+
+    struct DragSourceView;
+
+    impl Render for DragSourceView {
+        fn render(&mut self, _cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("drag-source")
+                .size_full()
+                .on_drag((), |_, cx| cx.new_view(|_| EmptyView))
+        }
+    }
+
+    #[gpui::test]
+    fn test_escape_cancels_drag(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| DragSourceView);
+        cx.simulate_resize(size(px(100.), px(100.)));
+        cx.run_until_parked();
+
+        cx.simulate_mouse_down(
+            point(px(10.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_move(
+            point(px(50.), px(50.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert!(cx.update(|cx| cx.has_active_drag()));
+
+        cx.simulate_keystrokes("escape");
+        assert!(!cx.update(|cx| cx.has_active_drag()));
+
+        cx.simulate_mouse_move(
+            point(px(60.), px(60.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert!(!cx.update(|cx| cx.has_active_drag()));
+    }
+
+    #[derive(Default)]
+    struct SliderView {
+        moves: usize,
+        cancels: usize,
+    }
+
+    impl Render for SliderView {
+        fn render(&mut self, cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("slider")
+                .size_full()
+                .on_drag((), |_, cx| cx.new_view(|_| EmptyView))
+                .on_drag_move(cx.listener(|this, _: &DragMoveEvent<()>, _| this.moves += 1))
+                .on_drag_cancel(cx.listener(|this, _: &(), _| this.cancels += 1))
+        }
+    }
+
+    #[gpui::test]
+    fn test_deactivating_window_cancels_drag(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| SliderView::default());
+        cx.simulate_resize(size(px(100.), px(100.)));
+        cx.run_until_parked();
+
+        cx.simulate_mouse_down(
+            point(px(10.), px(10.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        cx.simulate_mouse_move(
+            point(px(50.), px(50.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert!(cx.update(|cx| cx.has_active_drag()));
+
+        cx.deactivate_window();
+        assert!(!cx.update(|cx| cx.has_active_drag()));
+        assert_eq!(cx.update(|cx| view.read(cx).cancels), 1);
+
+        // The slider no longer follows the mouse once the drag has been cancelled.
+        let moves = cx.update(|cx| view.read(cx).moves);
+        cx.simulate_mouse_move(
+            point(px(60.), px(60.)),
+            MouseButton::Left,
+            Modifiers::none(),
+        );
+        assert!(!cx.update(|cx| cx.has_active_drag()));
+        assert_eq!(cx.update(|cx| view.read(cx).moves), moves);
+    }
+
+    struct AccessibleView;
+
+    impl Render for AccessibleView {
+        fn render(&mut self, _cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
+            div()
+                .child(
+                    div().size(px(20.)).accessibility(
+                        AccessibilityNode::new(AccessibilityRole::Checkbox, "Line Numbers")
+                            .checked(Some(true)),
+                    ),
+                )
+                .child(
+                    div()
+                        .invisible()
+                        .accessibility(AccessibilityNode::new(AccessibilityRole::Button, "Hidden")),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn test_accessibility_snapshot(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| AccessibleView);
+        cx.simulate_resize(size(px(100.), px(100.)));
+        cx.run_until_parked();
+
+        let snapshot = cx.update(|cx| cx.accessibility_snapshot().to_vec());
+        assert_eq!(
+            snapshot,
+            vec![AccessibilityNode {
+                role: AccessibilityRole::Checkbox,
+                label: "Line Numbers".into(),
+                value: None,
+                checked: Some(true),
+                disabled: false,
+                bounds: Bounds::new(point(px(0.), px(0.)), size(px(20.), px(20.))),
+            }]
+        );
+    }
+
+    struct MeasuredView {
+        show_row: bool,
+    }
+
+    impl Render for MeasuredView {
+        fn render(&mut self, _cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("group")
+                .flex()
+                .flex_col()
+                .w(px(80.))
+                .p(px(5.))
+                .when(self.show_row, |this| {
+                    this.child(div().id("row").w_full().h(px(10.)))
+                })
+        }
+    }
+
+    #[gpui::test]
+    fn test_measured_bounds(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| MeasuredView { show_row: true });
+        cx.simulate_resize(size(px(100.), px(100.)));
+        cx.run_until_parked();
+
+        assert_eq!(
+            cx.update(|cx| cx.measured_bounds("row")),
+            Some(Bounds::new(point(px(5.), px(5.)), size(px(70.), px(10.))))
+        );
+        assert_eq!(
+            cx.update(|cx| cx.measured_bounds("group")),
+            Some(Bounds::new(point(px(0.), px(0.)), size(px(80.), px(20.))))
+        );
+
+        // Elements that weren't painted in the last frame have no bounds.
+        view.update(cx, |view, cx| {
+            view.show_row = false;
+            cx.notify();
+        });
+        cx.simulate_resize(size(px(120.), px(120.)));
+        cx.run_until_parked();
+        assert_eq!(cx.update(|cx| cx.measured_bounds("row")), None);
+    }
+
+    #[derive(Default)]
+    struct SmallTargetsView {
+        small_clicks: usize,
+        overlapping_clicks: usize,
+    }
+
+    impl Render for SmallTargetsView {
+        fn render(&mut self, cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
+            div()
+                .size_full()
+                .child(
+                    div()
+                        .id("small")
+                        .absolute()
+                        .top(px(10.))
+                        .left(px(10.))
+                        .size(px(16.))
+                        .hit_area_outset(px(8.))
+                        .on_click(cx.listener(|this, _, _| this.small_clicks += 1)),
+                )
+                .child(
+                    div()
+                        .id("overlapping")
+                        .absolute()
+                        .top(px(10.))
+                        .left(px(30.))
+                        .size(px(16.))
+                        .occlude()
+                        .on_click(cx.listener(|this, _, _| this.overlapping_clicks += 1)),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn test_hit_area_outset(cx: &mut TestAppContext) {
+        let (view, cx) = cx.add_window_view(|_| SmallTargetsView::default());
+        cx.simulate_resize(size(px(100.), px(100.)));
+        cx.run_until_parked();
+
+        // Clicks just outside of the painted bounds still reach the element.
+        cx.simulate_click(point(px(4.), px(18.)), Modifiers::none());
+        assert_eq!(cx.update(|cx| view.read(cx).small_clicks), 1);
+
+        // Where the extended area is covered by an element painted later, that element wins.
+        cx.simulate_click(point(px(31.), px(18.)), Modifiers::none());
+        assert_eq!(cx.update(|cx| view.read(cx).small_clicks), 1);
+        assert_eq!(cx.update(|cx| view.read(cx).overlapping_clicks), 1);
+
+        // Outside of the extended area, the element isn't clicked.
+        cx.simulate_click(point(px(18.), px(40.)), Modifiers::none());
+        assert_eq!(cx.update(|cx| view.read(cx).small_clicks), 1);
+    }
+
+    struct LongListView {
+        cull_offscreen: bool,
+        scroll_handle: ScrollHandle,
+    }
+
+    impl Render for LongListView {
+        fn render(&mut self, _cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
+            div()
+                .id("list")
+                .size_full()
+                .overflow_y_scroll()
+                .track_scroll(&self.scroll_handle)
+                .children((0..500usize).map(|ix| {
+                    div()
+                        .id(ix)
+                        .h(px(10.))
+                        .w_full()
+                        .when(self.cull_offscreen, |this| this.cull_offscreen())
+                }))
+        }
+    }
+
+    #[gpui::test]
+    fn test_cull_offscreen(cx: &mut TestAppContext) {
+        let painted_elements = |cull_offscreen: bool, cx: &mut TestAppContext| {
+            let scroll_handle = ScrollHandle::new();
+            let (_, cx) = cx.add_window_view(|_| LongListView {
+                cull_offscreen,
+                scroll_handle: scroll_handle.clone(),
+            });
+            cx.simulate_resize(size(px(100.), px(100.)));
+            cx.run_until_parked();
+
+            scroll_handle.set_offset(point(px(0.), px(-2000.)));
+            cx.simulate_resize(size(px(100.), px(101.)));
+            cx.run_until_parked();
+
+            // Scrolling isn't affected by the culled elements, which still take up space.
+            assert_eq!(
+                cx.update(|cx| cx.measured_bounds(200usize)),
+                Some(Bounds::new(point(px(0.), px(0.)), size(px(100.), px(10.))))
+            );
+            cx.update(|cx| cx.window.rendered_frame.measured_bounds.len())
+        };
+
+        assert_eq!(painted_elements(false, cx), 501);
+        // Only the list and the 11 items that overlap the viewport are painted.
+        assert_eq!(painted_elements(true, cx), 12);
+    }
+
+    #[gpui::test]
+    async fn test_capture_image(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| DragSourceView);
+        cx.simulate_resize(size(px(100.), px(50.)));
+        cx.run_until_parked();
+
+        // Test windows don't rasterize their scenes, so the capture is blank and only its size can
+        // be checked.
+        let image = cx.update(|cx| cx.capture_image()).await.unwrap();
+        let scale_factor = cx.update(|cx| cx.scale_factor());
+        assert_eq!(
+            image.size(0),
+            size(
+                DevicePixels((100. * scale_factor) as i32),
+                DevicePixels((50. * scale_factor) as i32)
+            )
+        );
+    }
+
+    struct CursorView;
+
+    impl Render for CursorView {
+        fn render(&mut self, _cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
+            canvas(
+                |_, _| {},
+                |bounds, _, cx| cx.paint_cursor(bounds.origin, px(20.), black()),
+            )
+            .size_full()
+        }
+    }
+
+    #[gpui::test]
+    fn test_cursor_blinks_while_the_window_is_active(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| CursorView);
+        cx.update(|cx| cx.activate_window());
+        cx.run_until_parked();
+        let cursor_shown = |cx: &mut VisualTestContext| {
+            cx.update(|cx| !cx.window.rendered_frame.scene.quads.is_empty())
+        };
+        assert!(cursor_shown(cx));
+
+        // The window is only drawn again when the cursor is shown or hidden.
+        cx.executor().advance_clock(CURSOR_BLINK_INTERVAL);
+        cx.run_until_parked();
+        assert!(!cursor_shown(cx));
+        cx.executor().advance_clock(CURSOR_BLINK_INTERVAL);
+        cx.run_until_parked();
+        assert!(cursor_shown(cx));
+        cx.executor().advance_clock(CURSOR_BLINK_INTERVAL);
+        cx.run_until_parked();
+        assert!(!cursor_shown(cx));
+
+        // Resetting the blink shows the cursor right away, and restarts its interval.
+        cx.update(|cx| cx.reset_cursor_blink());
+        cx.run_until_parked();
+        assert!(cursor_shown(cx));
+        cx.executor().advance_clock(CURSOR_BLINK_INTERVAL / 2);
+        cx.run_until_parked();
+        assert!(cursor_shown(cx));
+        cx.executor().advance_clock(CURSOR_BLINK_INTERVAL / 2);
+        cx.run_until_parked();
+        assert!(!cursor_shown(cx));
+
+        // The cursor stops blinking while the window is inactive.
+        cx.deactivate_window();
+        cx.run_until_parked();
+        assert!(cursor_shown(cx));
+        cx.executor().advance_clock(CURSOR_BLINK_INTERVAL * 2);
+        cx.run_until_parked();
+        assert!(cursor_shown(cx));
+    }
 }
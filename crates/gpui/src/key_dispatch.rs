@@ -84,6 +84,7 @@ pub(crate) struct DispatchNode {
     pub modifiers_changed_listeners: Vec<ModifiersChangedListener>,
     pub context: Option<KeyContext>,
     pub focus_id: Option<FocusId>,
+    pub captures_keystrokes: bool,
     view_id: Option<EntityId>,
     parent: Option<DispatchNodeId>,
 }
@@ -108,6 +109,10 @@ impl ReusedSubtree {
     pub fn contains_focus(&self) -> bool {
         self.contains_focus
     }
+
+    pub fn new_range(&self) -> Range<usize> {
+        self.new_range.clone()
+    }
 }
 
 #[derive(Default, Debug)]
@@ -219,10 +224,26 @@ impl DispatchTree {
         self.focusable_node_ids.insert(focus_id, node_id);
     }
 
+    pub fn set_captures_keystrokes(&mut self) {
+        self.active_node().captures_keystrokes = true;
+    }
+
+    /// Returns whether keystrokes dispatched along the given path skip the key bindings.
+    pub fn captures_keystrokes(&self, dispatch_path: &SmallVec<[DispatchNodeId; 32]>) -> bool {
+        dispatch_path
+            .iter()
+            .any(|node_id| self.nodes[node_id.0].captures_keystrokes)
+    }
+
     pub fn parent_view_id(&mut self) -> Option<EntityId> {
         self.view_stack.last().copied()
     }
 
+    /// Returns the views of the nodes in the given range.
+    pub fn view_ids(&self, range: Range<usize>) -> impl Iterator<Item = EntityId> + '_ {
+        self.nodes[range].iter().filter_map(|node| node.view_id)
+    }
+
     pub fn set_view_id(&mut self, view_id: EntityId) {
         if self.view_stack.last().copied() != Some(view_id) {
             let node_id = *self.node_stack.last().unwrap();
@@ -254,6 +275,9 @@ impl DispatchTree {
         if let Some(view_id) = source.view_id {
             self.set_view_id(view_id);
         }
+        if source.captures_keystrokes {
+            self.set_captures_keystrokes();
+        }
 
         let target = self.active_node();
         target.key_listeners = mem::take(&mut source.key_listeners);
@@ -3,6 +3,7 @@
 
 mod app_menu;
 mod keystroke;
+mod offscreen;
 
 #[cfg(target_os = "linux")]
 mod linux;
@@ -29,6 +30,7 @@ use crate::{
 use anyhow::Result;
 use async_task::Runnable;
 use futures::channel::oneshot;
+use image::RgbaImage;
 use parking::Unparker;
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use seahash::SeaHasher;
@@ -47,6 +49,9 @@ use uuid::Uuid;
 
 pub use app_menu::*;
 pub use keystroke::*;
+#[cfg(any(test, feature = "test-support"))]
+pub(crate) use offscreen::OffscreenAtlas;
+pub use offscreen::OffscreenWindow;
 
 #[cfg(target_os = "linux")]
 pub(crate) use linux::*;
@@ -192,6 +197,12 @@ pub trait PlatformDisplay: Send + Sync + Debug {
     /// Get the bounds for this display
     fn bounds(&self) -> Bounds<Pixels>;
 
+    /// Get the bounds of the area of this display that windows can occupy, excluding
+    /// system UI such as the menu bar and dock. Relative to the same origin as [`Self::bounds`].
+    fn visible_bounds(&self) -> Bounds<Pixels> {
+        self.bounds()
+    }
+
     /// Get the default bounds for this display to place a window
     fn default_bounds(&self) -> Bounds<Pixels> {
         let center = self.bounds().center();
@@ -350,6 +361,10 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
     fn on_close(&self, callback: Box<dyn FnOnce()>);
     fn on_appearance_changed(&self, callback: Box<dyn FnMut()>);
     fn draw(&self, scene: &Scene);
+    /// Renders the scene off-screen at the window's device resolution, with its pixels in BGRA
+    /// order. Windows that don't rasterize their scenes, such as offscreen and test windows,
+    /// return a blank image of that size.
+    fn render_to_image(&self, scene: &Scene) -> Result<RgbaImage>;
     fn completed_frame(&self) {}
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas>;
 
@@ -375,6 +390,10 @@ pub(crate) trait PlatformWindow: HasWindowHandle + HasDisplayHandle {
     fn set_client_inset(&self, _inset: Pixels) {}
     fn gpu_specs(&self) -> Option<GPUSpecs>;
 
+    fn as_offscreen(&self) -> Option<&OffscreenWindow> {
+        None
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     fn as_test(&mut self) -> Option<&mut TestWindow> {
         None
@@ -466,9 +485,13 @@ pub(crate) trait PlatformAtlas: Send + Sync {
         key: &AtlasKey,
         build: &mut dyn FnMut() -> Result<Option<(Size<DevicePixels>, Cow<'a, [u8]>)>>,
     ) -> Result<Option<AtlasTile>>;
+
+    /// Removes the tiles whose keys don't satisfy the predicate, freeing up their space in the
+    /// atlas for new tiles.
+    fn retain(&self, predicate: &mut dyn FnMut(&AtlasKey) -> bool);
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 #[repr(C)]
 pub(crate) struct AtlasTile {
     pub(crate) texture_id: AtlasTextureId,
@@ -477,7 +500,7 @@ pub(crate) struct AtlasTile {
     pub(crate) bounds: Bounds<DevicePixels>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 #[repr(C)]
 pub(crate) struct AtlasTextureId {
     // We use u32 instead of usize for Metal Shader Language compatibility
@@ -485,7 +508,7 @@ pub(crate) struct AtlasTextureId {
     pub(crate) kind: AtlasTextureKind,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 #[repr(C)]
 pub(crate) enum AtlasTextureKind {
     Monochrome = 0,
@@ -493,7 +516,7 @@ pub(crate) enum AtlasTextureKind {
     Path = 2,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 #[repr(C)]
 pub(crate) struct TileId(pub(crate) u32);
 
@@ -690,6 +713,14 @@ pub struct WindowOptions {
     /// Whether to use client or server side decorations. Wayland only
     /// Note that this may be ignored.
     pub window_decorations: Option<WindowDecorations>,
+
+    /// Whether to draw the window offscreen, without a platform window, in which case it's never
+    /// shown. See [`OffscreenWindow`].
+    pub offscreen: bool,
+
+    /// The window this one is anchored to, such as for a popover that extends past the bounds of
+    /// the window it was opened from. The window is closed along with its parent.
+    pub parent: Option<AnyWindowHandle>,
 }
 
 /// The variables that can be configured when creating a new window
@@ -747,6 +778,15 @@ impl WindowBounds {
             WindowBounds::Fullscreen(bounds) => *bounds,
         }
     }
+
+    /// Replace the inner bounds, preserving the window state.
+    pub fn map(self, f: impl FnOnce(Bounds<Pixels>) -> Bounds<Pixels>) -> Self {
+        match self {
+            WindowBounds::Windowed(bounds) => WindowBounds::Windowed(f(bounds)),
+            WindowBounds::Maximized(bounds) => WindowBounds::Maximized(f(bounds)),
+            WindowBounds::Fullscreen(bounds) => WindowBounds::Fullscreen(f(bounds)),
+        }
+    }
 }
 
 impl Default for WindowOptions {
@@ -767,6 +807,8 @@ impl Default for WindowOptions {
             app_id: None,
             window_min_size: None,
             window_decorations: None,
+            offscreen: false,
+            parent: None,
         }
     }
 }
@@ -130,6 +130,20 @@ impl PlatformAtlas for BladeAtlas {
             Ok(Some(tile))
         }
     }
+
+    fn retain(&self, predicate: &mut dyn FnMut(&AtlasKey) -> bool) {
+        let mut lock = self.0.lock();
+        let lock = &mut *lock;
+        lock.tiles_by_key.retain(|key, tile| {
+            let retain = predicate(key);
+            if !retain {
+                lock.storage[tile.texture_id]
+                    .allocator
+                    .deallocate(tile.tile_id.into());
+            }
+            retain
+        });
+    }
 }
 
 impl BladeAtlasState {
@@ -294,6 +308,17 @@ impl ops::Index<AtlasTextureId> for BladeAtlasStorage {
     }
 }
 
+impl ops::IndexMut<AtlasTextureId> for BladeAtlasStorage {
+    fn index_mut(&mut self, id: AtlasTextureId) -> &mut Self::Output {
+        let textures = match id.kind {
+            crate::AtlasTextureKind::Monochrome => &mut self.monochrome_textures,
+            crate::AtlasTextureKind::Polychrome => &mut self.polychrome_textures,
+            crate::AtlasTextureKind::Path => &mut self.path_textures,
+        };
+        &mut textures[id.index as usize]
+    }
+}
+
 impl BladeAtlasStorage {
     fn destroy(&mut self, gpu: &gpu::Context) {
         for mut texture in self.monochrome_textures.drain(..) {
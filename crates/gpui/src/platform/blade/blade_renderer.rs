@@ -3,12 +3,14 @@
 
 use super::{BladeAtlas, PATH_TEXTURE_FORMAT};
 use crate::{
-    AtlasTextureKind, AtlasTile, Bounds, ContentMask, DevicePixels, GPUSpecs, Hsla,
+    AtlasTextureKind, AtlasTile, Bounds, ContentMask, DevicePixels, GPUSpecs, Gradient, Hsla,
     MonochromeSprite, Path, PathId, PathVertex, PolychromeSprite, PrimitiveBatch, Quad,
     ScaledPixels, Scene, Shadow, Size, Underline,
 };
+use anyhow::{anyhow, Result};
 use bytemuck::{Pod, Zeroable};
 use collections::HashMap;
+use image::RgbaImage;
 #[cfg(target_os = "macos")]
 use media::core_video::CVMetalTextureCache;
 #[cfg(target_os = "macos")]
@@ -16,7 +18,7 @@ use std::{ffi::c_void, ptr::NonNull};
 
 use blade_graphics as gpu;
 use blade_util::{BufferBelt, BufferBeltDescriptor};
-use std::{mem, sync::Arc};
+use std::{mem, slice, sync::Arc};
 
 const MAX_FRAME_TIME_MS: u32 = 10000;
 
@@ -118,6 +120,12 @@ struct ShaderQuadsData {
     b_quads: gpu::BufferPiece,
 }
 
+#[derive(blade_macros::ShaderData)]
+struct ShaderGradientsData {
+    globals: GlobalParams,
+    b_gradients: gpu::BufferPiece,
+}
+
 #[derive(blade_macros::ShaderData)]
 struct ShaderShadowsData {
     globals: GlobalParams,
@@ -179,6 +187,7 @@ struct PathSprite {
 
 struct BladePipelines {
     quads: gpu::RenderPipeline,
+    gradients: gpu::RenderPipeline,
     shadows: gpu::RenderPipeline,
     path_rasterization: gpu::RenderPipeline,
     paths: gpu::RenderPipeline,
@@ -202,6 +211,7 @@ impl BladePipelines {
         shader.check_struct_size::<GlobalParams>();
         shader.check_struct_size::<SurfaceParams>();
         shader.check_struct_size::<Quad>();
+        shader.check_struct_size::<Gradient>();
         shader.check_struct_size::<Shadow>();
         assert_eq!(
             mem::size_of::<PathVertex<ScaledPixels>>(),
@@ -238,6 +248,19 @@ impl BladePipelines {
                 fragment: shader.at("fs_quad"),
                 color_targets,
             }),
+            gradients: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
+                name: "gradients",
+                data_layouts: &[&ShaderGradientsData::layout()],
+                vertex: shader.at("vs_gradient"),
+                vertex_fetches: &[],
+                primitive: gpu::PrimitiveState {
+                    topology: gpu::PrimitiveTopology::TriangleStrip,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                fragment: shader.at("fs_gradient"),
+                color_targets,
+            }),
             shadows: gpu.create_render_pipeline(gpu::RenderPipelineDesc {
                 name: "shadows",
                 data_layouts: &[&ShaderShadowsData::layout()],
@@ -346,6 +369,7 @@ pub struct BladeRenderer {
     gpu: Arc<gpu::Context>,
     surface_config: gpu::SurfaceConfig,
     alpha_mode: gpu::AlphaMode,
+    surface_format: gpu::TextureFormat,
     command_encoder: gpu::CommandEncoder,
     last_sync_point: Option<gpu::SyncPoint>,
     pipelines: BladePipelines,
@@ -397,6 +421,7 @@ impl BladeRenderer {
             gpu,
             surface_config,
             alpha_mode: surface_info.alpha,
+            surface_format: surface_info.format,
             command_encoder,
             last_sync_point: None,
             pipelines,
@@ -439,6 +464,7 @@ impl BladeRenderer {
             let surface_info = self.gpu.resize(self.surface_config);
             self.pipelines = BladePipelines::new(&self.gpu, surface_info);
             self.alpha_mode = surface_info.alpha;
+            self.surface_format = surface_info.format;
         }
     }
 
@@ -547,7 +573,80 @@ impl BladeRenderer {
             self.gpu.acquire_frame()
         };
         self.command_encoder.init_texture(frame.texture());
+        self.draw_primitives(scene, frame.texture_view());
+
+        self.command_encoder.present(frame);
+        self.submit_frame();
+    }
+
+    /// Renders the scene into an off-screen texture the size of the surface and reads it back,
+    /// with its pixels in BGRA order.
+    pub fn render_to_image(&mut self, scene: &Scene) -> Result<RgbaImage> {
+        let swap_red_and_blue = match self.surface_format {
+            gpu::TextureFormat::Bgra8Unorm | gpu::TextureFormat::Bgra8UnormSrgb => false,
+            gpu::TextureFormat::Rgba8Unorm | gpu::TextureFormat::Rgba8UnormSrgb => true,
+            format => return Err(anyhow!("can't read back surface format {format:?}")),
+        };
+        let size = self.surface_config.size;
+        let bytes_per_row = size.width * 4;
+        let byte_count = bytes_per_row as usize * size.height as usize;
+
+        let texture = self.gpu.create_texture(gpu::TextureDesc {
+            name: "capture",
+            format: self.surface_format,
+            size,
+            array_layer_count: 1,
+            mip_level_count: 1,
+            dimension: gpu::TextureDimension::D2,
+            usage: gpu::TextureUsage::TARGET | gpu::TextureUsage::COPY,
+        });
+        let texture_view = self.gpu.create_texture_view(gpu::TextureViewDesc {
+            name: "capture",
+            texture,
+            format: self.surface_format,
+            dimension: gpu::ViewDimension::D2,
+            subresources: &Default::default(),
+        });
+        let buffer = self.gpu.create_buffer(gpu::BufferDesc {
+            name: "capture",
+            size: byte_count as u64,
+            memory: gpu::Memory::Shared,
+        });
 
+        self.command_encoder.start();
+        self.atlas.before_frame(&mut self.command_encoder);
+        self.rasterize_paths(scene.paths());
+        self.command_encoder.init_texture(texture);
+        self.draw_primitives(scene, texture_view);
+        self.command_encoder.transfer().copy_texture_to_buffer(
+            gpu::TexturePiece {
+                texture,
+                mip_level: 0,
+                array_layer: 0,
+                origin: [0, 0, 0],
+            },
+            buffer.into(),
+            bytes_per_row,
+            size,
+        );
+        self.submit_frame();
+        self.wait_for_gpu();
+
+        let mut bytes = unsafe { slice::from_raw_parts(buffer.data(), byte_count) }.to_vec();
+        self.gpu.destroy_buffer(buffer);
+        self.gpu.destroy_texture_view(texture_view);
+        self.gpu.destroy_texture(texture);
+
+        if swap_red_and_blue {
+            for pixel in bytes.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+        RgbaImage::from_raw(size.width, size.height, bytes)
+            .ok_or_else(|| anyhow!("failed to read back a {}x{} image", size.width, size.height))
+    }
+
+    fn draw_primitives(&mut self, scene: &Scene, target: gpu::TextureView) {
         let globals = GlobalParams {
             viewport_size: [
                 self.surface_config.size.width as f32,
@@ -562,7 +661,7 @@ impl BladeRenderer {
 
         if let mut pass = self.command_encoder.render(gpu::RenderTargetSet {
             colors: &[gpu::RenderTarget {
-                view: frame.texture_view(),
+                view: target,
                 init_op: gpu::InitOp::Clear(gpu::TextureColor::TransparentBlack),
                 finish_op: gpu::FinishOp::Store,
             }],
@@ -584,6 +683,19 @@ impl BladeRenderer {
                         );
                         encoder.draw(0, 4, 0, quads.len() as u32);
                     }
+                    PrimitiveBatch::Gradients(gradients) => {
+                        let instance_buf =
+                            unsafe { self.instance_belt.alloc_typed(gradients, &self.gpu) };
+                        let mut encoder = pass.with(&self.pipelines.gradients);
+                        encoder.bind(
+                            0,
+                            &ShaderGradientsData {
+                                globals,
+                                b_gradients: instance_buf,
+                            },
+                        );
+                        encoder.draw(0, 4, 0, gradients.len() as u32);
+                    }
                     PrimitiveBatch::Shadows(shadows) => {
                         let instance_buf =
                             unsafe { self.instance_belt.alloc_typed(shadows, &self.gpu) };
@@ -754,8 +866,9 @@ impl BladeRenderer {
                 }
             }
         }
+    }
 
-        self.command_encoder.present(frame);
+    fn submit_frame(&mut self) {
         let sync_point = self.gpu.submit(&mut self.command_encoder);
 
         profiling::scope!("finish");
@@ -290,6 +290,84 @@ fn fs_quad(input: QuadVarying) -> @location(0) vec4<f32> {
     return blend_color(color, saturate(0.5 - distance));
 }
 
+// --- gradients --- //
+
+struct GradientStop {
+    offset: f32,
+    color: Hsla,
+}
+
+struct Gradient {
+    order: u32,
+    pad: u32,
+    bounds: Bounds,
+    content_mask: Bounds,
+    corner_radii: Corners,
+    angle: f32,
+    stop_count: u32,
+    stops: array<GradientStop, 4>,
+}
+var<storage, read> b_gradients: array<Gradient>;
+
+struct GradientVarying {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) gradient_id: u32,
+    //TODO: use `clip_distance` once Naga supports it
+    @location(1) clip_distances: vec4<f32>,
+}
+
+fn premultiply(color: vec4<f32>) -> vec4<f32> {
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
+
+@vertex
+fn vs_gradient(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) instance_id: u32) -> GradientVarying {
+    let unit_vertex = vec2<f32>(f32(vertex_id & 1u), 0.5 * f32(vertex_id & 2u));
+    let gradient = b_gradients[instance_id];
+
+    var out = GradientVarying();
+    out.position = to_device_position(unit_vertex, gradient.bounds);
+    out.gradient_id = instance_id;
+    out.clip_distances = distance_from_clip_rect(unit_vertex, gradient.bounds, gradient.content_mask);
+    return out;
+}
+
+@fragment
+fn fs_gradient(input: GradientVarying) -> @location(0) vec4<f32> {
+    // Alpha clip first, since we don't have `clip_distance`.
+    if (any(input.clip_distances < vec4<f32>(0.0))) {
+        return vec4<f32>(0.0);
+    }
+
+    let gradient = b_gradients[input.gradient_id];
+    let half_size = gradient.bounds.size / 2.0;
+    let center = gradient.bounds.origin + half_size;
+
+    // Project the point onto the gradient line, which passes through the center of the bounds
+    // and is long enough for the first and last stops to reach the corners, like in CSS.
+    let direction = vec2<f32>(sin(gradient.angle), -cos(gradient.angle));
+    let line_length = abs(gradient.bounds.size.x * direction.x) + abs(gradient.bounds.size.y * direction.y);
+    let t = dot(input.position.xy - center, direction) / max(line_length, 1.0) + 0.5;
+
+    // `hsla_to_rgba` converts to linear space, so the stops are interpolated there. They're
+    // premultiplied first, so that transparent stops don't darken their neighbors.
+    // The stops are read from the buffer, since Naga can't index a local array dynamically.
+    let stops = &b_gradients[input.gradient_id].stops;
+    var color = premultiply(hsla_to_rgba((*stops)[0].color));
+    for (var i = 1u; i < gradient.stop_count; i += 1u) {
+        let start = (*stops)[i - 1u];
+        let end = (*stops)[i];
+        if (t >= start.offset) {
+            let progress = saturate((t - start.offset) / max(end.offset - start.offset, 0.0001));
+            color = mix(premultiply(hsla_to_rgba(start.color)), premultiply(hsla_to_rgba(end.color)), progress);
+        }
+    }
+    color = vec4<f32>(color.rgb / max(color.a, 0.0001), color.a);
+
+    let distance = quad_sdf(input.position.xy, gradient.bounds, gradient.corner_radii);
+    return blend_color(color, saturate(0.5 - distance));
+}
+
 // --- shadows --- //
 
 struct Shadow {
@@ -549,6 +627,8 @@ fn fs_mono_sprite(input: MonoSpriteVarying) -> @location(0) vec4<f32> {
 struct PolychromeSprite {
     order: u32,
     grayscale: u32,
+    opacity: f32,
+    pad: u32,
     bounds: Bounds,
     content_mask: Bounds,
     corner_radii: Corners,
@@ -592,7 +672,7 @@ fn fs_poly_sprite(input: PolySpriteVarying) -> @location(0) vec4<f32> {
         let grayscale = dot(color.rgb, GRAYSCALE_FACTORS);
         color = vec4<f32>(vec3<f32>(grayscale), sample.a);
     }
-    return blend_color(color, saturate(0.5 - distance));
+    return blend_color(color, sprite.opacity * saturate(0.5 - distance));
 }
 
 // --- surfaces --- //
@@ -121,6 +121,29 @@ impl Keystroke {
         })
     }
 
+    /// Produces a representation of this keystroke that can be parsed with [`Keystroke::parse`],
+    /// such as for storing it in a keymap or settings file. The `ime_key` is omitted.
+    pub fn unparse(&self) -> String {
+        let mut source = String::new();
+        if self.modifiers.control {
+            source.push_str("ctrl-");
+        }
+        if self.modifiers.alt {
+            source.push_str("alt-");
+        }
+        if self.modifiers.shift {
+            source.push_str("shift-");
+        }
+        if self.modifiers.platform {
+            source.push_str("cmd-");
+        }
+        if self.modifiers.function {
+            source.push_str("fn-");
+        }
+        source.push_str(&self.key);
+        source
+    }
+
     /// Returns true if this keystroke left
     /// the ime system in an incomplete state.
     pub fn is_ime_in_progress(&self) -> bool {
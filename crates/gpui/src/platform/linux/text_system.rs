@@ -440,6 +440,8 @@ impl CosmicTextSystemState {
             descent: layout.max_descent.into(),
             runs,
             len: text.len(),
+            // Right-to-left text is shaped a run at a time by `layout_bidi_line`.
+            has_rtl: false,
         }
     }
 }
@@ -7,6 +7,7 @@ use std::sync::Arc;
 use blade_graphics as gpu;
 use collections::HashMap;
 use futures::channel::oneshot::Receiver;
+use image::RgbaImage;
 
 use raw_window_handle as rwh;
 use wayland_backend::client::ObjectId;
@@ -939,6 +940,11 @@ impl PlatformWindow for WaylandWindow {
         state.renderer.draw(scene);
     }
 
+    fn render_to_image(&self, scene: &Scene) -> anyhow::Result<RgbaImage> {
+        let mut state = self.borrow_mut();
+        state.renderer.render_to_image(scene)
+    }
+
     fn completed_frame(&self) {
         let state = self.borrow();
         state.surface.commit();
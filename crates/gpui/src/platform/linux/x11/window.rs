@@ -10,6 +10,7 @@ use crate::{
 };
 
 use blade_graphics as gpu;
+use image::RgbaImage;
 use raw_window_handle as rwh;
 use util::{maybe, ResultExt};
 use x11rb::{
@@ -1215,6 +1216,11 @@ impl PlatformWindow for X11Window {
         inner.renderer.draw(scene);
     }
 
+    fn render_to_image(&self, scene: &Scene) -> anyhow::Result<RgbaImage> {
+        let mut inner = self.0.state.borrow_mut();
+        inner.renderer.render_to_image(scene)
+    }
+
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas> {
         let inner = self.0.state.borrow();
         inner.renderer.sprite_atlas().clone()
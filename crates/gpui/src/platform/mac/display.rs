@@ -1,9 +1,9 @@
-use crate::{px, size, Bounds, DisplayId, Pixels, PlatformDisplay};
+use crate::{point, px, size, Bounds, DisplayId, Pixels, PlatformDisplay};
 use anyhow::Result;
 use cocoa::{
     appkit::NSScreen,
     base::{id, nil},
-    foundation::{NSDictionary, NSString},
+    foundation::{NSArray, NSAutoreleasePool, NSDictionary, NSString},
 };
 use core_foundation::uuid::{CFUUIDGetUUIDBytes, CFUUIDRef};
 use core_graphics::display::{CGDirectDisplayID, CGDisplayBounds, CGGetActiveDisplayList};
@@ -114,4 +114,41 @@ impl PlatformDisplay for MacDisplay {
             }
         }
     }
+
+    fn visible_bounds(&self) -> Bounds<Pixels> {
+        unsafe {
+            let screens = NSScreen::screens(nil);
+            let count = NSArray::count(screens);
+            let screen_number_key: id = NSString::alloc(nil)
+                .init_str("NSScreenNumber")
+                .autorelease();
+            for i in 0..count {
+                let screen = NSArray::objectAtIndex(screens, i);
+                let device_description = NSScreen::deviceDescription(screen);
+                let screen_number = device_description.objectForKey_(screen_number_key);
+                let screen_number: CGDirectDisplayID =
+                    msg_send![screen_number, unsignedIntegerValue];
+                if screen_number != self.0 {
+                    continue;
+                }
+
+                // AppKit frames have a bottom-left origin, so flip the visible frame
+                // into the top-left origin of the screen it belongs to.
+                let frame = NSScreen::frame(screen);
+                let visible = NSScreen::visibleFrame(screen);
+                let left = visible.origin.x - frame.origin.x;
+                let top =
+                    (frame.origin.y + frame.size.height) - (visible.origin.y + visible.size.height);
+                return Bounds {
+                    origin: point(px(left as f32), px(top as f32)),
+                    size: size(
+                        px(visible.size.width as f32),
+                        px(visible.size.height as f32),
+                    ),
+                };
+            }
+
+            self.bounds()
+        }
+    }
 }
@@ -78,6 +78,25 @@ impl PlatformAtlas for MetalAtlas {
             Ok(Some(tile))
         }
     }
+
+    fn retain(&self, predicate: &mut dyn FnMut(&AtlasKey) -> bool) {
+        let mut lock = self.0.lock();
+        let lock = &mut *lock;
+        lock.tiles_by_key.retain(|key, tile| {
+            let retain = predicate(key);
+            if !retain {
+                let textures = match tile.texture_id.kind {
+                    AtlasTextureKind::Monochrome => &mut lock.monochrome_textures,
+                    AtlasTextureKind::Polychrome => &mut lock.polychrome_textures,
+                    AtlasTextureKind::Path => &mut lock.path_textures,
+                };
+                textures[tile.texture_id.index as usize]
+                    .allocator
+                    .deallocate(tile.tile_id.into());
+            }
+            retain
+        });
+    }
 }
 
 impl MetalAtlasState {
@@ -1,8 +1,8 @@
 use super::metal_atlas::MetalAtlas;
 use crate::{
     point, size, AtlasTextureId, AtlasTextureKind, AtlasTile, Bounds, ContentMask, DevicePixels,
-    Hsla, MonochromeSprite, Path, PathId, PathVertex, PolychromeSprite, PrimitiveBatch, Quad,
-    ScaledPixels, Scene, Shadow, Size, Surface, Underline,
+    Gradient, Hsla, MonochromeSprite, Path, PathId, PathVertex, PolychromeSprite, PrimitiveBatch,
+    Quad, ScaledPixels, Scene, Shadow, Size, Surface, Underline,
 };
 use anyhow::{anyhow, Result};
 use block::ConcreteBlock;
@@ -14,6 +14,7 @@ use cocoa::{
 use collections::HashMap;
 use core_foundation::base::TCFType;
 use foreign_types::ForeignType;
+use image::RgbaImage;
 use media::core_video::CVMetalTextureCache;
 use metal::{CAMetalLayer, CommandQueue, MTLPixelFormat, MTLResourceOptions, NSRange};
 use objc::{self, msg_send, sel, sel_impl};
@@ -96,6 +97,7 @@ pub(crate) struct MetalRenderer {
     path_sprites_pipeline_state: metal::RenderPipelineState,
     shadows_pipeline_state: metal::RenderPipelineState,
     quads_pipeline_state: metal::RenderPipelineState,
+    gradients_pipeline_state: metal::RenderPipelineState,
     underlines_pipeline_state: metal::RenderPipelineState,
     monochrome_sprites_pipeline_state: metal::RenderPipelineState,
     polychrome_sprites_pipeline_state: metal::RenderPipelineState,
@@ -195,6 +197,14 @@ impl MetalRenderer {
             "quad_fragment",
             MTLPixelFormat::BGRA8Unorm,
         );
+        let gradients_pipeline_state = build_pipeline_state(
+            &device,
+            &library,
+            "gradients",
+            "gradient_vertex",
+            "gradient_fragment",
+            MTLPixelFormat::BGRA8Unorm,
+        );
         let underlines_pipeline_state = build_pipeline_state(
             &device,
             &library,
@@ -242,6 +252,7 @@ impl MetalRenderer {
             path_sprites_pipeline_state,
             shadows_pipeline_state,
             quads_pipeline_state,
+            gradients_pipeline_state,
             underlines_pipeline_state,
             monochrome_sprites_pipeline_state,
             polychrome_sprites_pipeline_state,
@@ -312,8 +323,12 @@ impl MetalRenderer {
         loop {
             let mut instance_buffer = self.instance_buffer_pool.lock().acquire(&self.device);
 
-            let command_buffer =
-                self.draw_primitives(scene, &mut instance_buffer, drawable, viewport_size);
+            let command_buffer = self.draw_primitives(
+                scene,
+                &mut instance_buffer,
+                drawable.texture(),
+                viewport_size,
+            );
 
             match command_buffer {
                 Ok(command_buffer) => {
@@ -358,11 +373,52 @@ impl MetalRenderer {
         }
     }
 
+    /// Renders the scene into an off-screen texture the size of the drawable and reads it back,
+    /// with its pixels in BGRA order.
+    pub fn render_to_image(&mut self, scene: &Scene) -> Result<RgbaImage> {
+        let viewport_size = self.layer.drawable_size();
+        let width = viewport_size.width.ceil() as u32;
+        let height = viewport_size.height.ceil() as u32;
+        let viewport_size: Size<DevicePixels> = size((width as i32).into(), (height as i32).into());
+
+        let texture_descriptor = metal::TextureDescriptor::new();
+        texture_descriptor.set_width(width as u64);
+        texture_descriptor.set_height(height as u64);
+        texture_descriptor.set_pixel_format(MTLPixelFormat::BGRA8Unorm);
+        texture_descriptor.set_usage(metal::MTLTextureUsage::RenderTarget);
+        texture_descriptor.set_storage_mode(metal::MTLStorageMode::Managed);
+        let texture = self.device.new_texture(&texture_descriptor);
+
+        let mut instance_buffer = self.instance_buffer_pool.lock().acquire(&self.device);
+        let command_buffer =
+            self.draw_primitives(scene, &mut instance_buffer, &texture, viewport_size);
+        let result = command_buffer.map(|command_buffer| {
+            let blit_encoder = command_buffer.new_blit_command_encoder();
+            blit_encoder.synchronize_resource(&texture);
+            blit_encoder.end_encoding();
+            command_buffer.commit();
+            command_buffer.wait_until_completed();
+        });
+        self.instance_buffer_pool.lock().release(instance_buffer);
+        result?;
+
+        let bytes_per_row = width as usize * 4;
+        let mut bytes = vec![0; bytes_per_row * height as usize];
+        texture.get_bytes(
+            bytes.as_mut_ptr() as *mut c_void,
+            bytes_per_row as u64,
+            metal::MTLRegion::new_2d(0, 0, width as u64, height as u64),
+            0,
+        );
+        RgbaImage::from_raw(width, height, bytes)
+            .ok_or_else(|| anyhow!("failed to read back a {width}x{height} image"))
+    }
+
     fn draw_primitives(
         &mut self,
         scene: &Scene,
         instance_buffer: &mut InstanceBuffer,
-        drawable: &metal::MetalDrawableRef,
+        texture: &metal::TextureRef,
         viewport_size: Size<DevicePixels>,
     ) -> Result<metal::CommandBuffer> {
         let command_queue = self.command_queue.clone();
@@ -384,7 +440,7 @@ impl MetalRenderer {
             .object_at(0)
             .unwrap();
 
-        color_attachment.set_texture(Some(drawable.texture()));
+        color_attachment.set_texture(Some(texture));
         color_attachment.set_load_action(metal::MTLLoadAction::Clear);
         color_attachment.set_store_action(metal::MTLStoreAction::Store);
         let alpha = if self.layer.is_opaque() { 1. } else { 0. };
@@ -416,6 +472,13 @@ impl MetalRenderer {
                     viewport_size,
                     command_encoder,
                 ),
+                PrimitiveBatch::Gradients(gradients) => self.draw_gradients(
+                    gradients,
+                    instance_buffer,
+                    &mut instance_offset,
+                    viewport_size,
+                    command_encoder,
+                ),
                 PrimitiveBatch::Paths(paths) => self.draw_paths(
                     paths,
                     &path_tiles,
@@ -464,10 +527,11 @@ impl MetalRenderer {
 
             if !ok {
                 command_encoder.end_encoding();
-                return Err(anyhow!("scene too large: {} paths, {} shadows, {} quads, {} underlines, {} mono, {} poly, {} surfaces",
+                return Err(anyhow!("scene too large: {} paths, {} shadows, {} quads, {} gradients, {} underlines, {} mono, {} poly, {} surfaces",
                     scene.paths.len(),
                     scene.shadows.len(),
                     scene.quads.len(),
+                    scene.gradients.len(),
                     scene.underlines.len(),
                     scene.monochrome_sprites.len(),
                     scene.polychrome_sprites.len(),
@@ -697,6 +761,69 @@ impl MetalRenderer {
         true
     }
 
+    fn draw_gradients(
+        &mut self,
+        gradients: &[Gradient],
+        instance_buffer: &mut InstanceBuffer,
+        instance_offset: &mut usize,
+        viewport_size: Size<DevicePixels>,
+        command_encoder: &metal::RenderCommandEncoderRef,
+    ) -> bool {
+        if gradients.is_empty() {
+            return true;
+        }
+        align_offset(instance_offset);
+
+        command_encoder.set_render_pipeline_state(&self.gradients_pipeline_state);
+        command_encoder.set_vertex_buffer(
+            GradientInputIndex::Vertices as u64,
+            Some(&self.unit_vertices),
+            0,
+        );
+        command_encoder.set_vertex_buffer(
+            GradientInputIndex::Gradients as u64,
+            Some(&instance_buffer.metal_buffer),
+            *instance_offset as u64,
+        );
+        command_encoder.set_fragment_buffer(
+            GradientInputIndex::Gradients as u64,
+            Some(&instance_buffer.metal_buffer),
+            *instance_offset as u64,
+        );
+
+        command_encoder.set_vertex_bytes(
+            GradientInputIndex::ViewportSize as u64,
+            mem::size_of_val(&viewport_size) as u64,
+            &viewport_size as *const Size<DevicePixels> as *const _,
+        );
+
+        let gradient_bytes_len = mem::size_of_val(gradients);
+        let buffer_contents =
+            unsafe { (instance_buffer.metal_buffer.contents() as *mut u8).add(*instance_offset) };
+
+        let next_offset = *instance_offset + gradient_bytes_len;
+        if next_offset > instance_buffer.size {
+            return false;
+        }
+
+        unsafe {
+            ptr::copy_nonoverlapping(
+                gradients.as_ptr() as *const u8,
+                buffer_contents,
+                gradient_bytes_len,
+            );
+        }
+
+        command_encoder.draw_primitives_instanced(
+            metal::MTLPrimitiveType::Triangle,
+            0,
+            6,
+            gradients.len() as u64,
+        );
+        *instance_offset = next_offset;
+        true
+    }
+
     fn draw_paths(
         &mut self,
         paths: &[Path<ScaledPixels>],
@@ -1206,6 +1333,13 @@ enum QuadInputIndex {
     ViewportSize = 2,
 }
 
+#[repr(C)]
+enum GradientInputIndex {
+    Vertices = 0,
+    Gradients = 1,
+    ViewportSize = 2,
+}
+
 #[repr(C)]
 enum UnderlineInputIndex {
     Vertices = 0,
@@ -21,6 +21,8 @@ float2 erf(float2 x);
 float blur_along_x(float x, float y, float sigma, float corner,
                    float2 half_size);
 float4 over(float4 below, float4 above);
+float3 srgb_to_linear(float3 color);
+float3 linear_to_srgb(float3 color);
 
 struct QuadVertexOutput {
   float4 position [[position]];
@@ -133,6 +135,77 @@ fragment float4 quad_fragment(QuadFragmentInput input [[stage_in]],
   return color * float4(1., 1., 1., saturate(0.5 - distance));
 }
 
+struct GradientVertexOutput {
+  float4 position [[position]];
+  uint gradient_id [[flat]];
+  float clip_distance [[clip_distance]][4];
+};
+
+struct GradientFragmentInput {
+  float4 position [[position]];
+  uint gradient_id [[flat]];
+};
+
+vertex GradientVertexOutput gradient_vertex(
+    uint unit_vertex_id [[vertex_id]], uint gradient_id [[instance_id]],
+    constant float2 *unit_vertices [[buffer(GradientInputIndex_Vertices)]],
+    constant Gradient *gradients [[buffer(GradientInputIndex_Gradients)]],
+    constant Size_DevicePixels *viewport_size
+    [[buffer(GradientInputIndex_ViewportSize)]]) {
+  float2 unit_vertex = unit_vertices[unit_vertex_id];
+  Gradient gradient = gradients[gradient_id];
+  float4 device_position =
+      to_device_position(unit_vertex, gradient.bounds, viewport_size);
+  float4 clip_distance = distance_from_clip_rect(unit_vertex, gradient.bounds,
+                                                 gradient.content_mask.bounds);
+  return GradientVertexOutput{
+      device_position,
+      gradient_id,
+      {clip_distance.x, clip_distance.y, clip_distance.z, clip_distance.w}};
+}
+
+fragment float4 gradient_fragment(GradientFragmentInput input [[stage_in]],
+                                  constant Gradient *gradients
+                                  [[buffer(GradientInputIndex_Gradients)]]) {
+  Gradient gradient = gradients[input.gradient_id];
+  float2 size = float2(gradient.bounds.size.width, gradient.bounds.size.height);
+  float2 center =
+      float2(gradient.bounds.origin.x, gradient.bounds.origin.y) + size / 2.;
+
+  // Project the point onto the gradient line, which passes through the center
+  // of the bounds and is long enough for the first and last stops to reach the
+  // corners, like in CSS.
+  float2 direction = float2(sin(gradient.angle), -cos(gradient.angle));
+  float line_length =
+      fabs(size.x * direction.x) + fabs(size.y * direction.y);
+  float t = dot(input.position.xy - center, direction) / max(line_length, 1.) +
+            0.5;
+
+  // Interpolate the stops in linear space, premultiplied so that transparent
+  // stops don't darken their neighbors.
+  float4 color = hsla_to_rgba(gradient.stops[0].color);
+  color = float4(srgb_to_linear(color.rgb) * color.a, color.a);
+  for (uint i = 1; i < gradient.stop_count; i++) {
+    GradientStop start = gradient.stops[i - 1];
+    GradientStop end = gradient.stops[i];
+    if (t >= start.offset) {
+      float4 start_color = hsla_to_rgba(start.color);
+      float4 end_color = hsla_to_rgba(end.color);
+      float progress =
+          saturate((t - start.offset) / max(end.offset - start.offset, 0.0001));
+      color = mix(
+          float4(srgb_to_linear(start_color.rgb) * start_color.a, start_color.a),
+          float4(srgb_to_linear(end_color.rgb) * end_color.a, end_color.a),
+          progress);
+    }
+  }
+  color = float4(linear_to_srgb(color.rgb / max(color.a, 0.0001)), color.a);
+
+  float distance =
+      quad_sdf(input.position.xy, gradient.bounds, gradient.corner_radii);
+  return color * float4(1., 1., 1., saturate(0.5 - distance));
+}
+
 struct ShadowVertexOutput {
   float4 position [[position]];
   float4 color [[flat]];
@@ -385,7 +458,7 @@ fragment float4 polychrome_sprite_fragment(
     color.g = grayscale;
     color.b = grayscale;
   }
-  color.a *= saturate(0.5 - distance);
+  color.a *= sprite.opacity * saturate(0.5 - distance);
   return color;
 }
 
@@ -683,6 +756,18 @@ float4 distance_from_clip_rect(float2 unit_vertex, Bounds_ScaledPixels bounds,
                 clip_bounds.origin.y + clip_bounds.size.height - position.y);
 }
 
+float3 srgb_to_linear(float3 color) {
+  float3 higher = pow((color + 0.055) / 1.055, 2.4);
+  float3 lower = color / 12.92;
+  return select(higher, lower, color < 0.04045);
+}
+
+float3 linear_to_srgb(float3 color) {
+  float3 higher = 1.055 * pow(color, 1. / 2.4) - 0.055;
+  float3 lower = color * 12.92;
+  return select(higher, lower, color < 0.0031308);
+}
+
 float4 over(float4 below, float4 above) {
   float4 result;
   float alpha = above.a + below.a * (1.0 - above.a);
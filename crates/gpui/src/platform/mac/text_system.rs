@@ -1,7 +1,8 @@
 use crate::{
     point, px, size, Bounds, DevicePixels, Font, FontFallbacks, FontFeatures, FontId, FontMetrics,
     FontRun, FontStyle, FontWeight, GlyphId, LineLayout, Pixels, PlatformTextSystem, Point,
-    RenderGlyphParams, Result, ShapedGlyph, ShapedRun, SharedString, Size, SUBPIXEL_VARIANTS,
+    RenderGlyphParams, Result, ShapedGlyph, ShapedRun, SharedString, Size, TextAntialiasing,
+    SUBPIXEL_VARIANTS,
 };
 use anyhow::anyhow;
 use cocoa::appkit::{CGFloat, CGPoint};
@@ -410,6 +411,9 @@ impl MacTextSystemState {
             cx.set_should_subpixel_position_fonts(true);
             cx.set_allows_font_subpixel_quantization(false);
             cx.set_should_subpixel_quantize_fonts(false);
+            let smooth = params.antialiasing == TextAntialiasing::FontSmoothing;
+            cx.set_allows_font_smoothing(smooth);
+            cx.set_should_smooth_fonts(smooth);
             self.fonts[params.font_id.0]
                 .native_font()
                 .clone_with_font_size(f32::from(params.font_size) as CGFloat)
@@ -498,6 +502,10 @@ impl MacTextSystemState {
                 .zip(run.string_indices().iter())
             {
                 let glyph_utf16_ix = usize::try_from(*glyph_utf16_ix).unwrap();
+                // The glyphs of right-to-left runs are in visual order, so their indices decrease.
+                if glyph_utf16_ix < ix_converter.utf16_ix {
+                    ix_converter = StringIndexConverter::new(text);
+                }
                 ix_converter.advance_to_utf16_ix(glyph_utf16_ix);
                 glyphs.push(ShapedGlyph {
                     id: GlyphId(*glyph_id as u32),
@@ -518,6 +526,8 @@ impl MacTextSystemState {
             ascent: typographic_bounds.ascent.into(),
             descent: typographic_bounds.descent.into(),
             len: text.len(),
+            // Right-to-left text is shaped a run at a time by `layout_bidi_line`.
+            has_rtl: false,
         }
     }
 }
@@ -23,6 +23,7 @@ use cocoa::{
 use core_graphics::display::{CGDirectDisplayID, CGRect};
 use ctor::ctor;
 use futures::channel::oneshot;
+use image::RgbaImage;
 use objc::{
     class,
     declare::ClassDecl,
@@ -1112,6 +1113,11 @@ impl PlatformWindow for MacWindow {
         this.renderer.draw(scene);
     }
 
+    fn render_to_image(&self, scene: &crate::Scene) -> anyhow::Result<RgbaImage> {
+        let mut this = self.0.lock();
+        this.renderer.render_to_image(scene)
+    }
+
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas> {
         self.0.lock().renderer.sprite_atlas().clone()
     }
@@ -0,0 +1,430 @@
+use crate::{
+    AtlasKey, AtlasTextureId, AtlasTextureKind, AtlasTile, Bounds, DevicePixels,
+    DispatchEventResult, GPUSpecs, Modifiers, MouseMoveEvent, Pixels, PlatformAtlas,
+    PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow, Point, PromptLevel,
+    Scene, Size, TileId, WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowParams,
+};
+use anyhow::Result;
+use collections::HashMap;
+use futures::channel::oneshot;
+use image::RgbaImage;
+use parking_lot::Mutex;
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::{borrow::Cow, cell::RefCell, rc::Rc, sync::Arc};
+
+struct OffscreenWindowState {
+    bounds: Bounds<Pixels>,
+    scale_factor: f32,
+    mouse_position: Point<Pixels>,
+    sprite_atlas: Arc<OffscreenAtlas>,
+    input_handler: Option<PlatformInputHandler>,
+    request_frame_callback: Option<Box<dyn FnMut()>>,
+    input_callback: Option<Box<dyn FnMut(PlatformInput) -> DispatchEventResult>>,
+    resize_callback: Option<Box<dyn FnMut(Size<Pixels>, f32)>>,
+    should_close_callback: Option<Box<dyn FnMut() -> bool>>,
+    close_callback: Option<Box<dyn FnOnce()>>,
+    frame_count: usize,
+}
+
+/// A window that isn't shown on any display, opened with
+/// [`WindowOptions::offscreen`](crate::WindowOptions::offscreen).
+///
+/// Its scenes are only kept in memory, so it can be opened without a real platform, such as in
+/// tests that inspect what a view paints. They're never rasterized, so capturing the window with
+/// [`WindowContext::capture_image`](crate::WindowContext::capture_image) returns a blank image
+/// that only has the window's size. Its scale factor, size and the mouse position are only
+/// changed through its setters, which keeps them the same from run to run. The setters notify
+/// the window, so they have to be called outside of its updates.
+#[derive(Clone)]
+pub struct OffscreenWindow(Rc<RefCell<OffscreenWindowState>>);
+
+impl OffscreenWindow {
+    pub(crate) fn new(params: WindowParams) -> Self {
+        Self(Rc::new(RefCell::new(OffscreenWindowState {
+            bounds: params.bounds,
+            scale_factor: 1.,
+            mouse_position: Point::default(),
+            sprite_atlas: Arc::new(OffscreenAtlas::new()),
+            input_handler: None,
+            request_frame_callback: None,
+            input_callback: None,
+            resize_callback: None,
+            should_close_callback: None,
+            close_callback: None,
+            frame_count: 0,
+        })))
+    }
+
+    /// Sets the size of the window's content, and resizes its views to it.
+    pub fn set_content_size(&self, size: Size<Pixels>) {
+        self.0.borrow_mut().bounds.size = size;
+        self.resized();
+    }
+
+    /// Sets the number of device pixels in each of the window's pixels.
+    pub fn set_scale_factor(&self, scale_factor: f32) {
+        self.0.borrow_mut().scale_factor = scale_factor;
+        self.resized();
+    }
+
+    /// Moves the mouse to the given position in the window, dispatching the move to its views.
+    pub fn set_mouse_position(&self, position: Point<Pixels>) {
+        self.0.borrow_mut().mouse_position = position;
+        self.dispatch_input(PlatformInput::MouseMove(MouseMoveEvent {
+            position,
+            pressed_button: None,
+            modifiers: Modifiers::default(),
+        }));
+    }
+
+    /// Draws the window's next frame, if it has changed since the last one.
+    pub fn request_frame(&self) {
+        let mut state = self.0.borrow_mut();
+        let Some(mut callback) = state.request_frame_callback.take() else {
+            return;
+        };
+        drop(state);
+        callback();
+        self.0.borrow_mut().request_frame_callback = Some(callback);
+    }
+
+    /// Returns how many frames have been drawn in the window.
+    pub fn frame_count(&self) -> usize {
+        self.0.borrow().frame_count
+    }
+
+    /// Closes the window, as if its close button had been clicked.
+    pub fn close(&self) {
+        let mut state = self.0.borrow_mut();
+        let Some(mut should_close) = state.should_close_callback.take() else {
+            return;
+        };
+        drop(state);
+        if should_close() {
+            let close = self.0.borrow_mut().close_callback.take();
+            if let Some(close) = close {
+                close();
+            }
+        } else {
+            self.0.borrow_mut().should_close_callback = Some(should_close);
+        }
+    }
+
+    fn resized(&self) {
+        let mut state = self.0.borrow_mut();
+        let (size, scale_factor) = (state.bounds.size, state.scale_factor);
+        let Some(mut callback) = state.resize_callback.take() else {
+            return;
+        };
+        drop(state);
+        callback(size, scale_factor);
+        self.0.borrow_mut().resize_callback = Some(callback);
+    }
+
+    fn dispatch_input(&self, event: PlatformInput) {
+        let mut state = self.0.borrow_mut();
+        let Some(mut callback) = state.input_callback.take() else {
+            return;
+        };
+        drop(state);
+        callback(event);
+        self.0.borrow_mut().input_callback = Some(callback);
+    }
+}
+
+impl HasWindowHandle for OffscreenWindow {
+    fn window_handle(
+        &self,
+    ) -> Result<raw_window_handle::WindowHandle<'_>, raw_window_handle::HandleError> {
+        Err(raw_window_handle::HandleError::Unavailable)
+    }
+}
+
+impl HasDisplayHandle for OffscreenWindow {
+    fn display_handle(
+        &self,
+    ) -> Result<raw_window_handle::DisplayHandle<'_>, raw_window_handle::HandleError> {
+        Err(raw_window_handle::HandleError::Unavailable)
+    }
+}
+
+impl PlatformWindow for OffscreenWindow {
+    fn bounds(&self) -> Bounds<Pixels> {
+        self.0.borrow().bounds
+    }
+
+    fn is_maximized(&self) -> bool {
+        false
+    }
+
+    fn window_bounds(&self) -> WindowBounds {
+        WindowBounds::Windowed(self.bounds())
+    }
+
+    fn content_size(&self) -> Size<Pixels> {
+        self.bounds().size
+    }
+
+    fn scale_factor(&self) -> f32 {
+        self.0.borrow().scale_factor
+    }
+
+    fn appearance(&self) -> WindowAppearance {
+        WindowAppearance::Light
+    }
+
+    fn display(&self) -> Option<Rc<dyn PlatformDisplay>> {
+        None
+    }
+
+    fn mouse_position(&self) -> Point<Pixels> {
+        self.0.borrow().mouse_position
+    }
+
+    fn modifiers(&self) -> Modifiers {
+        Modifiers::default()
+    }
+
+    fn set_input_handler(&mut self, input_handler: PlatformInputHandler) {
+        self.0.borrow_mut().input_handler = Some(input_handler);
+    }
+
+    fn take_input_handler(&mut self) -> Option<PlatformInputHandler> {
+        self.0.borrow_mut().input_handler.take()
+    }
+
+    /// There's no platform dialog to show prompts in, so they're drawn in the window itself.
+    fn prompt(
+        &self,
+        _level: PromptLevel,
+        _msg: &str,
+        _detail: Option<&str>,
+        _answers: &[&str],
+    ) -> Option<oneshot::Receiver<usize>> {
+        None
+    }
+
+    fn activate(&self) {}
+
+    fn is_active(&self) -> bool {
+        false
+    }
+
+    fn is_hovered(&self) -> bool {
+        false
+    }
+
+    fn set_title(&mut self, _title: &str) {}
+
+    fn set_background_appearance(&self, _background_appearance: WindowBackgroundAppearance) {}
+
+    fn minimize(&self) {}
+
+    fn zoom(&self) {}
+
+    fn toggle_fullscreen(&self) {}
+
+    fn is_fullscreen(&self) -> bool {
+        false
+    }
+
+    fn on_request_frame(&self, callback: Box<dyn FnMut()>) {
+        self.0.borrow_mut().request_frame_callback = Some(callback);
+    }
+
+    fn on_input(&self, callback: Box<dyn FnMut(PlatformInput) -> DispatchEventResult>) {
+        self.0.borrow_mut().input_callback = Some(callback);
+    }
+
+    fn on_active_status_change(&self, _callback: Box<dyn FnMut(bool)>) {}
+
+    fn on_hover_status_change(&self, _callback: Box<dyn FnMut(bool)>) {}
+
+    fn on_resize(&self, callback: Box<dyn FnMut(Size<Pixels>, f32)>) {
+        self.0.borrow_mut().resize_callback = Some(callback);
+    }
+
+    fn on_moved(&self, _callback: Box<dyn FnMut()>) {}
+
+    fn on_should_close(&self, callback: Box<dyn FnMut() -> bool>) {
+        self.0.borrow_mut().should_close_callback = Some(callback);
+    }
+
+    fn on_close(&self, callback: Box<dyn FnOnce()>) {
+        self.0.borrow_mut().close_callback = Some(callback);
+    }
+
+    fn on_appearance_changed(&self, _callback: Box<dyn FnMut()>) {}
+
+    fn draw(&self, _scene: &Scene) {
+        self.0.borrow_mut().frame_count += 1;
+    }
+
+    /// Scenes aren't rasterized offscreen, so this returns a blank, fully transparent image that
+    /// only has the window's device resolution.
+    fn render_to_image(&self, _scene: &Scene) -> Result<RgbaImage> {
+        let size = self.content_size().scale(self.scale_factor());
+        Ok(RgbaImage::new(
+            size.width.0.ceil() as u32,
+            size.height.0.ceil() as u32,
+        ))
+    }
+
+    fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas> {
+        self.0.borrow().sprite_atlas.clone()
+    }
+
+    #[cfg(target_os = "windows")]
+    fn get_raw_handle(&self) -> windows::Win32::Foundation::HWND {
+        windows::Win32::Foundation::HWND::default()
+    }
+
+    fn gpu_specs(&self) -> Option<GPUSpecs> {
+        None
+    }
+
+    fn as_offscreen(&self) -> Option<&OffscreenWindow> {
+        Some(self)
+    }
+}
+
+struct OffscreenAtlasState {
+    next_id: u32,
+    tiles: HashMap<AtlasKey, AtlasTile>,
+}
+
+/// An atlas that hands out tiles without uploading the textures they're rasterized into, for
+/// windows whose scenes aren't rendered.
+pub(crate) struct OffscreenAtlas(Mutex<OffscreenAtlasState>);
+
+impl OffscreenAtlas {
+    pub fn new() -> Self {
+        OffscreenAtlas(Mutex::new(OffscreenAtlasState {
+            next_id: 0,
+            tiles: HashMap::default(),
+        }))
+    }
+}
+
+impl PlatformAtlas for OffscreenAtlas {
+    fn get_or_insert_with<'a>(
+        &self,
+        key: &AtlasKey,
+        build: &mut dyn FnMut() -> Result<Option<(Size<DevicePixels>, Cow<'a, [u8]>)>>,
+    ) -> Result<Option<AtlasTile>> {
+        let mut state = self.0.lock();
+        if let Some(tile) = state.tiles.get(key) {
+            return Ok(Some(tile.clone()));
+        }
+        drop(state);
+
+        let Some((size, _)) = build()? else {
+            return Ok(None);
+        };
+
+        let mut state = self.0.lock();
+        state.next_id += 1;
+        let texture_id = state.next_id;
+        state.next_id += 1;
+        let tile_id = state.next_id;
+
+        state.tiles.insert(
+            key.clone(),
+            AtlasTile {
+                texture_id: AtlasTextureId {
+                    index: texture_id,
+                    kind: AtlasTextureKind::Path,
+                },
+                tile_id: TileId(tile_id),
+                padding: 0,
+                bounds: Bounds {
+                    origin: Point::default(),
+                    size,
+                },
+            },
+        );
+
+        Ok(Some(state.tiles[key].clone()))
+    }
+
+    fn retain(&self, predicate: &mut dyn FnMut(&AtlasKey) -> bool) {
+        self.0.lock().tiles.retain(|key, _| predicate(key));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as gpui, div, point, px, size, Bounds, InteractiveElement, IntoElement, ParentElement,
+        Render, ScaledPixels, StatefulInteractiveElement, Styled, TestAppContext, ViewContext,
+        VisualContext, WindowBounds, WindowOptions,
+    };
+
+    #[derive(Default)]
+    struct Card {
+        hovered: bool,
+    }
+
+    impl Render for Card {
+        fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .size_full()
+                .child(
+                    div()
+                        .id("card")
+                        .size(px(50.))
+                        .bg(gpui::red())
+                        .on_hover(cx.listener(|this, hovered, cx| {
+                            this.hovered = *hovered;
+                            cx.notify();
+                        })),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn test_offscreen_window(cx: &mut TestAppContext) {
+        let window = cx
+            .update(|cx| {
+                cx.open_window(
+                    WindowOptions {
+                        offscreen: true,
+                        window_bounds: Some(WindowBounds::Windowed(Bounds::new(
+                            point(px(0.), px(0.)),
+                            size(px(200.), px(100.)),
+                        ))),
+                        ..Default::default()
+                    },
+                    |cx| cx.new_view(|_| Card::default()),
+                )
+            })
+            .unwrap();
+        let offscreen = window
+            .update(cx, |_, cx| cx.offscreen_window())
+            .unwrap()
+            .expect("the window is offscreen");
+
+        offscreen.set_scale_factor(2.);
+        offscreen.set_content_size(size(px(400.), px(300.)));
+        offscreen.set_mouse_position(point(px(10.), px(10.)));
+        cx.run_until_parked();
+
+        window
+            .update(cx, |card, cx| {
+                assert!(card.hovered);
+                assert_eq!(cx.viewport_size(), size(px(400.), px(300.)));
+                assert_eq!(cx.mouse_position(), point(px(10.), px(10.)));
+
+                // The card is painted in device pixels.
+                let card_size = size(ScaledPixels(100.), ScaledPixels(100.));
+                assert!(cx
+                    .window
+                    .rendered_frame
+                    .scene
+                    .quads
+                    .iter()
+                    .any(|quad| quad.bounds.size == card_size));
+            })
+            .unwrap();
+    }
+}
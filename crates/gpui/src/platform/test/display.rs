@@ -1,11 +1,13 @@
 use crate::{px, Bounds, DisplayId, Pixels, PlatformDisplay, Point};
 use anyhow::{Ok, Result};
+use std::cell::Cell;
 
 #[derive(Debug)]
 pub(crate) struct TestDisplay {
     id: DisplayId,
     uuid: uuid::Uuid,
     bounds: Bounds<Pixels>,
+    visible_bounds: Cell<Option<Bounds<Pixels>>>,
 }
 
 impl TestDisplay {
@@ -14,8 +16,15 @@ impl TestDisplay {
             id: DisplayId(1),
             uuid: uuid::Uuid::new_v4(),
             bounds: Bounds::from_corners(Point::default(), Point::new(px(1920.), px(1080.))),
+            visible_bounds: Cell::new(None),
         }
     }
+
+    /// Sets the part of the display that isn't covered by system UI, which is all of it by
+    /// default.
+    pub fn set_visible_bounds(&self, visible_bounds: Bounds<Pixels>) {
+        self.visible_bounds.set(Some(visible_bounds));
+    }
 }
 
 impl PlatformDisplay for TestDisplay {
@@ -30,4 +39,8 @@ impl PlatformDisplay for TestDisplay {
     fn bounds(&self) -> crate::Bounds<crate::Pixels> {
         self.bounds
     }
+
+    fn visible_bounds(&self) -> crate::Bounds<crate::Pixels> {
+        self.visible_bounds.get().unwrap_or(self.bounds)
+    }
 }
@@ -1,7 +1,7 @@
 use crate::{
-    AnyWindowHandle, BackgroundExecutor, ClipboardItem, CursorStyle, ForegroundExecutor, Keymap,
-    Platform, PlatformDisplay, PlatformTextSystem, Task, TestDisplay, TestWindow, WindowAppearance,
-    WindowParams,
+    AnyWindowHandle, BackgroundExecutor, Bounds, ClipboardItem, CursorStyle, ForegroundExecutor,
+    Keymap, Pixels, Platform, PlatformDisplay, PlatformTextSystem, Task, TestDisplay, TestWindow,
+    WindowAppearance, WindowParams,
 };
 use anyhow::Result;
 use collections::VecDeque;
@@ -25,7 +25,7 @@ pub(crate) struct TestPlatform {
     foreground_executor: ForegroundExecutor,
 
     pub(crate) active_window: RefCell<Option<TestWindow>>,
-    active_display: Rc<dyn PlatformDisplay>,
+    active_display: Rc<TestDisplay>,
     active_cursor: Mutex<CursorStyle>,
     current_clipboard_item: Mutex<Option<ClipboardItem>>,
     #[cfg(target_os = "linux")]
@@ -110,6 +110,10 @@ impl TestPlatform {
         tx.send(response_ix).ok();
     }
 
+    pub(crate) fn set_display_visible_bounds(&self, visible_bounds: Bounds<Pixels>) {
+        self.active_display.set_visible_bounds(visible_bounds);
+    }
+
     pub(crate) fn has_pending_prompt(&self) -> bool {
         !self.prompts.borrow().multiple_choice.is_empty()
     }
@@ -189,11 +193,11 @@ impl Platform for TestPlatform {
     }
 
     fn displays(&self) -> Vec<std::rc::Rc<dyn crate::PlatformDisplay>> {
-        vec![self.active_display.clone()]
+        vec![self.active_display.clone() as Rc<dyn PlatformDisplay>]
     }
 
     fn primary_display(&self) -> Option<std::rc::Rc<dyn crate::PlatformDisplay>> {
-        Some(self.active_display.clone())
+        Some(self.active_display.clone() as Rc<dyn PlatformDisplay>)
     }
 
     fn active_window(&self) -> Option<crate::AnyWindowHandle> {
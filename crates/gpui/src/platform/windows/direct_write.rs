@@ -614,6 +614,8 @@ impl DirectWriteState {
                 descent,
                 runs,
                 len: text.len(),
+                // Right-to-left text is shaped a run at a time by `layout_bidi_line`.
+                has_rtl: false,
             })
         }
     }
@@ -13,6 +13,7 @@ use std::{
 use ::util::ResultExt;
 use anyhow::{Context, Result};
 use futures::channel::oneshot::{self, Receiver};
+use image::RgbaImage;
 use itertools::Itertools;
 use raw_window_handle as rwh;
 use smallvec::SmallVec;
@@ -664,6 +665,10 @@ impl PlatformWindow for WindowsWindow {
         self.0.state.borrow_mut().renderer.draw(scene)
     }
 
+    fn render_to_image(&self, scene: &Scene) -> Result<RgbaImage> {
+        self.0.state.borrow_mut().renderer.render_to_image(scene)
+    }
+
     fn sprite_atlas(&self) -> Arc<dyn PlatformAtlas> {
         self.0.state.borrow().renderer.sprite_atlas().clone()
     }
@@ -3,22 +3,68 @@
 
 use crate::{
     bounds_tree::BoundsTree, point, AtlasTextureId, AtlasTile, Bounds, ContentMask, Corners, Edges,
-    Hsla, Pixels, Point, Radians, ScaledPixels, Size,
+    Hsla, IsZero, Pixels, Point, Radians, ScaledPixels, Size,
 };
-use std::{fmt::Debug, iter::Peekable, ops::Range, slice};
+use collections::FxHashSet;
+use serde_derive::Serialize;
+use std::{cmp::Ordering, fmt::Debug, iter::Peekable, ops::Range, slice};
 
 #[allow(non_camel_case_types, unused)]
 pub(crate) type PathVertex_ScaledPixels = PathVertex<ScaledPixels>;
 
 pub(crate) type DrawOrder = u32;
 
-#[derive(Default)]
+/// Compares sprites by draw order, grouping those that share one by texture, unless it's the
+/// draw order of a layer, inside which sprites may overlap and have to stay in insertion order.
+fn compare_sprites(
+    layer_orders: &FxHashSet<DrawOrder>,
+    (a_order, a_tile): (DrawOrder, &AtlasTile),
+    (b_order, b_tile): (DrawOrder, &AtlasTile),
+) -> Ordering {
+    a_order.cmp(&b_order).then_with(|| {
+        if layer_orders.contains(&a_order) {
+            Ordering::Equal
+        } else {
+            a_tile
+                .texture_id
+                .cmp(&b_tile.texture_id)
+                .then_with(|| a_tile.tile_id.cmp(&b_tile.tile_id))
+        }
+    })
+}
+
+/// How many opaque quads [`Scene::cull_occluded`] checks the other primitives against. Only the
+/// largest are checked, such as window backgrounds and cards, as they hide the most.
+const MAX_OCCLUDERS: usize = 16;
+
+/// The primitives painted in a frame, sorted by draw order once the frame is finished.
+///
+/// Primitives are drawn by their draw order, and then in the order they were inserted, so that
+/// those sharing a draw order are painted over each other in a stable way from frame to frame.
+/// Primitives of different kinds that share a draw order are drawn kind by kind, in the order of
+/// [`PrimitiveKind`].
+///
+/// The only exception is sprites that share a draw order outside of a layer, which are grouped
+/// by texture to be drawn in fewer batches. Outside of layers, every primitive is given a draw
+/// order above those of the primitives it overlaps, so such sprites never overlap each other.
+///
+/// Serializing a scene captures its primitives, so that tests can compare what a view paints
+/// against a snapshot.
+#[derive(Default, Serialize)]
 pub(crate) struct Scene {
+    #[serde(skip)]
     pub(crate) paint_operations: Vec<PaintOperation>,
+    #[serde(skip)]
     primitive_bounds: BoundsTree<ScaledPixels>,
+    #[serde(skip)]
     layer_stack: Vec<DrawOrder>,
+    /// The draw orders of the layers pushed in this frame, which the primitives inside them share
+    /// however they overlap.
+    #[serde(skip)]
+    layer_orders: FxHashSet<DrawOrder>,
     pub(crate) shadows: Vec<Shadow>,
     pub(crate) quads: Vec<Quad>,
+    pub(crate) gradients: Vec<Gradient>,
     pub(crate) paths: Vec<Path<ScaledPixels>>,
     pub(crate) underlines: Vec<Underline>,
     pub(crate) monochrome_sprites: Vec<MonochromeSprite>,
@@ -31,9 +77,11 @@ impl Scene {
         self.paint_operations.clear();
         self.primitive_bounds.clear();
         self.layer_stack.clear();
+        self.layer_orders.clear();
         self.paths.clear();
         self.shadows.clear();
         self.quads.clear();
+        self.gradients.clear();
         self.underlines.clear();
         self.monochrome_sprites.clear();
         self.polychrome_sprites.clear();
@@ -48,9 +96,33 @@ impl Scene {
         self.paint_operations.len()
     }
 
+    /// Returns the atlas tiles of the sprites painted in the scene, including those dropped by
+    /// [`Scene::cull_occluded`], which can still be replayed into later frames.
+    pub fn painted_sprite_tiles(&self) -> impl Iterator<Item = &AtlasTile> {
+        self.paint_operations
+            .iter()
+            .filter_map(|operation| match operation {
+                PaintOperation::Primitive(Primitive::MonochromeSprite(sprite)) => {
+                    Some(&sprite.tile)
+                }
+                PaintOperation::Primitive(Primitive::PolychromeSprite(sprite)) => {
+                    Some(&sprite.tile)
+                }
+                _ => None,
+            })
+    }
+
+    pub fn primitive_count(&self) -> usize {
+        self.paint_operations
+            .iter()
+            .filter(|operation| matches!(operation, PaintOperation::Primitive(_)))
+            .count()
+    }
+
     pub fn push_layer(&mut self, bounds: Bounds<ScaledPixels>) {
         let order = self.primitive_bounds.insert(bounds);
         self.layer_stack.push(order);
+        self.layer_orders.insert(order);
         self.paint_operations
             .push(PaintOperation::StartLayer(bounds));
     }
@@ -84,6 +156,10 @@ impl Scene {
                 quad.order = order;
                 self.quads.push(quad.clone());
             }
+            Primitive::Gradient(gradient) => {
+                gradient.order = order;
+                self.gradients.push(gradient.clone());
+            }
             Primitive::Path(path) => {
                 path.order = order;
                 path.id = PathId(self.paths.len());
@@ -120,16 +196,133 @@ impl Scene {
         }
     }
 
+    /// Replays the given paint operations of the previous scene moved by `offset`, for an element
+    /// that has only moved since it was painted, such as by scrolling.
+    ///
+    /// Content masks equal to `inherited_mask`, the mask the element was painted within, came
+    /// from outside the element and are kept where they are. The others were narrowed by elements
+    /// inside it, so they move with them.
+    pub fn replay_with_offset(
+        &mut self,
+        range: Range<usize>,
+        prev_scene: &Scene,
+        offset: Point<ScaledPixels>,
+        inherited_mask: &ContentMask<ScaledPixels>,
+    ) {
+        for operation in &prev_scene.paint_operations[range] {
+            match operation {
+                PaintOperation::Primitive(primitive) => {
+                    let mut primitive = primitive.clone();
+                    primitive.offset(offset, inherited_mask);
+                    self.insert_primitive(primitive);
+                }
+                PaintOperation::StartLayer(bounds) => self.push_layer(Bounds {
+                    origin: bounds.origin + offset,
+                    size: bounds.size,
+                }),
+                PaintOperation::EndLayer => self.pop_layer(),
+            }
+        }
+    }
+
     pub fn finish(&mut self) {
         self.shadows.sort();
         self.quads.sort();
+        self.gradients.sort();
         self.paths.sort();
         self.underlines.sort();
-        self.monochrome_sprites.sort();
-        self.polychrome_sprites.sort();
+        // The sorts are stable, which keeps primitives that compare equal in insertion order.
+        let layer_orders = &self.layer_orders;
+        self.monochrome_sprites
+            .sort_by(|a, b| compare_sprites(layer_orders, (a.order, &a.tile), (b.order, &b.tile)));
+        self.polychrome_sprites
+            .sort_by(|a, b| compare_sprites(layer_orders, (a.order, &a.tile), (b.order, &b.tile)));
         self.surfaces.sort();
     }
 
+    /// Drops the primitives that are entirely hidden behind an opaque quad drawn above them, so
+    /// that they aren't rendered, returning how many were dropped. Must be called after
+    /// [`Scene::finish`].
+    ///
+    /// Only quads with an opaque background and square corners hide what's beneath them. Paths
+    /// and surfaces are always kept, as are sprites that are transformed, since their bounds
+    /// don't describe where they're drawn. The paint operations are left as they are, so the
+    /// dropped primitives are still replayed into later frames.
+    pub fn cull_occluded(&mut self) -> usize {
+        let mut occluders = self
+            .quads
+            .iter()
+            .filter(|quad| quad.background.a >= 1. && quad.corner_radii.is_zero())
+            .map(|quad| (quad.order, quad.bounds.intersect(&quad.content_mask.bounds)))
+            .collect::<Vec<_>>();
+        if occluders.is_empty() {
+            return 0;
+        }
+        occluders.sort_by(|(_, a), (_, b)| area(b).total_cmp(&area(a)));
+        occluders.truncate(MAX_OCCLUDERS);
+
+        let is_occluded = |order: DrawOrder, bounds: Bounds<ScaledPixels>| {
+            occluders.iter().any(|(occluder_order, occluder_bounds)| {
+                // Primitives sharing the quad's order may be drawn after it.
+                *occluder_order > order && contains(occluder_bounds, &bounds)
+            })
+        };
+        let visible_bounds =
+            |bounds: &Bounds<ScaledPixels>, content_mask: &ContentMask<ScaledPixels>| {
+                bounds.intersect(&content_mask.bounds)
+            };
+
+        let count_before = self.shadows.len()
+            + self.quads.len()
+            + self.gradients.len()
+            + self.underlines.len()
+            + self.monochrome_sprites.len()
+            + self.polychrome_sprites.len();
+
+        self.shadows.retain(|shadow| {
+            // Blurred shadows fade out beyond their bounds.
+            let mut bounds = shadow.bounds.clone();
+            bounds.dilate(shadow.blur_radius + shadow.blur_radius + shadow.blur_radius);
+            !is_occluded(shadow.order, visible_bounds(&bounds, &shadow.content_mask))
+        });
+        self.quads.retain(|quad| {
+            !is_occluded(quad.order, visible_bounds(&quad.bounds, &quad.content_mask))
+        });
+        self.gradients.retain(|gradient| {
+            !is_occluded(
+                gradient.order,
+                visible_bounds(&gradient.bounds, &gradient.content_mask),
+            )
+        });
+        self.underlines.retain(|underline| {
+            !is_occluded(
+                underline.order,
+                visible_bounds(&underline.bounds, &underline.content_mask),
+            )
+        });
+        self.monochrome_sprites.retain(|sprite| {
+            sprite.transformation != TransformationMatrix::unit()
+                || !is_occluded(
+                    sprite.order,
+                    visible_bounds(&sprite.bounds, &sprite.content_mask),
+                )
+        });
+        self.polychrome_sprites.retain(|sprite| {
+            !is_occluded(
+                sprite.order,
+                visible_bounds(&sprite.bounds, &sprite.content_mask),
+            )
+        });
+
+        count_before
+            - (self.shadows.len()
+                + self.quads.len()
+                + self.gradients.len()
+                + self.underlines.len()
+                + self.monochrome_sprites.len()
+                + self.polychrome_sprites.len())
+    }
+
     pub(crate) fn batches(&self) -> impl Iterator<Item = PrimitiveBatch> {
         BatchIterator {
             shadows: &self.shadows,
@@ -138,6 +331,9 @@ impl Scene {
             quads: &self.quads,
             quads_start: 0,
             quads_iter: self.quads.iter().peekable(),
+            gradients: &self.gradients,
+            gradients_start: 0,
+            gradients_iter: self.gradients.iter().peekable(),
             paths: &self.paths,
             paths_start: 0,
             paths_iter: self.paths.iter().peekable(),
@@ -157,11 +353,24 @@ impl Scene {
     }
 }
 
+fn area(bounds: &Bounds<ScaledPixels>) -> f32 {
+    bounds.size.width.0 * bounds.size.height.0
+}
+
+/// Returns whether `inner` lies entirely within `outer`.
+fn contains(outer: &Bounds<ScaledPixels>, inner: &Bounds<ScaledPixels>) -> bool {
+    inner.left() >= outer.left()
+        && inner.top() >= outer.top()
+        && inner.right() <= outer.right()
+        && inner.bottom() <= outer.bottom()
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Default)]
 pub(crate) enum PrimitiveKind {
     Shadow,
     #[default]
     Quad,
+    Gradient,
     Path,
     Underline,
     MonochromeSprite,
@@ -179,6 +388,7 @@ pub(crate) enum PaintOperation {
 pub(crate) enum Primitive {
     Shadow(Shadow),
     Quad(Quad),
+    Gradient(Gradient),
     Path(Path<ScaledPixels>),
     Underline(Underline),
     MonochromeSprite(MonochromeSprite),
@@ -191,6 +401,7 @@ impl Primitive {
         match self {
             Primitive::Shadow(shadow) => &shadow.bounds,
             Primitive::Quad(quad) => &quad.bounds,
+            Primitive::Gradient(gradient) => &gradient.bounds,
             Primitive::Path(path) => &path.bounds,
             Primitive::Underline(underline) => &underline.bounds,
             Primitive::MonochromeSprite(sprite) => &sprite.bounds,
@@ -199,10 +410,51 @@ impl Primitive {
         }
     }
 
+    /// Moves the primitive by the given offset, along with its content mask unless the mask is
+    /// `inherited_mask`.
+    fn offset(&mut self, offset: Point<ScaledPixels>, inherited_mask: &ContentMask<ScaledPixels>) {
+        let offset_mask = |content_mask: &mut ContentMask<ScaledPixels>| {
+            if content_mask != inherited_mask {
+                content_mask.bounds.origin += offset;
+            }
+        };
+        let (bounds, content_mask) = match self {
+            Primitive::Shadow(shadow) => (&mut shadow.bounds, &mut shadow.content_mask),
+            Primitive::Quad(quad) => (&mut quad.bounds, &mut quad.content_mask),
+            Primitive::Gradient(gradient) => (&mut gradient.bounds, &mut gradient.content_mask),
+            Primitive::Path(path) => (&mut path.bounds, &mut path.content_mask),
+            Primitive::Underline(underline) => (&mut underline.bounds, &mut underline.content_mask),
+            Primitive::MonochromeSprite(sprite) => (&mut sprite.bounds, &mut sprite.content_mask),
+            Primitive::PolychromeSprite(sprite) => (&mut sprite.bounds, &mut sprite.content_mask),
+            Primitive::Surface(surface) => (&mut surface.bounds, &mut surface.content_mask),
+        };
+        bounds.origin += offset;
+        offset_mask(content_mask);
+
+        match self {
+            Primitive::Path(path) => {
+                for vertex in &mut path.vertices {
+                    vertex.xy_position += offset;
+                    offset_mask(&mut vertex.content_mask);
+                }
+            }
+            Primitive::MonochromeSprite(sprite) => {
+                // The transformation is applied to the sprite's moved position, so its
+                // translation is adjusted for the sprite to move by exactly the offset.
+                let transformation = &mut sprite.transformation;
+                let [[a, b], [c, d]] = transformation.rotation_scale;
+                transformation.translation[0] += offset.x.0 - (a * offset.x.0 + b * offset.y.0);
+                transformation.translation[1] += offset.y.0 - (c * offset.x.0 + d * offset.y.0);
+            }
+            _ => {}
+        }
+    }
+
     pub fn content_mask(&self) -> &ContentMask<ScaledPixels> {
         match self {
             Primitive::Shadow(shadow) => &shadow.content_mask,
             Primitive::Quad(quad) => &quad.content_mask,
+            Primitive::Gradient(gradient) => &gradient.content_mask,
             Primitive::Path(path) => &path.content_mask,
             Primitive::Underline(underline) => &underline.content_mask,
             Primitive::MonochromeSprite(sprite) => &sprite.content_mask,
@@ -219,6 +471,9 @@ struct BatchIterator<'a> {
     quads: &'a [Quad],
     quads_start: usize,
     quads_iter: Peekable<slice::Iter<'a, Quad>>,
+    gradients: &'a [Gradient],
+    gradients_start: usize,
+    gradients_iter: Peekable<slice::Iter<'a, Gradient>>,
     paths: &'a [Path<ScaledPixels>],
     paths_start: usize,
     paths_iter: Peekable<slice::Iter<'a, Path<ScaledPixels>>>,
@@ -246,6 +501,10 @@ impl<'a> Iterator for BatchIterator<'a> {
                 PrimitiveKind::Shadow,
             ),
             (self.quads_iter.peek().map(|q| q.order), PrimitiveKind::Quad),
+            (
+                self.gradients_iter.peek().map(|g| g.order),
+                PrimitiveKind::Gradient,
+            ),
             (self.paths_iter.peek().map(|q| q.order), PrimitiveKind::Path),
             (
                 self.underlines_iter.peek().map(|u| u.order),
@@ -305,6 +564,22 @@ impl<'a> Iterator for BatchIterator<'a> {
                 self.quads_start = quads_end;
                 Some(PrimitiveBatch::Quads(&self.quads[quads_start..quads_end]))
             }
+            PrimitiveKind::Gradient => {
+                let gradients_start = self.gradients_start;
+                let mut gradients_end = gradients_start + 1;
+                self.gradients_iter.next();
+                while self
+                    .gradients_iter
+                    .next_if(|gradient| (gradient.order, batch_kind) < max_order_and_kind)
+                    .is_some()
+                {
+                    gradients_end += 1;
+                }
+                self.gradients_start = gradients_end;
+                Some(PrimitiveBatch::Gradients(
+                    &self.gradients[gradients_start..gradients_end],
+                ))
+            }
             PrimitiveKind::Path => {
                 let paths_start = self.paths_start;
                 let mut paths_end = paths_start + 1;
@@ -401,6 +676,7 @@ impl<'a> Iterator for BatchIterator<'a> {
 pub(crate) enum PrimitiveBatch<'a> {
     Shadows(&'a [Shadow]),
     Quads(&'a [Quad]),
+    Gradients(&'a [Gradient]),
     Paths(&'a [Path<ScaledPixels>]),
     Underlines(&'a [Underline]),
     MonochromeSprites {
@@ -414,7 +690,7 @@ pub(crate) enum PrimitiveBatch<'a> {
     Surfaces(&'a [Surface]),
 }
 
-#[derive(Default, Debug, Clone, Eq, PartialEq)]
+#[derive(Default, Debug, Clone, Eq, PartialEq, Serialize)]
 #[repr(C)]
 pub(crate) struct Quad {
     pub order: DrawOrder,
@@ -445,7 +721,53 @@ impl From<Quad> for Primitive {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+/// The maximum number of color stops in a [`LinearGradient`](crate::LinearGradient).
+pub const MAX_GRADIENT_STOPS: usize = 4;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize)]
+#[repr(C)]
+pub(crate) struct GradientStop {
+    pub offset: f32,
+    pub color: Hsla,
+}
+
+/// A linear gradient, whose stops are sorted by offset and are interpolated in linear color
+/// space by the shaders.
+#[derive(Default, Debug, Clone, PartialEq, Serialize)]
+#[repr(C)]
+pub(crate) struct Gradient {
+    pub order: DrawOrder,
+    pub pad: u32, // align to 8 bytes
+    pub bounds: Bounds<ScaledPixels>,
+    pub content_mask: ContentMask<ScaledPixels>,
+    pub corner_radii: Corners<ScaledPixels>,
+    /// The clockwise angle of the gradient in radians, where zero paints from bottom to top.
+    pub angle: f32,
+    pub stop_count: u32,
+    pub stops: [GradientStop; MAX_GRADIENT_STOPS],
+}
+
+impl Eq for Gradient {}
+
+impl Ord for Gradient {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.order.cmp(&other.order)
+    }
+}
+
+impl PartialOrd for Gradient {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<Gradient> for Primitive {
+    fn from(gradient: Gradient) -> Self {
+        Primitive::Gradient(gradient)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 #[repr(C)]
 pub(crate) struct Underline {
     pub order: DrawOrder,
@@ -475,7 +797,7 @@ impl From<Underline> for Primitive {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 #[repr(C)]
 pub(crate) struct Shadow {
     pub order: DrawOrder,
@@ -505,7 +827,7 @@ impl From<Shadow> for Primitive {
 }
 
 /// A data type representing a 2 dimensional transformation that can be applied to an element.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 #[repr(C)]
 pub struct TransformationMatrix {
     /// 2x2 matrix containing rotation and scale,
@@ -607,7 +929,7 @@ impl Default for TransformationMatrix {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[repr(C)]
 pub(crate) struct MonochromeSprite {
     pub order: DrawOrder,
@@ -621,10 +943,9 @@ pub(crate) struct MonochromeSprite {
 
 impl Ord for MonochromeSprite {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match self.order.cmp(&other.order) {
-            std::cmp::Ordering::Equal => self.tile.tile_id.cmp(&other.tile.tile_id),
-            order => order,
-        }
+        // Sprites sharing a draw order are only grouped by texture where they can't overlap,
+        // which the scene decides when it's finished.
+        self.order.cmp(&other.order)
     }
 }
 
@@ -640,23 +961,26 @@ impl From<MonochromeSprite> for Primitive {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 #[repr(C)]
 pub(crate) struct PolychromeSprite {
     pub order: DrawOrder,
     pub grayscale: bool,
+    pub opacity: f32,
+    pub pad: u32, // align to 8 bytes
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
     pub corner_radii: Corners<ScaledPixels>,
     pub tile: AtlasTile,
 }
 
+impl Eq for PolychromeSprite {}
+
 impl Ord for PolychromeSprite {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match self.order.cmp(&other.order) {
-            std::cmp::Ordering::Equal => self.tile.tile_id.cmp(&other.tile.tile_id),
-            order => order,
-        }
+        // Sprites sharing a draw order are only grouped by texture where they can't overlap,
+        // which the scene decides when it's finished.
+        self.order.cmp(&other.order)
     }
 }
 
@@ -672,12 +996,13 @@ impl From<PolychromeSprite> for Primitive {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub(crate) struct Surface {
     pub order: DrawOrder,
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
     #[cfg(target_os = "macos")]
+    #[serde(skip)]
     pub image_buffer: media::core_video::CVImageBuffer,
 }
 
@@ -699,11 +1024,11 @@ impl From<Surface> for Primitive {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
 pub(crate) struct PathId(pub(crate) usize);
 
 /// A line made up of a series of vertices and control points.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Path<P: Clone + Default + Debug> {
     pub(crate) id: PathId,
     order: DrawOrder,
@@ -711,8 +1036,11 @@ pub struct Path<P: Clone + Default + Debug> {
     pub(crate) content_mask: ContentMask<P>,
     pub(crate) vertices: Vec<PathVertex<P>>,
     pub(crate) color: Hsla,
+    #[serde(skip)]
     start: Point<P>,
+    #[serde(skip)]
     current: Point<P>,
+    #[serde(skip)]
     contour_count: usize,
 }
 
@@ -754,6 +1082,13 @@ impl Path<Pixels> {
         }
     }
 
+    /// Move the current point to the given point without drawing, starting a new contour.
+    pub fn move_to(&mut self, to: Point<Pixels>) {
+        self.start = to;
+        self.current = to;
+        self.contour_count = 0;
+    }
+
     /// Draw a straight line from the current point to the given point.
     pub fn line_to(&mut self, to: Point<Pixels>) {
         self.contour_count += 1;
@@ -847,7 +1182,7 @@ impl From<Path<ScaledPixels>> for Primitive {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 #[repr(C)]
 pub(crate) struct PathVertex<P: Clone + Default + Debug> {
     pub(crate) xy_position: Point<P>,
@@ -864,3 +1199,268 @@ impl PathVertex<Pixels> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        black, div, px, red, size, white, AtlasTextureKind, IntoElement, ParentElement, Render,
+        Styled, TestAppContext, TileId, ViewContext,
+    };
+
+    fn monochrome_sprite(x: f32, texture_index: u32, tile_id: u32) -> MonochromeSprite {
+        let bounds = Bounds::new(
+            point(ScaledPixels(x), ScaledPixels(0.)),
+            size(ScaledPixels(10.), ScaledPixels(10.)),
+        );
+        MonochromeSprite {
+            order: 0,
+            pad: 0,
+            bounds,
+            content_mask: ContentMask { bounds },
+            color: Hsla::default(),
+            tile: AtlasTile {
+                texture_id: AtlasTextureId {
+                    index: texture_index,
+                    kind: AtlasTextureKind::Monochrome,
+                },
+                tile_id: TileId(tile_id),
+                padding: 0,
+                bounds: Default::default(),
+            },
+            transformation: TransformationMatrix::unit(),
+        }
+    }
+
+    #[test]
+    fn test_sprites_sharing_a_texture_are_batched_together() {
+        let mut scene = Scene::default();
+        scene.insert_primitive(monochrome_sprite(0., 0, 1));
+        scene.insert_primitive(monochrome_sprite(20., 1, 2));
+        scene.insert_primitive(monochrome_sprite(40., 0, 3));
+        scene.finish();
+
+        let batches = scene
+            .batches()
+            .map(|batch| match batch {
+                PrimitiveBatch::MonochromeSprites {
+                    texture_id,
+                    sprites,
+                } => (
+                    texture_id.index,
+                    sprites
+                        .iter()
+                        .map(|sprite| sprite.tile.tile_id.0)
+                        .collect::<Vec<_>>(),
+                ),
+                _ => panic!("unexpected batch"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(batches, vec![(0, vec![1, 3]), (1, vec![2])]);
+    }
+
+    #[test]
+    fn test_overlapping_sprites_in_a_layer_keep_insertion_order() {
+        let mut scene = Scene::default();
+        let layer_bounds = Bounds::new(
+            point(ScaledPixels(0.), ScaledPixels(0.)),
+            size(ScaledPixels(20.), ScaledPixels(10.)),
+        );
+        scene.push_layer(layer_bounds);
+        // The sprites all share the layer's draw order and overlap, so they have to be painted
+        // over each other in the order they were inserted, whatever their textures.
+        scene.insert_primitive(monochrome_sprite(0., 1, 4));
+        scene.insert_primitive(monochrome_sprite(5., 0, 3));
+        scene.insert_primitive(monochrome_sprite(10., 1, 2));
+        scene.insert_primitive(monochrome_sprite(5., 0, 1));
+        scene.pop_layer();
+        scene.finish();
+
+        let sprites = scene
+            .monochrome_sprites
+            .iter()
+            .map(|sprite| (sprite.order, sprite.tile.tile_id.0))
+            .collect::<Vec<_>>();
+        let layer_order = sprites[0].0;
+        assert_eq!(
+            sprites,
+            vec![
+                (layer_order, 4),
+                (layer_order, 3),
+                (layer_order, 2),
+                (layer_order, 1)
+            ]
+        );
+        assert_eq!(scene.batches().count(), 4);
+    }
+
+    #[test]
+    fn test_gradients_are_drawn_in_order_with_quads() {
+        let bounds = Bounds::new(
+            point(ScaledPixels(0.), ScaledPixels(0.)),
+            size(ScaledPixels(10.), ScaledPixels(10.)),
+        );
+        let mut scene = Scene::default();
+        scene.insert_primitive(Quad {
+            bounds,
+            content_mask: ContentMask { bounds },
+            ..Default::default()
+        });
+        scene.insert_primitive(Gradient {
+            bounds,
+            content_mask: ContentMask { bounds },
+            stop_count: 2,
+            ..Default::default()
+        });
+        scene.insert_primitive(Quad {
+            bounds,
+            content_mask: ContentMask { bounds },
+            ..Default::default()
+        });
+        scene.finish();
+
+        let batches = scene
+            .batches()
+            .map(|batch| match batch {
+                PrimitiveBatch::Quads(quads) => ("quads", quads.len()),
+                PrimitiveBatch::Gradients(gradients) => ("gradients", gradients.len()),
+                _ => panic!("unexpected batch"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(batches, vec![("quads", 1), ("gradients", 1), ("quads", 1)]);
+    }
+
+    #[test]
+    fn test_primitives_behind_opaque_quads_are_culled() {
+        let bounds = |x: f32, width: f32| {
+            Bounds::new(
+                point(ScaledPixels(x), ScaledPixels(0.)),
+                size(ScaledPixels(width), ScaledPixels(10.)),
+            )
+        };
+        let quad = |bounds: Bounds<ScaledPixels>, background: Hsla| Quad {
+            content_mask: ContentMask {
+                bounds: bounds.clone(),
+            },
+            bounds,
+            background,
+            ..Default::default()
+        };
+
+        let mut scene = Scene::default();
+        // Hidden by the card.
+        scene.insert_primitive(monochrome_sprite(10., 0, 1));
+        // Only partly covered by the card.
+        scene.insert_primitive(monochrome_sprite(45., 0, 2));
+        // The card, with a translucent quad and a rounded opaque quad above it that don't hide it.
+        scene.insert_primitive(quad(bounds(0., 50.), red()));
+        scene.insert_primitive(quad(bounds(0., 50.), red().opacity(0.5)));
+        scene.insert_primitive(Quad {
+            corner_radii: Corners::all(ScaledPixels(4.)),
+            ..quad(bounds(0., 50.), red())
+        });
+        scene.finish();
+
+        assert_eq!(scene.cull_occluded(), 1);
+        let sprites = scene
+            .monochrome_sprites
+            .iter()
+            .map(|sprite| sprite.tile.tile_id.0)
+            .collect::<Vec<_>>();
+        assert_eq!(sprites, vec![2]);
+        assert_eq!(scene.quads.len(), 3);
+        // The culled primitives are still painted, for replaying into later frames.
+        assert_eq!(scene.primitive_count(), 5);
+    }
+
+    #[test]
+    fn test_scene_serializes_its_primitives() {
+        let bounds = Bounds::new(
+            point(ScaledPixels(0.), ScaledPixels(0.)),
+            size(ScaledPixels(10.), ScaledPixels(10.)),
+        );
+        let mut scene = Scene::default();
+        scene.insert_primitive(Quad {
+            bounds,
+            content_mask: ContentMask { bounds },
+            background: red(),
+            ..Default::default()
+        });
+        scene.finish();
+
+        let bounds = json!({
+            "origin": { "x": 0.0, "y": 0.0 },
+            "size": { "width": 10.0, "height": 10.0 },
+        });
+        assert_eq!(
+            serde_json::to_value(&scene).unwrap(),
+            json!({
+                "shadows": [],
+                "quads": [{
+                    "order": 1,
+                    "pad": 0,
+                    "bounds": bounds,
+                    "content_mask": { "bounds": bounds },
+                    "background": "#ff0000ff",
+                    "border_color": "#00000000",
+                    "corner_radii": {
+                        "top_left": 0.0,
+                        "top_right": 0.0,
+                        "bottom_right": 0.0,
+                        "bottom_left": 0.0,
+                    },
+                    "border_widths": { "top": 0.0, "right": 0.0, "bottom": 0.0, "left": 0.0 },
+                }],
+                "gradients": [],
+                "paths": [],
+                "underlines": [],
+                "monochrome_sprites": [],
+                "polychrome_sprites": [],
+                "surfaces": [],
+            })
+        );
+    }
+
+    struct SnapshotView;
+
+    impl Render for SnapshotView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .size_full()
+                .flex()
+                .gap(px(10.))
+                .p(px(10.))
+                .bg(white())
+                .child(div().size(px(20.)).bg(red()))
+                .child(div().size(px(20.)).rounded(px(4.)).bg(black()))
+        }
+    }
+
+    #[gpui::test]
+    fn test_view_scene_matches_snapshot(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| SnapshotView);
+        cx.simulate_resize(size(px(100.), px(50.)));
+        cx.update(|cx| cx.refresh());
+
+        let expected: serde_json::Value =
+            serde_json::from_str(include_str!("../test_data/scene_snapshot.json")).unwrap();
+        let actual = cx.scene_snapshot();
+        assert!(
+            actual == expected,
+            "scene doesn't match test_data/scene_snapshot.json, it now paints:\n{}",
+            serde_json::to_string_pretty(&actual).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_polychrome_sprite_layout_matches_shaders() {
+        // WGSL aligns `Bounds` to 8 bytes, so the sprite's bounds have to start at an offset that
+        // is a multiple of 8 for the Blade shaders to read them.
+        assert_eq!(std::mem::offset_of!(PolychromeSprite, bounds), 16);
+        assert_eq!(std::mem::size_of::<PolychromeSprite>(), 96);
+    }
+}
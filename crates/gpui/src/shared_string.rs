@@ -1,14 +1,59 @@
+use collections::HashSet;
 use derive_more::{Deref, DerefMut};
 
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::{borrow::Borrow, sync::Arc};
 use util::arc_cow::ArcCow;
 
+/// How many strings can be interned before the ones that are no longer used are released.
+const MIN_INTERNED_BEFORE_RELEASE: usize = 1024;
+
+lazy_static::lazy_static! {
+    static ref INTERNED_STRINGS: Mutex<InternedStrings> = Mutex::new(InternedStrings {
+        strings: HashSet::default(),
+        release_at: MIN_INTERNED_BEFORE_RELEASE,
+    });
+}
+
+struct InternedStrings {
+    strings: HashSet<Arc<str>>,
+    /// How many strings there can be before the unused ones are released.
+    release_at: usize,
+}
+
 /// A shared string is an immutable string that can be cheaply cloned in GPUI
 /// tasks. Essentially an abstraction over an `Arc<str>` and `&'static str`,
 #[derive(Deref, DerefMut, Eq, PartialEq, PartialOrd, Ord, Hash, Clone)]
 pub struct SharedString(ArcCow<'static, str>);
 
+impl SharedString {
+    /// Returns a shared string with the given contents, which shares its allocation with every
+    /// other string interned with the same contents, such as a label that's rendered in many
+    /// places. Interned strings also compare equal to each other without comparing their contents.
+    ///
+    /// Strings that are only used once gain nothing from being interned, as they still have to be
+    /// allocated and are looked up on top of that.
+    pub fn intern(text: &str) -> Self {
+        let mut interned = INTERNED_STRINGS.lock();
+        if let Some(string) = interned.strings.get(text) {
+            return Self(ArcCow::Owned(string.clone()));
+        }
+
+        if interned.strings.len() >= interned.release_at {
+            // Release the strings that are only referenced by the interner.
+            interned
+                .strings
+                .retain(|string| Arc::strong_count(string) > 1);
+            interned.release_at = (interned.strings.len() * 2).max(MIN_INTERNED_BEFORE_RELEASE);
+        }
+
+        let string = Arc::<str>::from(text);
+        interned.strings.insert(string.clone());
+        Self(ArcCow::Owned(string))
+    }
+}
+
 impl Default for SharedString {
     fn default() -> Self {
         Self(ArcCow::Owned(Arc::default()))
@@ -102,3 +147,27 @@ impl<'de> Deserialize<'de> for SharedString {
         Ok(SharedString::from(s))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interned_strings_share_an_allocation() {
+        let interned = |text: &str| match SharedString::intern(text).0 {
+            ArcCow::Owned(string) => string,
+            ArcCow::Borrowed(_) => panic!("interned strings are owned"),
+        };
+
+        let font_size = interned("Font Size");
+        assert!(Arc::ptr_eq(
+            &font_size,
+            &interned(&String::from("Font Size"))
+        ));
+        assert!(!Arc::ptr_eq(&font_size, &interned("Font Weight")));
+        assert_eq!(
+            SharedString::intern("Font Size"),
+            SharedString::from("Font Size")
+        );
+    }
+}
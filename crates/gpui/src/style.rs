@@ -100,6 +100,10 @@ pub struct Style {
     /// The relative rate at which this item shrinks when it is contracting to fit into space, 1.0 is the default value, and this value must be positive.
     pub flex_shrink: f32,
 
+    // Grid properties
+    /// How many equally sized columns should a grid container lay its children out in?
+    pub grid_cols: Option<u16>,
+
     /// The fill color of this element
     pub background: Option<Fill>,
 
@@ -559,6 +563,8 @@ impl Default for Style {
             flex_grow: 0.0,
             flex_shrink: 1.0,
             flex_basis: Length::Auto,
+            // Grid
+            grid_cols: None,
             background: None,
             border_color: None,
             corner_radii: Corners::default(),
@@ -7,7 +7,11 @@ use smallvec::SmallVec;
 use std::fmt::Debug;
 use taffy::{
     geometry::{Point as TaffyPoint, Rect as TaffyRect, Size as TaffySize},
-    style::AvailableSpace as TaffyAvailableSpace,
+    style::{
+        AvailableSpace as TaffyAvailableSpace, GridTrackRepetition, LengthPercentage,
+        MaxTrackSizingFunction, MinTrackSizingFunction, NonRepeatedTrackSizingFunction,
+        TrackSizingFunction,
+    },
     tree::NodeId,
     TaffyTree, TraversePartialTree as _,
 };
@@ -271,7 +275,20 @@ impl ToTaffy<taffy::style::Style> for Style {
             flex_basis: self.flex_basis.to_taffy(rem_size),
             flex_grow: self.flex_grow,
             flex_shrink: self.flex_shrink,
-            ..Default::default() // Ignore grid properties for now
+            grid_template_columns: self
+                .grid_cols
+                .map(|cols| {
+                    // repeat(<cols>, minmax(0, 1fr))
+                    vec![TrackSizingFunction::Repeat(
+                        GridTrackRepetition::Count(cols),
+                        vec![NonRepeatedTrackSizingFunction {
+                            min: MinTrackSizingFunction::Fixed(LengthPercentage::Length(0.)),
+                            max: MaxTrackSizingFunction::Fraction(1.),
+                        }],
+                    )]
+                })
+                .unwrap_or_default(),
+            ..Default::default() // Ignore the remaining grid properties for now
         }
     }
 }
@@ -1,9 +1,12 @@
+mod bidi;
 mod font_fallbacks;
 mod font_features;
 mod line;
 mod line_layout;
 mod line_wrapper;
 
+pub use bidi::TextDirection;
+pub(crate) use bidi::*;
 pub use font_fallbacks::*;
 pub use font_features::*;
 pub use line::*;
@@ -481,6 +484,11 @@ impl WindowTextSystem {
         self.line_layout_cache.finish_frame()
     }
 
+    /// Returns how often line layouts were reused from the cache during the previous frame.
+    pub fn layout_cache_stats(&self) -> LineLayoutCacheStats {
+        self.line_layout_cache.previous_frame_stats()
+    }
+
     /// Layout the given line of text, at the given font_size.
     /// Subsets of the line can be styled independently with the `runs` parameter.
     /// Generally, you should prefer to use `TextLayout::shape_line` instead, which
@@ -651,6 +659,23 @@ pub struct TextRun {
 #[repr(C)]
 pub struct GlyphId(pub(crate) u32);
 
+/// How the edges of glyphs are anti-aliased when they're rasterized.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum TextAntialiasing {
+    /// Each pixel is covered by a single value, which blends correctly over any background.
+    #[default]
+    Grayscale,
+    /// Coverage is still a single value per pixel, but the platform's font smoothing is applied
+    /// when rasterizing it, which makes text heavier and sharper on low-density displays. This
+    /// isn't per-channel subpixel anti-aliasing, and it's only used in windows with an opaque
+    /// background.
+    ///
+    /// On macOS, Core Graphics font smoothing is drawn into the single-channel glyph mask. On
+    /// Linux and Windows it currently has no effect on rasterization, though the glyphs are still
+    /// cached apart from grayscale ones.
+    FontSmoothing,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct RenderGlyphParams {
     pub(crate) font_id: FontId,
@@ -659,6 +684,7 @@ pub(crate) struct RenderGlyphParams {
     pub(crate) subpixel_variant: Point<u8>,
     pub(crate) scale_factor: f32,
     pub(crate) is_emoji: bool,
+    pub(crate) antialiasing: TextAntialiasing,
 }
 
 impl Eq for RenderGlyphParams {}
@@ -670,6 +696,7 @@ impl Hash for RenderGlyphParams {
         self.font_size.0.to_bits().hash(state);
         self.subpixel_variant.hash(state);
         self.scale_factor.to_bits().hash(state);
+        self.antialiasing.hash(state);
     }
 }
 
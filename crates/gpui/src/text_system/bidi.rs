@@ -0,0 +1,268 @@
+use std::{cmp::Ordering, ops::Range};
+
+use smallvec::SmallVec;
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo};
+
+use crate::{FontId, FontRun, LineLayout, Pixels, ShapedGlyph, ShapedRun};
+
+/// The direction in which text is read.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TextDirection {
+    /// Read from left to right, like English.
+    #[default]
+    LeftToRight,
+    /// Read from right to left, like Arabic and Hebrew.
+    RightToLeft,
+}
+
+impl TextDirection {
+    /// Returns the direction of the given text, which the Unicode bidirectional algorithm takes
+    /// from its first strongly directional character. Text without one is read from left to
+    /// right.
+    pub fn of(text: &str) -> Self {
+        for c in text.chars() {
+            match bidi_class(c) {
+                BidiClass::L => return Self::LeftToRight,
+                BidiClass::R | BidiClass::AL => return Self::RightToLeft,
+                _ => {}
+            }
+        }
+        Self::LeftToRight
+    }
+}
+
+/// Returns whether the text contains characters that can be read from right to left, and may
+/// have to be reordered.
+fn has_rtl(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(
+            bidi_class(c),
+            BidiClass::R | BidiClass::AL | BidiClass::RLE | BidiClass::RLO | BidiClass::RLI
+        )
+    })
+}
+
+/// Lays out a line of text whose characters may be read in different directions, placing its
+/// directional runs in the visual order given by the Unicode bidirectional algorithm.
+///
+/// Each run is shaped on its own with `shape`, and the glyphs of the returned layout are in
+/// visual order, from left to right, so the glyphs of right-to-left runs have decreasing indices.
+/// Lines that are read from left to right throughout are shaped as a whole.
+pub(crate) fn layout_bidi_line(
+    text: &str,
+    font_size: Pixels,
+    runs: &[FontRun],
+    mut shape: impl FnMut(&str, &[FontRun]) -> LineLayout,
+) -> LineLayout {
+    if !has_rtl(text) {
+        return shape(text, runs);
+    }
+
+    let bidi_info = BidiInfo::new(text, None);
+    let mut layout = LineLayout {
+        font_size,
+        len: text.len(),
+        has_rtl: true,
+        ..Default::default()
+    };
+    for paragraph in &bidi_info.paragraphs {
+        let (levels, visual_runs) = bidi_info.visual_runs(paragraph, paragraph.range.clone());
+        for range in visual_runs {
+            let run_layout = shape(&text[range.clone()], &font_runs_in(runs, range.clone()));
+            let rtl = levels[range.start].is_rtl();
+            append_run(&mut layout, run_layout, range.start, rtl);
+        }
+    }
+    layout
+}
+
+/// Returns the parts of the font runs that fall in the given range of the line.
+fn font_runs_in(runs: &[FontRun], range: Range<usize>) -> SmallVec<[FontRun; 1]> {
+    let mut run_start = 0;
+    runs.iter()
+        .filter_map(|run| {
+            let start = run_start.max(range.start);
+            let end = (run_start + run.len).min(range.end);
+            run_start += run.len;
+            (start < end).then_some(FontRun {
+                len: end - start,
+                font_id: run.font_id,
+            })
+        })
+        .collect()
+}
+
+/// Places the layout of a directional run, which starts at the given index in the line, after
+/// the runs that have already been placed.
+fn append_run(line: &mut LineLayout, run: LineLayout, offset: usize, rtl: bool) {
+    let mut glyphs = run
+        .runs
+        .into_iter()
+        .flat_map(|shaped_run| {
+            let font_id = shaped_run.font_id;
+            shaped_run
+                .glyphs
+                .into_iter()
+                .map(move |glyph| (font_id, glyph))
+        })
+        .collect::<Vec<_>>();
+    glyphs.sort_by(|(_, a), (_, b)| compare_x(a, b));
+
+    // Shapers that don't reorder right-to-left text themselves lay it out from left to right, in
+    // which case the glyphs are mirrored within the run.
+    if rtl && laid_out_left_to_right(&glyphs) {
+        mirror(&mut glyphs, run.width);
+        glyphs.sort_by(|(_, a), (_, b)| compare_x(a, b));
+    }
+
+    let x_offset = line.width;
+    for (font_id, mut glyph) in glyphs {
+        glyph.position.x += x_offset;
+        glyph.index += offset;
+        match line.runs.last_mut() {
+            Some(shaped_run) if shaped_run.font_id == font_id => shaped_run.glyphs.push(glyph),
+            _ => line.runs.push(ShapedRun {
+                font_id,
+                glyphs: SmallVec::from_iter([glyph]),
+            }),
+        }
+    }
+
+    line.width += run.width;
+    line.ascent = line.ascent.max(run.ascent);
+    line.descent = line.descent.max(run.descent);
+}
+
+fn compare_x(a: &ShapedGlyph, b: &ShapedGlyph) -> Ordering {
+    a.position
+        .x
+        .partial_cmp(&b.position.x)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Returns whether the glyphs, sorted from left to right, start with the first character of the
+/// run rather than its last.
+fn laid_out_left_to_right(glyphs: &[(FontId, ShapedGlyph)]) -> bool {
+    match (glyphs.first(), glyphs.last()) {
+        (Some((_, first)), Some((_, last))) => first.index < last.index,
+        _ => false,
+    }
+}
+
+/// Mirrors the glyphs, sorted from left to right, within a run of the given width, so that each
+/// glyph keeps its advance.
+fn mirror(glyphs: &mut [(FontId, ShapedGlyph)], width: Pixels) {
+    let xs = glyphs
+        .iter()
+        .map(|(_, glyph)| glyph.position.x)
+        .collect::<Vec<_>>();
+    for (ix, (_, glyph)) in glyphs.iter_mut().enumerate() {
+        let x = xs[ix];
+        // Glyphs that share a position, like combining marks, share the advance that follows.
+        let next_x = xs[ix..]
+            .iter()
+            .copied()
+            .find(|next_x| *next_x > x)
+            .unwrap_or(width);
+        glyph.position.x = width - next_x;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{point, px, GlyphId};
+
+    /// Shapes each character as a 10px wide glyph, from left to right, like a shaper that doesn't
+    /// reorder right-to-left text.
+    fn shape_left_to_right(text: &str, runs: &[FontRun]) -> LineLayout {
+        let glyphs = text
+            .char_indices()
+            .enumerate()
+            .map(|(ix, (index, _))| ShapedGlyph {
+                id: GlyphId(ix as u32),
+                position: point(px(ix as f32 * 10.), px(0.)),
+                index,
+                is_emoji: false,
+            })
+            .collect();
+
+        LineLayout {
+            font_size: px(16.),
+            width: px(text.chars().count() as f32 * 10.),
+            ascent: px(12.),
+            descent: px(4.),
+            runs: vec![ShapedRun {
+                font_id: runs[0].font_id,
+                glyphs,
+            }],
+            len: text.len(),
+            has_rtl: false,
+        }
+    }
+
+    #[test]
+    fn test_text_direction() {
+        assert_eq!(
+            TextDirection::of("Enable العربية"),
+            TextDirection::LeftToRight
+        );
+        assert_eq!(
+            TextDirection::of("العربية Enable"),
+            TextDirection::RightToLeft
+        );
+        assert_eq!(TextDirection::of("42 עברית"), TextDirection::RightToLeft);
+        assert_eq!(TextDirection::of("42"), TextDirection::LeftToRight);
+    }
+
+    #[test]
+    fn test_mixed_direction_line_layout() {
+        let text = "Enable العربية";
+        let runs = [FontRun {
+            len: text.len(),
+            font_id: FontId(0),
+        }];
+        let layout = layout_bidi_line(text, px(16.), &runs, shape_left_to_right);
+
+        let glyphs = layout
+            .runs
+            .iter()
+            .flat_map(|run| run.glyphs.iter())
+            .collect::<Vec<_>>();
+        // "Enable " is read from left to right, and the Arabic word after it from right to left,
+        // so its last letter is placed first.
+        assert_eq!(
+            glyphs.iter().map(|glyph| glyph.index).collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4, 5, 6, 19, 17, 15, 13, 11, 9, 7]
+        );
+        assert!(glyphs
+            .windows(2)
+            .all(|pair| pair[0].position.x < pair[1].position.x));
+        assert_eq!(layout.width, px(140.));
+        assert_eq!(layout.len, text.len());
+        assert!(layout.has_rtl);
+
+        // The Arabic word starts at the right end of the line.
+        assert_eq!(layout.x_for_index(7), px(130.));
+        assert_eq!(layout.index_for_x(px(75.)), Some(19));
+    }
+
+    #[test]
+    fn test_left_to_right_lines_are_shaped_whole() {
+        let text = "Enable line numbers";
+        let runs = [FontRun {
+            len: text.len(),
+            font_id: FontId(0),
+        }];
+        let mut shaped = Vec::new();
+        let layout = layout_bidi_line(text, px(16.), &runs, |text, runs| {
+            shaped.push(text.to_string());
+            shape_left_to_right(text, runs)
+        });
+        assert_eq!(shaped, [text]);
+
+        assert!(!layout.has_rtl);
+        assert_eq!(layout.x_for_index(7), px(70.));
+        assert_eq!(layout.x_for_index(text.len()), layout.width);
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
 };
 use derive_more::{Deref, DerefMut};
 use smallvec::SmallVec;
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 
 /// Set the text decoration for a run of text.
 #[derive(Debug, Clone)]
@@ -61,6 +61,46 @@ impl ShapedLine {
 
         Ok(())
     }
+
+    /// Paint a selection highlight behind the text in the given range of utf-8 byte offsets.
+    ///
+    /// Call this before [`Self::paint`] so that the highlight is painted behind the glyphs.
+    pub fn paint_selection(
+        &self,
+        origin: Point<Pixels>,
+        line_height: Pixels,
+        range: Range<usize>,
+        color: impl Into<Hsla>,
+        cx: &mut WindowContext,
+    ) {
+        if range.is_empty() {
+            return;
+        }
+
+        let start_x = self.layout.x_for_index(range.start);
+        let end_x = self.layout.x_for_index(range.end);
+        cx.paint_quad(fill(
+            Bounds::new(
+                point(origin.x + start_x, origin.y),
+                size(end_x - start_x, line_height),
+            ),
+            color,
+        ));
+    }
+
+    /// Paint a blinking text cursor before the character at the given utf-8 byte offset, or at
+    /// `origin` if the line is empty. See [`WindowContext::paint_cursor`].
+    pub fn paint_cursor(
+        &self,
+        origin: Point<Pixels>,
+        line_height: Pixels,
+        index: usize,
+        color: impl Into<Hsla>,
+        cx: &mut WindowContext,
+    ) {
+        let x = self.layout.x_for_index(index);
+        cx.paint_cursor(point(origin.x + x, origin.y), line_height, color);
+    }
 }
 
 /// A line of text that has been shaped, decorated, and wrapped by the text layout system.
@@ -119,9 +159,17 @@ fn paint_line(
     cx.paint_layer(line_bounds, |cx| {
         let padding_top = (line_height - layout.ascent - layout.descent) / 2.;
         let baseline_offset = point(px(0.), padding_top + layout.ascent);
-        let mut decoration_runs = decoration_runs.iter();
+        // The glyphs are painted from left to right, which isn't the order of the text in lines
+        // that mix reading directions, so each glyph's decoration run is looked up by its index.
+        let decoration_run_ends = decoration_runs
+            .iter()
+            .scan(0, |end, run| {
+                *end += run.len as usize;
+                Some(*end)
+            })
+            .collect::<SmallVec<[usize; 32]>>();
+        let mut decoration_run_ix = None;
         let mut wraps = wrap_boundaries.iter().peekable();
-        let mut run_end = 0;
         let mut color = black();
         let mut current_underline: Option<(Point<Pixels>, UnderlineStyle)> = None;
         let mut current_strikethrough: Option<(Point<Pixels>, StrikethroughStyle)> = None;
@@ -178,8 +226,11 @@ fn paint_line(
                 let mut finished_background: Option<(Point<Pixels>, Hsla)> = None;
                 let mut finished_underline: Option<(Point<Pixels>, UnderlineStyle)> = None;
                 let mut finished_strikethrough: Option<(Point<Pixels>, StrikethroughStyle)> = None;
-                if glyph.index >= run_end {
-                    if let Some(style_run) = decoration_runs.next() {
+                let glyph_decoration_run_ix =
+                    decoration_run_ends.partition_point(|end| *end <= glyph.index);
+                if decoration_run_ix != Some(glyph_decoration_run_ix) {
+                    decoration_run_ix = Some(glyph_decoration_run_ix);
+                    if let Some(style_run) = decoration_runs.get(glyph_decoration_run_ix) {
                         if let Some((_, background_color)) = &mut current_background {
                             if style_run.background_color.as_ref() != Some(background_color) {
                                 finished_background = current_background.take();
@@ -229,10 +280,8 @@ fn paint_line(
                             ));
                         }
 
-                        run_end += style_run.len as usize;
                         color = style_run.color;
                     } else {
-                        run_end = layout.len;
                         finished_background = current_background.take();
                         finished_underline = current_underline.take();
                         finished_strikethrough = current_strikethrough.take();
@@ -1,4 +1,6 @@
-use crate::{point, px, FontId, GlyphId, Pixels, PlatformTextSystem, Point, Size};
+use crate::{
+    layout_bidi_line, point, px, FontId, GlyphId, Pixels, PlatformTextSystem, Point, Size,
+};
 use collections::FxHashMap;
 use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
 use smallvec::SmallVec;
@@ -6,7 +8,10 @@ use std::{
     borrow::Borrow,
     hash::{Hash, Hasher},
     ops::Range,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering::SeqCst},
+        Arc,
+    },
 };
 
 /// A laid out and styled line of text
@@ -20,10 +25,14 @@ pub struct LineLayout {
     pub ascent: Pixels,
     /// The descent of the line
     pub descent: Pixels,
-    /// The shaped runs that make up this line
+    /// The shaped runs that make up this line, in visual order from left to right. In lines
+    /// that mix reading directions, their glyphs aren't in the order of the text.
     pub runs: Vec<ShapedRun>,
     /// The length of the line in utf-8 bytes
     pub len: usize,
+    /// Whether the line contains right-to-left text, in which case its glyphs aren't sorted by
+    /// their index in the text.
+    pub has_rtl: bool,
 }
 
 /// A run of text that has been shaped .
@@ -93,26 +102,30 @@ impl LineLayout {
 
     /// The x position of the character at the given index
     pub fn x_for_index(&self, index: usize) -> Pixels {
-        for run in &self.runs {
-            for glyph in &run.glyphs {
-                if glyph.index >= index {
-                    return glyph.position.x;
-                }
-            }
-        }
-        self.width
+        self.glyph_for_index(index)
+            .map_or(self.width, |(_, glyph)| glyph.position.x)
     }
 
     /// The corresponding Font at the given index
     pub fn font_id_for_index(&self, index: usize) -> Option<FontId> {
-        for run in &self.runs {
-            for glyph in &run.glyphs {
-                if glyph.index >= index {
-                    return Some(run.font_id);
-                }
-            }
+        self.glyph_for_index(index).map(|(font_id, _)| font_id)
+    }
+
+    /// Returns the first glyph of the text at or after the given index, along with its font.
+    fn glyph_for_index(&self, index: usize) -> Option<(FontId, &ShapedGlyph)> {
+        let mut glyphs = self
+            .runs
+            .iter()
+            .flat_map(|run| run.glyphs.iter().map(move |glyph| (run.font_id, glyph)));
+        if self.has_rtl {
+            // Right-to-left runs place the glyphs of later characters first, so every glyph has
+            // to be searched.
+            glyphs
+                .filter(|(_, glyph)| glyph.index >= index)
+                .min_by_key(|(_, glyph)| glyph.index)
+        } else {
+            glyphs.find(|(_, glyph)| glyph.index >= index)
         }
-        None
     }
 
     fn compute_wrap_boundaries(
@@ -337,6 +350,30 @@ pub(crate) struct LineLayoutCache {
     previous_frame: Mutex<FrameCache>,
     current_frame: RwLock<FrameCache>,
     platform_text_system: Arc<dyn PlatformTextSystem>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+    previous_frame_stats: Mutex<LineLayoutCacheStats>,
+}
+
+/// How often line layouts were reused instead of being shaped again during a frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LineLayoutCacheStats {
+    /// The number of lines whose layout was found in the cache.
+    pub hits: usize,
+    /// The number of lines that had to be shaped by the platform text system.
+    pub misses: usize,
+}
+
+impl LineLayoutCacheStats {
+    /// The fraction of lookups that were served from the cache, from `0.` to `1.`.
+    pub fn hit_rate(&self) -> f32 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.
+        } else {
+            self.hits as f32 / lookups as f32
+        }
+    }
 }
 
 #[derive(Default)]
@@ -359,9 +396,17 @@ impl LineLayoutCache {
             previous_frame: Mutex::default(),
             current_frame: RwLock::default(),
             platform_text_system,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            previous_frame_stats: Mutex::default(),
         }
     }
 
+    /// Returns the cache statistics for the most recently finished frame.
+    pub fn previous_frame_stats(&self) -> LineLayoutCacheStats {
+        *self.previous_frame_stats.lock()
+    }
+
     pub fn layout_index(&self) -> LineLayoutIndex {
         let frame = self.current_frame.read();
         LineLayoutIndex {
@@ -407,6 +452,10 @@ impl LineLayoutCache {
         curr_frame.wrapped_lines.clear();
         curr_frame.used_lines.clear();
         curr_frame.used_wrapped_lines.clear();
+        *self.previous_frame_stats.lock() = LineLayoutCacheStats {
+            hits: self.hits.swap(0, SeqCst),
+            misses: self.misses.swap(0, SeqCst),
+        };
     }
 
     pub fn layout_wrapped_line(
@@ -425,11 +474,13 @@ impl LineLayoutCache {
 
         let current_frame = self.current_frame.upgradable_read();
         if let Some(layout) = current_frame.wrapped_lines.get(key) {
+            self.hits.fetch_add(1, SeqCst);
             return layout.clone();
         }
 
         let previous_frame_entry = self.previous_frame.lock().wrapped_lines.remove_entry(key);
         if let Some((key, layout)) = previous_frame_entry {
+            self.hits.fetch_add(1, SeqCst);
             let mut current_frame = RwLockUpgradableReadGuard::upgrade(current_frame);
             current_frame
                 .wrapped_lines
@@ -439,6 +490,7 @@ impl LineLayoutCache {
         } else {
             drop(current_frame);
 
+            // Hits and misses for the unwrapped layout are recorded by `layout_line`.
             let unwrapped_layout = self.layout_line(text, font_size, runs);
             let wrap_boundaries = if let Some(wrap_width) = wrap_width {
                 unwrapped_layout.compute_wrap_boundaries(text.as_ref(), wrap_width)
@@ -477,16 +529,21 @@ impl LineLayoutCache {
 
         let current_frame = self.current_frame.upgradable_read();
         if let Some(layout) = current_frame.lines.get(key) {
+            self.hits.fetch_add(1, SeqCst);
             return layout.clone();
         }
 
         let mut current_frame = RwLockUpgradableReadGuard::upgrade(current_frame);
         if let Some((key, layout)) = self.previous_frame.lock().lines.remove_entry(key) {
+            self.hits.fetch_add(1, SeqCst);
             current_frame.lines.insert(key.clone(), layout.clone());
             current_frame.used_lines.push(key);
             layout
         } else {
-            let layout = Arc::new(self.platform_text_system.layout_line(text, font_size, runs));
+            self.misses.fetch_add(1, SeqCst);
+            let layout = Arc::new(layout_bidi_line(text, font_size, runs, |text, runs| {
+                self.platform_text_system.layout_line(text, font_size, runs)
+            }));
             let key = Arc::new(CacheKey {
                 text: text.into(),
                 font_size,
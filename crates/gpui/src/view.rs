@@ -103,9 +103,11 @@ impl<V: Render> Element for View<V> {
         _id: Option<&GlobalElementId>,
         cx: &mut WindowContext,
     ) -> (LayoutId, Self::RequestLayoutState) {
-        let mut element = self.update(cx, |view, cx| view.render(cx).into_any_element());
-        let layout_id = element.request_layout(cx);
-        (layout_id, element)
+        cx.with_drawing_view(self.entity_id(), |cx| {
+            let mut element = self.update(cx, |view, cx| view.render(cx).into_any_element());
+            let layout_id = element.request_layout(cx);
+            (layout_id, element)
+        })
     }
 
     fn prepaint(
@@ -116,7 +118,7 @@ impl<V: Render> Element for View<V> {
         cx: &mut WindowContext,
     ) {
         cx.set_view_id(self.entity_id());
-        element.prepaint(cx);
+        cx.with_drawing_view(self.entity_id(), |cx| element.prepaint(cx));
     }
 
     fn paint(
@@ -127,7 +129,7 @@ impl<V: Render> Element for View<V> {
         _: &mut Self::PrepaintState,
         cx: &mut WindowContext,
     ) {
-        element.paint(cx);
+        cx.with_drawing_view(self.entity_id(), |cx| element.paint(cx));
     }
 }
 
@@ -307,9 +309,11 @@ impl Element for AnyView {
             let layout_id = cx.request_layout(root_style, None);
             (layout_id, None)
         } else {
-            let mut element = (self.render)(self, cx);
-            let layout_id = element.request_layout(cx);
-            (layout_id, Some(element))
+            cx.with_drawing_view(self.entity_id(), |cx| {
+                let mut element = (self.render)(self, cx);
+                let layout_id = element.request_layout(cx);
+                (layout_id, Some(element))
+            })
         }
     }
 
@@ -335,6 +339,7 @@ impl Element for AnyView {
                     {
                         let prepaint_start = cx.prepaint_index();
                         cx.reuse_prepaint(element_state.prepaint_range.clone());
+                        cx.reuse_global_reads(self.entity_id());
                         let prepaint_end = cx.prepaint_index();
                         element_state.prepaint_range = prepaint_start..prepaint_end;
                         return (None, element_state);
@@ -342,9 +347,12 @@ impl Element for AnyView {
                 }
 
                 let prepaint_start = cx.prepaint_index();
-                let mut element = (self.render)(self, cx);
-                element.layout_as_root(bounds.size.into(), cx);
-                element.prepaint_at(bounds.origin, cx);
+                let element = cx.with_drawing_view(self.entity_id(), |cx| {
+                    let mut element = (self.render)(self, cx);
+                    element.layout_as_root(bounds.size.into(), cx);
+                    element.prepaint_at(bounds.origin, cx);
+                    element
+                });
                 let prepaint_end = cx.prepaint_index();
 
                 (
@@ -362,7 +370,7 @@ impl Element for AnyView {
             })
         } else {
             let mut element = element.take().unwrap();
-            element.prepaint(cx);
+            cx.with_drawing_view(self.entity_id(), |cx| element.prepaint(cx));
             Some(element)
         }
     }
@@ -382,7 +390,7 @@ impl Element for AnyView {
                 let paint_start = cx.paint_index();
 
                 if let Some(element) = element {
-                    element.paint(cx);
+                    cx.with_drawing_view(self.entity_id(), |cx| element.paint(cx));
                 } else {
                     cx.reuse_paint(element_state.paint_range.clone());
                 }
@@ -393,7 +401,8 @@ impl Element for AnyView {
                 ((), element_state)
             })
         } else {
-            element.as_mut().unwrap().paint(cx);
+            let element = element.as_mut().unwrap();
+            cx.with_drawing_view(self.entity_id(), |cx| element.paint(cx));
         }
     }
 }
@@ -1,20 +1,21 @@
 use crate::{
-    hash, point, prelude::*, px, size, transparent_black, Action, AnyDrag, AnyElement, AnyTooltip,
-    AnyView, AppContext, Arena, Asset, AsyncWindowContext, AvailableSpace, Bounds, BoxShadow,
-    Context, Corners, CursorStyle, Decorations, DevicePixels, DispatchActionListener,
-    DispatchNodeId, DispatchTree, DisplayId, Edges, Effect, Entity, EntityId, EventEmitter,
-    FileDropEvent, Flatten, FontId, GPUSpecs, Global, GlobalElementId, GlyphId, Hsla, ImageData,
-    InputHandler, IsZero, KeyBinding, KeyContext, KeyDownEvent, KeyEvent, Keystroke,
-    KeystrokeEvent, LayoutId, LineLayoutIndex, Model, ModelContext, Modifiers,
-    ModifiersChangedEvent, MonochromeSprite, MouseButton, MouseEvent, MouseMoveEvent, MouseUpEvent,
-    Path, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler,
+    hash, point, prelude::*, px, size, transparent_black, AccessibilityNode, Action, AnyDrag,
+    AnyElement, AnyTooltip, AnyView, AppContext, Arena, Asset, AsyncWindowContext, AvailableSpace,
+    Bounds, BoxShadow, Context, Corners, CursorStyle, Decorations, DevicePixels,
+    DispatchActionListener, DispatchNodeId, DispatchTree, DisplayId, Edges, Effect,
+    ElementCacheMetrics, Entity, EntityId, EventEmitter, FileDropEvent, Flatten, FontId, GPUSpecs,
+    Global, GlobalElementId, GlyphId, Gradient, GradientStop, Hsla, ImageData, InputHandler,
+    IsZero, KeyBinding, KeyContext, KeyDownEvent, KeyEvent, Keystroke, KeystrokeEvent, LayoutId,
+    LineLayoutIndex, LinearGradient, Model, ModelContext, Modifiers, ModifiersChangedEvent,
+    MonochromeSprite, MouseButton, MouseEvent, MouseMoveEvent, MouseUpEvent, OffscreenWindow, Path,
+    PathPromptOptions, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler,
     PlatformWindow, Point, PolychromeSprite, PromptLevel, Quad, Render, RenderGlyphParams,
     RenderImageParams, RenderSvgParams, Replay, ResizeEdge, ScaledPixels, Scene, Shadow,
     SharedString, Size, StrikethroughStyle, Style, SubscriberSet, Subscription, TaffyLayoutEngine,
-    Task, TextStyle, TextStyleRefinement, TransformationMatrix, Underline, UnderlineStyle, View,
-    VisualContext, WeakView, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
-    WindowControls, WindowDecorations, WindowOptions, WindowParams, WindowTextSystem,
-    SUBPIXEL_VARIANTS,
+    Task, TextAntialiasing, TextStyle, TextStyleRefinement, TransformationMatrix, Underline,
+    UnderlineStyle, View, VisualContext, WeakView, WindowAppearance, WindowBackgroundAppearance,
+    WindowBounds, WindowControls, WindowDecorations, WindowOptions, WindowParams, WindowTextSystem,
+    MAX_GRADIENT_STOPS, SUBPIXEL_VARIANTS,
 };
 use anyhow::{anyhow, Context as _, Result};
 use collections::{FxHashMap, FxHashSet};
@@ -25,6 +26,7 @@ use futures::{future::Shared, FutureExt};
 use media::core_video::CVImageBuffer;
 use parking_lot::RwLock;
 use refineable::Refineable;
+use serde_derive::Serialize;
 use slotmap::SlotMap;
 use smallvec::SmallVec;
 use std::{
@@ -38,6 +40,7 @@ use std::{
     marker::PhantomData,
     mem,
     ops::Range,
+    path::PathBuf,
     rc::Rc,
     sync::{
         atomic::{AtomicUsize, Ordering::SeqCst},
@@ -49,12 +52,23 @@ use util::post_inc;
 use util::{measure, ResultExt};
 use uuid::Uuid;
 
+mod glyph_atlas;
 mod prompts;
+mod toasts;
 
+use glyph_atlas::GlyphAtlasUsage;
+use toasts::ToastStack;
+
+pub use glyph_atlas::GlyphAtlasMetrics;
 pub use prompts::*;
+pub use toasts::ToastId;
 
 pub(crate) const DEFAULT_WINDOW_SIZE: Size<Pixels> = size(px(1024.), px(700.));
 
+/// How long a text cursor painted with [`WindowContext::paint_cursor`] stays visible or hidden
+/// before blinking.
+pub(crate) const CURSOR_BLINK_INTERVAL: Duration = Duration::from_millis(500);
+
 /// Represents the two different phases when dispatching events.
 #[derive(Default, Copy, Clone, Debug, Eq, PartialEq)]
 pub enum DispatchPhase {
@@ -89,6 +103,9 @@ type AnyObserver = Box<dyn FnMut(&mut WindowContext) -> bool + 'static>;
 type AnyWindowFocusListener =
     Box<dyn FnMut(&WindowFocusEvent, &mut WindowContext) -> bool + 'static>;
 
+/// Dispatched to the mouse listeners when the active drag is cancelled.
+struct DragCancelEvent;
+
 struct WindowFocusEvent {
     previous_focus_path: SmallVec<[FocusId; 8]>,
     current_focus_path: SmallVec<[FocusId; 8]>,
@@ -400,6 +417,8 @@ pub(crate) struct Frame {
     pub(crate) window_active: bool,
     pub(crate) element_states: FxHashMap<(GlobalElementId, TypeId), ElementStateBox>,
     accessed_element_states: Vec<(GlobalElementId, TypeId)>,
+    /// The globals read by each view while it was drawn.
+    pub(crate) global_reads: FxHashMap<EntityId, FxHashSet<TypeId>>,
     pub(crate) mouse_listeners: Vec<Option<AnyMouseListener>>,
     pub(crate) dispatch_tree: DispatchTree,
     pub(crate) scene: Scene,
@@ -408,8 +427,11 @@ pub(crate) struct Frame {
     pub(crate) input_handlers: Vec<Option<PlatformInputHandler>>,
     pub(crate) tooltip_requests: Vec<Option<TooltipRequest>>,
     pub(crate) cursor_styles: Vec<CursorStyleRequest>,
+    pub(crate) accessibility_nodes: Vec<AccessibilityNode>,
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) debug_bounds: FxHashMap<String, Bounds<Pixels>>,
+    #[cfg(any(test, feature = "test-support"))]
+    pub(crate) measured_bounds: Vec<(ElementId, Bounds<Pixels>)>,
 }
 
 #[derive(Clone, Default)]
@@ -428,6 +450,9 @@ pub(crate) struct PaintIndex {
     mouse_listeners_index: usize,
     input_handlers_index: usize,
     cursor_styles_index: usize,
+    accessibility_nodes_index: usize,
+    #[cfg(any(test, feature = "test-support"))]
+    measured_bounds_index: usize,
     accessed_element_states_index: usize,
     line_layout_index: LineLayoutIndex,
 }
@@ -439,6 +464,7 @@ impl Frame {
             window_active: false,
             element_states: FxHashMap::default(),
             accessed_element_states: Vec::new(),
+            global_reads: FxHashMap::default(),
             mouse_listeners: Vec::new(),
             dispatch_tree,
             scene: Scene::default(),
@@ -447,21 +473,28 @@ impl Frame {
             input_handlers: Vec::new(),
             tooltip_requests: Vec::new(),
             cursor_styles: Vec::new(),
+            accessibility_nodes: Vec::new(),
 
             #[cfg(any(test, feature = "test-support"))]
             debug_bounds: FxHashMap::default(),
+            #[cfg(any(test, feature = "test-support"))]
+            measured_bounds: Vec::new(),
         }
     }
 
     pub(crate) fn clear(&mut self) {
         self.element_states.clear();
         self.accessed_element_states.clear();
+        self.global_reads.clear();
         self.mouse_listeners.clear();
         self.dispatch_tree.clear();
         self.scene.clear();
         self.input_handlers.clear();
         self.tooltip_requests.clear();
         self.cursor_styles.clear();
+        self.accessibility_nodes.clear();
+        #[cfg(any(test, feature = "test-support"))]
+        self.measured_bounds.clear();
         self.hitboxes.clear();
         self.deferred_draws.clear();
         self.focus = None;
@@ -505,10 +538,16 @@ impl Frame {
 pub struct Window {
     pub(crate) handle: AnyWindowHandle,
     pub(crate) removed: bool,
+    /// The window this one is anchored to, which closes it when it's closed.
+    pub(crate) parent: Option<AnyWindowHandle>,
     pub(crate) platform_window: Box<dyn PlatformWindow>,
     display_id: Option<DisplayId>,
     sprite_atlas: Arc<dyn PlatformAtlas>,
+    glyph_atlas_usage: GlyphAtlasUsage,
+    pub(crate) element_cache_metrics: ElementCacheMetrics,
     text_system: Arc<WindowTextSystem>,
+    text_antialiasing: TextAntialiasing,
+    background_appearance: WindowBackgroundAppearance,
     rem_size: Pixels,
     /// The stack of override values for the window's rem size.
     ///
@@ -522,6 +561,16 @@ pub struct Window {
     pub(crate) text_style_stack: Vec<TextStyleRefinement>,
     pub(crate) element_offset_stack: Vec<Point<Pixels>>,
     pub(crate) content_mask_stack: Vec<ContentMask<Pixels>>,
+    pub(crate) element_opacity: f32,
+    /// Whether text cursors painted with [`WindowContext::paint_cursor`] are in the shown half of
+    /// their blink.
+    cursor_blink_visible: bool,
+    /// Flips `cursor_blink_visible` every [`CURSOR_BLINK_INTERVAL`], while cursors are painted in
+    /// the window when it's active.
+    cursor_blink_task: Option<Task<()>>,
+    /// The views that painted a cursor since the blink last flipped, which are redrawn when it
+    /// flips again.
+    cursor_views: FxHashSet<EntityId>,
     pub(crate) requested_autoscroll: Option<Bounds<Pixels>>,
     pub(crate) rendered_frame: Frame,
     pub(crate) next_frame: Frame,
@@ -546,6 +595,7 @@ pub struct Window {
     pub(crate) dirty: Rc<Cell<bool>>,
     pub(crate) needs_present: Rc<Cell<bool>>,
     pub(crate) last_input_timestamp: Rc<Cell<Instant>>,
+    min_frame_interval: Rc<Cell<Option<Duration>>>,
     pub(crate) refreshing: bool,
     pub(crate) draw_phase: DrawPhase,
     activation_observers: SubscriberSet<(), AnyObserver>,
@@ -555,6 +605,27 @@ pub struct Window {
     pending_modifier: ModifierState,
     pending_input_observers: SubscriberSet<(), AnyObserver>,
     prompt: Option<RenderablePromptHandle>,
+    toasts: Option<View<ToastStack>>,
+    frame_metrics_enabled: bool,
+    last_frame_metrics: Cell<Option<FrameMetrics>>,
+    occlusion_culling_enabled: bool,
+}
+
+/// Timings for the drawing of a single frame, collected while
+/// [`WindowContext::set_frame_metrics_enabled`] is on.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FrameMetrics {
+    /// Time spent laying out and prepainting the element tree.
+    pub prepaint: Duration,
+    /// Time spent painting the element tree into the scene.
+    pub paint: Duration,
+    /// Time spent submitting the scene to the platform renderer.
+    pub present: Duration,
+    /// The number of primitives painted into the scene.
+    pub primitive_count: usize,
+    /// The number of primitives that weren't rendered because they were hidden behind opaque
+    /// quads, which is zero unless [`WindowContext::set_occlusion_culling_enabled`] is on.
+    pub occluded_primitive_count: usize,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -622,24 +693,39 @@ impl Window {
             app_id,
             window_min_size,
             window_decorations,
+            offscreen,
+            parent,
         } = options;
 
+        // If the display the window was last shown on is no longer connected, the restored
+        // bounds may be entirely off-screen. Center the window on the primary display instead.
+        let (window_bounds, display_id) = match display_id {
+            Some(id) if cx.find_display(id).is_none() => (
+                window_bounds
+                    .map(|bounds| bounds.map(|bounds| Bounds::centered(None, bounds.size, cx))),
+                None,
+            ),
+            _ => (window_bounds, display_id),
+        };
+
         let bounds = window_bounds
             .map(|bounds| bounds.get_bounds())
             .unwrap_or_else(|| default_bounds(display_id, cx));
-        let mut platform_window = cx.platform.open_window(
-            handle,
-            WindowParams {
-                bounds,
-                titlebar,
-                kind,
-                is_movable,
-                focus,
-                show,
-                display_id,
-                window_min_size,
-            },
-        )?;
+        let params = WindowParams {
+            bounds,
+            titlebar,
+            kind,
+            is_movable,
+            focus,
+            show,
+            display_id,
+            window_min_size,
+        };
+        let mut platform_window: Box<dyn PlatformWindow> = if offscreen {
+            Box::new(OffscreenWindow::new(params))
+        } else {
+            cx.platform.open_window(handle, params)?
+        };
         let display_id = platform_window.display().map(|display| display.id());
         let sprite_atlas = platform_window.sprite_atlas();
         let mouse_position = platform_window.mouse_position();
@@ -654,6 +740,7 @@ impl Window {
         let needs_present = Rc::new(Cell::new(false));
         let next_frame_callbacks: Rc<RefCell<Vec<FrameCallback>>> = Default::default();
         let last_input_timestamp = Rc::new(Cell::new(Instant::now()));
+        let min_frame_interval = Rc::new(Cell::new(None));
 
         platform_window
             .request_decorations(window_decorations.unwrap_or(WindowDecorations::Server));
@@ -680,6 +767,8 @@ impl Window {
             let needs_present = needs_present.clone();
             let next_frame_callbacks = next_frame_callbacks.clone();
             let last_input_timestamp = last_input_timestamp.clone();
+            let min_frame_interval = min_frame_interval.clone();
+            let mut last_draw_timestamp = None::<Instant>;
             move || {
                 let next_frame_callbacks = next_frame_callbacks.take();
                 if !next_frame_callbacks.is_empty() {
@@ -698,7 +787,14 @@ impl Window {
                     || (active.get()
                         && last_input_timestamp.get().elapsed() < Duration::from_secs(1));
 
-                if dirty.get() {
+                // When the frame rate is capped, leave the window dirty so that it's drawn on a
+                // later frame once enough time has passed.
+                let frame_due = min_frame_interval.get().map_or(true, |interval| {
+                    last_draw_timestamp.map_or(true, |timestamp| timestamp.elapsed() >= interval)
+                });
+
+                if dirty.get() && frame_due {
+                    last_draw_timestamp = Some(Instant::now());
                     measure("frame duration", || {
                         handle
                             .update(&mut cx, |_, cx| {
@@ -748,6 +844,11 @@ impl Window {
                 handle
                     .update(&mut cx, |_, cx| {
                         cx.window.active.set(active);
+                        // The mouse up ending a drag may be delivered to another window, so
+                        // drags are cancelled rather than left following the mouse.
+                        if !active {
+                            cx.cancel_drag();
+                        }
                         cx.window
                             .activation_observers
                             .clone()
@@ -785,10 +886,15 @@ impl Window {
         Ok(Window {
             handle,
             removed: false,
+            parent,
             platform_window,
             display_id,
             sprite_atlas,
+            glyph_atlas_usage: GlyphAtlasUsage::new(),
+            element_cache_metrics: ElementCacheMetrics::default(),
             text_system,
+            text_antialiasing: TextAntialiasing::default(),
+            background_appearance: window_background,
             rem_size: px(16.),
             rem_size_override_stack: SmallVec::new(),
             viewport_size: content_size,
@@ -798,6 +904,10 @@ impl Window {
             text_style_stack: Vec::new(),
             element_offset_stack: Vec::new(),
             content_mask_stack: Vec::new(),
+            element_opacity: 1.,
+            cursor_blink_visible: true,
+            cursor_blink_task: None,
+            cursor_views: FxHashSet::default(),
             requested_autoscroll: None,
             rendered_frame: Frame::new(DispatchTree::new(cx.keymap.clone(), cx.actions.clone())),
             next_frame: Frame::new(DispatchTree::new(cx.keymap.clone(), cx.actions.clone())),
@@ -822,6 +932,7 @@ impl Window {
             dirty,
             needs_present,
             last_input_timestamp,
+            min_frame_interval,
             refreshing: false,
             draw_phase: DrawPhase::None,
             activation_observers: SubscriberSet::new(),
@@ -831,6 +942,10 @@ impl Window {
             pending_modifier: ModifierState::default(),
             pending_input_observers: SubscriberSet::new(),
             prompt: None,
+            toasts: None,
+            frame_metrics_enabled: false,
+            last_frame_metrics: Cell::new(None),
+            occlusion_culling_enabled: false,
         })
     }
     fn new_focus_listener(
@@ -839,6 +954,22 @@ impl Window {
     ) -> (Subscription, impl FnOnce()) {
         self.focus_listeners.insert((), value)
     }
+
+    /// Marks the given view and its ancestors as dirty, so that they aren't reused from the
+    /// previous frame when the window is next drawn.
+    pub(crate) fn mark_view_dirty(&mut self, view_id: EntityId) {
+        for view_id in self
+            .rendered_frame
+            .dispatch_tree
+            .view_path(view_id)
+            .into_iter()
+            .rev()
+        {
+            if !self.dirty_views.insert(view_id) {
+                break;
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -850,7 +981,7 @@ pub(crate) struct DispatchEventResult {
 /// Indicates which region of the window is visible. Content falling outside of this mask will not be
 /// rendered. Currently, only rectangular content masks are supported, but we give the mask its own type
 /// to leave room to support more complex shapes in the future.
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
 #[repr(C)]
 pub struct ContentMask<P: Clone + Default + Debug> {
     /// The bounds
@@ -901,18 +1032,7 @@ impl<'a> WindowContext<'a> {
     /// Indicate that this view has changed, which will invoke any observers and also mark the window as dirty.
     /// If this view or any of its ancestors are *cached*, notifying it will cause it or its ancestors to be redrawn.
     pub fn notify(&mut self, view_id: EntityId) {
-        for view_id in self
-            .window
-            .rendered_frame
-            .dispatch_tree
-            .view_path(view_id)
-            .into_iter()
-            .rev()
-        {
-            if !self.window.dirty_views.insert(view_id) {
-                break;
-            }
-        }
+        self.window.mark_view_dirty(view_id);
 
         if self.window.draw_phase == DrawPhase::None {
             self.window.dirty.set(true);
@@ -1188,7 +1308,16 @@ impl<'a> WindowContext<'a> {
     }
 
     fn bounds_changed(&mut self) {
-        self.window.scale_factor = self.window.platform_window.scale_factor();
+        let scale_factor = self.window.platform_window.scale_factor();
+        if scale_factor != self.window.scale_factor {
+            // Glyphs are rasterized for a specific scale factor, so the tiles rasterized for the
+            // previous one won't be used again. They're evicted once the frames that may still be
+            // drawing them are done, instead of letting them accumulate in the atlas.
+            self.window
+                .glyph_atlas_usage
+                .scale_factor_changed(scale_factor);
+        }
+        self.window.scale_factor = scale_factor;
         self.window.viewport_size = self.window.platform_window.content_size();
         self.window.display_id = self
             .window
@@ -1292,10 +1421,15 @@ impl<'a> WindowContext<'a> {
     }
 
     /// Sets the window background appearance.
-    pub fn set_background_appearance(&self, background_appearance: WindowBackgroundAppearance) {
+    pub fn set_background_appearance(&mut self, background_appearance: WindowBackgroundAppearance) {
         self.window
             .platform_window
             .set_background_appearance(background_appearance);
+        if self.window.background_appearance != background_appearance {
+            self.window.background_appearance = background_appearance;
+            // Whether text can use font smoothing depends on the background.
+            self.refresh();
+        }
     }
 
     /// Mark the window as dirty at the platform level.
@@ -1311,6 +1445,157 @@ impl<'a> WindowContext<'a> {
             .find(|display| Some(display.id()) == self.window.display_id)
     }
 
+    /// Returns the part of the viewport that lies within the visible bounds of the window's
+    /// display, which exclude system UI such as the menu bar and dock. Relative to the viewport,
+    /// like [`Self::viewport_size`]. Falls back to the whole viewport when the window isn't on a
+    /// display, or doesn't overlap its visible bounds at all.
+    pub fn visible_viewport_bounds(&self) -> Bounds<Pixels> {
+        let viewport = Bounds {
+            origin: Point::default(),
+            size: self.viewport_size(),
+        };
+        let Some(display) = self.display() else {
+            return viewport;
+        };
+
+        // The window's bounds include its titlebar, if it has one, while the viewport is only its
+        // content.
+        let window_bounds = self.bounds();
+        let content_origin = window_bounds.origin
+            + point(
+                px(0.),
+                window_bounds.size.height - self.viewport_size().height,
+            );
+        let visible_bounds = display.visible_bounds();
+        let visible_bounds = Bounds {
+            origin: visible_bounds.origin - content_origin,
+            size: visible_bounds.size,
+        };
+        let bounds = viewport.intersect(&visible_bounds);
+        if bounds.is_empty() {
+            viewport
+        } else {
+            bounds
+        }
+    }
+
+    /// Returns the window's [`OffscreenWindow`], if it was opened with
+    /// [`WindowOptions::offscreen`].
+    pub fn offscreen_window(&self) -> Option<OffscreenWindow> {
+        self.window.platform_window.as_offscreen().cloned()
+    }
+
+    /// Sets whether to measure how long each frame takes to draw. This is off by default, so
+    /// that the timing isn't paid for unless someone is looking at it.
+    pub fn set_frame_metrics_enabled(&mut self, enabled: bool) {
+        self.window.frame_metrics_enabled = enabled;
+        if !enabled {
+            self.window.last_frame_metrics.set(None);
+        }
+    }
+
+    /// Sets whether to skip rendering the primitives hidden behind opaque quads, such as what's
+    /// painted beneath a card that covers the window's background. This is off by default, as
+    /// looking for hidden primitives costs time in every frame, which is only made up for when
+    /// many of them are hidden.
+    pub fn set_occlusion_culling_enabled(&mut self, enabled: bool) {
+        self.window.occlusion_culling_enabled = enabled;
+        self.refresh();
+    }
+
+    /// Returns how much of this window's sprite atlas is occupied by glyphs, and how many glyphs
+    /// have been evicted from it to stay within its budget.
+    pub fn glyph_atlas_metrics(&self) -> GlyphAtlasMetrics {
+        self.window.glyph_atlas_usage.metrics()
+    }
+
+    /// Returns how many times the [`Cached`](crate::Cached) elements in this window have been
+    /// reused from the previous frame, and how many times they've been drawn again.
+    pub fn element_cache_metrics(&self) -> ElementCacheMetrics {
+        self.window.element_cache_metrics
+    }
+
+    /// Sets how many bytes of this window's sprite atlas glyphs may occupy. Once exceeded, the
+    /// least recently used glyphs are evicted after each frame, and rasterized again if they're
+    /// painted later.
+    pub fn set_glyph_atlas_budget(&mut self, budget_bytes: usize) {
+        self.window.glyph_atlas_usage.set_budget(budget_bytes);
+    }
+
+    /// Sets how text in this window is anti-aliased.
+    ///
+    /// Windows with a transparent or blurred background always use
+    /// [`TextAntialiasing::Grayscale`], as smoothed glyphs don't blend correctly over the content
+    /// behind the window.
+    pub fn set_text_antialiasing(&mut self, antialiasing: TextAntialiasing) {
+        if self.window.text_antialiasing != antialiasing {
+            self.window.text_antialiasing = antialiasing;
+            self.refresh();
+        }
+    }
+
+    /// Returns how text in this window is anti-aliased.
+    pub fn text_antialiasing(&self) -> TextAntialiasing {
+        if self.window.background_appearance == WindowBackgroundAppearance::Opaque {
+            self.window.text_antialiasing
+        } else {
+            TextAntialiasing::Grayscale
+        }
+    }
+
+    /// Caps how many frames per second this window draws, to save power in windows that don't
+    /// need to animate smoothly. Updates made between frames are drawn once the next frame is due.
+    /// Passing `None` removes the cap, drawing at most once per display refresh.
+    pub fn set_max_fps(&mut self, max_fps: Option<u32>) {
+        self.window.min_frame_interval.set(
+            max_fps
+                .filter(|max_fps| *max_fps > 0)
+                .map(|max_fps| Duration::from_secs(1) / max_fps),
+        );
+    }
+
+    /// Records semantic information about an element painted at the given bounds, to be exposed
+    /// to assistive technologies. This method should only be called as part of the paint phase of
+    /// element drawing.
+    pub fn insert_accessibility_node(&mut self, node: AccessibilityNode, bounds: Bounds<Pixels>) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+
+        self.window
+            .next_frame
+            .accessibility_nodes
+            .push(AccessibilityNode { bounds, ..node });
+    }
+
+    /// Returns the accessibility nodes painted in the most recently drawn frame, in paint order.
+    pub fn accessibility_snapshot(&self) -> &[AccessibilityNode] {
+        &self.window.rendered_frame.accessibility_nodes
+    }
+
+    /// Returns the bounds the element with the given id was painted at in the most recently drawn
+    /// frame, if it was painted.
+    ///
+    /// Only elements with an id are measured. If several painted elements share the id, the bounds
+    /// of the last one painted are returned.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn measured_bounds(&self, element_id: impl Into<ElementId>) -> Option<Bounds<Pixels>> {
+        let element_id = element_id.into();
+        self.window
+            .rendered_frame
+            .measured_bounds
+            .iter()
+            .rev()
+            .find_map(|(id, bounds)| (*id == element_id).then_some(*bounds))
+    }
+
+    /// Returns the metrics for the most recently drawn frame, if frame metrics are enabled.
+    pub fn last_frame_metrics(&self) -> Option<FrameMetrics> {
+        self.window.last_frame_metrics.get()
+    }
+
     /// Show the platform character palette.
     pub fn show_character_palette(&self) {
         self.window.platform_window.show_character_palette();
@@ -1411,6 +1696,54 @@ impl<'a> WindowContext<'a> {
         self.window.platform_window.completed_frame();
     }
 
+    /// Draws and presents a frame right away, rather than when the platform next requests one,
+    /// such as before capturing the window's contents or to keep a test independent of the
+    /// platform's frame loop.
+    ///
+    /// The views that have been notified are rendered again, or every view if the window has been
+    /// refreshed. Observers of those notifications run once the current update completes, as
+    /// usual.
+    ///
+    /// Returns the metrics of the new frame, whether or not frame metrics are enabled, or `None`
+    /// without drawing anything when called while the window is being drawn.
+    pub fn draw_frame(&mut self) -> Option<FrameMetrics> {
+        if self.window.draw_phase != DrawPhase::None {
+            return None;
+        }
+
+        let frame_metrics_enabled = mem::replace(&mut self.window.frame_metrics_enabled, true);
+        self.draw();
+        self.present();
+        self.window.frame_metrics_enabled = frame_metrics_enabled;
+        if frame_metrics_enabled {
+            self.window.last_frame_metrics.get()
+        } else {
+            self.window.last_frame_metrics.take()
+        }
+    }
+
+    /// Captures the window's current contents at its device resolution, i.e. its content size
+    /// multiplied by its scale factor, such as to attach a screenshot to a bug report.
+    ///
+    /// Like any other [`ImageData`], the pixels are in BGRA order, so the capture can be shown
+    /// with an [`img`](crate::img) element.
+    ///
+    /// Offscreen and test windows don't rasterize their scenes, so their captures are blank:
+    /// fully transparent images with only the window's device size. Tests that check what a view
+    /// paints can compare `VisualTestContext::scene_snapshot` against a snapshot instead.
+    pub fn capture_image(&mut self) -> Task<Result<Arc<ImageData>>> {
+        if self.window.dirty.get() {
+            self.draw();
+        }
+
+        let image = self
+            .window
+            .platform_window
+            .render_to_image(&self.window.rendered_frame.scene)
+            .map(|buffer| Arc::new(ImageData::new(vec![image::Frame::new(buffer)])));
+        Task::ready(image)
+    }
+
     /// Produces a new frame and assigns it to `rendered_frame`. To actually show
     /// the contents of the new [Scene], use [present].
     #[profiling::function]
@@ -1442,6 +1775,13 @@ impl<'a> WindowContext<'a> {
         self.window
             .next_frame
             .finish(&mut self.window.rendered_frame);
+        if self.window.occlusion_culling_enabled {
+            let occluded_primitive_count = self.window.next_frame.scene.cull_occluded();
+            if let Some(mut metrics) = self.window.last_frame_metrics.get() {
+                metrics.occluded_primitive_count = occluded_primitive_count;
+                self.window.last_frame_metrics.set(Some(metrics));
+            }
+        }
         ELEMENT_ARENA.with_borrow_mut(|element_arena| {
             let percentage = (element_arena.len() as f32 / element_arena.capacity() as f32) * 100.;
             if percentage >= 80. {
@@ -1455,6 +1795,10 @@ impl<'a> WindowContext<'a> {
         let previous_window_active = self.window.rendered_frame.window_active;
         mem::swap(&mut self.window.rendered_frame, &mut self.window.next_frame);
         self.window.next_frame.clear();
+        self.window.glyph_atlas_usage.finish_frame(
+            &self.window.rendered_frame.scene,
+            self.window.sprite_atlas.as_ref(),
+        );
         let current_focus_path = self.window.rendered_frame.focus_path();
         let current_window_active = self.window.rendered_frame.window_active;
 
@@ -1494,14 +1838,22 @@ impl<'a> WindowContext<'a> {
 
     #[profiling::function]
     fn present(&self) {
+        let present_start = self.window.frame_metrics_enabled.then(Instant::now);
         self.window
             .platform_window
             .draw(&self.window.rendered_frame.scene);
+        if let Some((present_start, mut metrics)) =
+            present_start.zip(self.window.last_frame_metrics.get())
+        {
+            metrics.present = present_start.elapsed();
+            self.window.last_frame_metrics.set(Some(metrics));
+        }
         self.window.needs_present.set(false);
         profiling::finish_frame!();
     }
 
     fn draw_roots(&mut self) {
+        let prepaint_start = self.window.frame_metrics_enabled.then(Instant::now);
         self.window.draw_phase = DrawPhase::Prepaint;
         self.window.tooltip_bounds.take();
 
@@ -1514,6 +1866,13 @@ impl<'a> WindowContext<'a> {
         sorted_deferred_draws.sort_by_key(|ix| self.window.next_frame.deferred_draws[*ix].priority);
         self.prepaint_deferred_draws(&sorted_deferred_draws);
 
+        // Toasts are shown over everything but prompts, without keeping the window from being used.
+        let mut toasts_element = self.window.toasts.clone().map(|toasts| {
+            let mut element = toasts.into_any();
+            element.prepaint_as_root(Point::default(), self.window.viewport_size.into(), self);
+            element
+        });
+
         let mut prompt_element = None;
         let mut active_drag_element = None;
         let mut tooltip_element = None;
@@ -1535,11 +1894,16 @@ impl<'a> WindowContext<'a> {
         self.window.mouse_hit_test = self.window.next_frame.hit_test(self.window.mouse_position);
 
         // Now actually paint the elements.
+        let paint_start = self.window.frame_metrics_enabled.then(Instant::now);
         self.window.draw_phase = DrawPhase::Paint;
         root_element.paint(self);
 
         self.paint_deferred_draws(&sorted_deferred_draws);
 
+        if let Some(toasts_element) = toasts_element.as_mut() {
+            toasts_element.paint(self);
+        }
+
         if let Some(mut prompt_element) = prompt_element {
             prompt_element.paint(self);
         } else if let Some(mut drag_element) = active_drag_element {
@@ -1547,6 +1911,16 @@ impl<'a> WindowContext<'a> {
         } else if let Some(mut tooltip_element) = tooltip_element {
             tooltip_element.paint(self);
         }
+
+        if let Some((prepaint_start, paint_start)) = prepaint_start.zip(paint_start) {
+            self.window.last_frame_metrics.set(Some(FrameMetrics {
+                prepaint: paint_start - prepaint_start,
+                paint: paint_start.elapsed(),
+                present: Duration::ZERO,
+                primitive_count: self.window.next_frame.scene.primitive_count(),
+                occluded_primitive_count: 0,
+            }));
+        }
     }
 
     fn prepaint_tooltip(&mut self) -> Option<AnyElement> {
@@ -1557,18 +1931,15 @@ impl<'a> WindowContext<'a> {
         let tooltip_size = element.layout_as_root(AvailableSpace::min_size(), self);
 
         let mut tooltip_bounds = Bounds::new(mouse_position + point(px(1.), px(1.)), tooltip_size);
-        let window_bounds = Bounds {
-            origin: Point::default(),
-            size: self.viewport_size(),
-        };
+        let window_bounds = self.visible_viewport_bounds();
 
         if tooltip_bounds.right() > window_bounds.right() {
             let new_x = mouse_position.x - tooltip_bounds.size.width - px(1.);
-            if new_x >= Pixels::ZERO {
+            if new_x >= window_bounds.left() {
                 tooltip_bounds.origin.x = new_x;
             } else {
                 tooltip_bounds.origin.x = cmp::max(
-                    Pixels::ZERO,
+                    window_bounds.left(),
                     tooltip_bounds.origin.x - tooltip_bounds.right() - window_bounds.right(),
                 );
             }
@@ -1576,11 +1947,11 @@ impl<'a> WindowContext<'a> {
 
         if tooltip_bounds.bottom() > window_bounds.bottom() {
             let new_y = mouse_position.y - tooltip_bounds.size.height - px(1.);
-            if new_y >= Pixels::ZERO {
+            if new_y >= window_bounds.top() {
                 tooltip_bounds.origin.y = new_y;
             } else {
                 tooltip_bounds.origin.y = cmp::max(
-                    Pixels::ZERO,
+                    window_bounds.top(),
                     tooltip_bounds.origin.y - tooltip_bounds.bottom() - window_bounds.bottom(),
                 );
             }
@@ -1671,6 +2042,36 @@ impl<'a> WindowContext<'a> {
         }
     }
 
+    /// Draws the given view with `f`, attributing the globals that are read to the view so that
+    /// it's redrawn when they're updated.
+    pub(crate) fn with_drawing_view<R>(
+        &mut self,
+        view_id: EntityId,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let parent_view_id = self.app.drawing_view.replace(Some(view_id));
+        let result = f(self);
+        self.app.drawing_view.set(parent_view_id);
+
+        let global_reads = &mut self.window.next_frame.global_reads;
+        for (view_id, global_type) in self.app.global_reads.get_mut().drain(..) {
+            global_reads.entry(view_id).or_default().insert(global_type);
+        }
+        result
+    }
+
+    /// Carries over the globals read by a view that is reused from the previous frame.
+    pub(crate) fn reuse_global_reads(&mut self, view_id: EntityId) {
+        if let Some(global_types) = self.window.rendered_frame.global_reads.remove(&view_id) {
+            self.window
+                .next_frame
+                .global_reads
+                .entry(view_id)
+                .or_default()
+                .extend(global_types);
+        }
+    }
+
     pub(crate) fn reuse_prepaint(&mut self, range: Range<PrepaintStateIndex>) {
         let window = &mut self.window;
         window.next_frame.hitboxes.extend(
@@ -1699,6 +2100,15 @@ impl<'a> WindowContext<'a> {
             &mut window.rendered_frame.dispatch_tree,
             window.focus,
         );
+        for view_id in window
+            .next_frame
+            .dispatch_tree
+            .view_ids(reused_subtree.new_range())
+        {
+            if let Some(global_types) = window.rendered_frame.global_reads.remove(&view_id) {
+                window.next_frame.global_reads.insert(view_id, global_types);
+            }
+        }
 
         if reused_subtree.contains_focus() {
             window.next_frame.focus = window.focus;
@@ -1721,12 +2131,50 @@ impl<'a> WindowContext<'a> {
         );
     }
 
+    /// Returns whether what was drawn within the given ranges of the previous frame can be reused
+    /// at a different position.
+    ///
+    /// Deferred draws are replayed separately, and input handlers can't be moved, so elements
+    /// drawing either have to be drawn again.
+    pub(crate) fn can_reuse_with_offset(
+        &self,
+        prepaint_range: &Range<PrepaintStateIndex>,
+        paint_range: &Range<PaintIndex>,
+    ) -> bool {
+        prepaint_range.start.deferred_draws_index == prepaint_range.end.deferred_draws_index
+            && paint_range.start.input_handlers_index == paint_range.end.input_handlers_index
+    }
+
+    /// Like [`Self::reuse_prepaint`], but moves the reused hitboxes by the given offset, for an
+    /// element that has only moved since the previous frame.
+    ///
+    /// Content masks equal to `inherited_mask`, the mask the element was prepainted within, are
+    /// kept where they are, while those narrowed by elements inside it move with them.
+    pub(crate) fn reuse_prepaint_with_offset(
+        &mut self,
+        range: Range<PrepaintStateIndex>,
+        offset: Point<Pixels>,
+        inherited_mask: &ContentMask<Pixels>,
+    ) {
+        let hitboxes_start = self.window.next_frame.hitboxes.len();
+        self.reuse_prepaint(range);
+        for hitbox in &mut self.window.next_frame.hitboxes[hitboxes_start..] {
+            hitbox.bounds.origin += offset;
+            if hitbox.content_mask != *inherited_mask {
+                hitbox.content_mask.bounds.origin += offset;
+            }
+        }
+    }
+
     pub(crate) fn paint_index(&self) -> PaintIndex {
         PaintIndex {
             scene_index: self.window.next_frame.scene.len(),
             mouse_listeners_index: self.window.next_frame.mouse_listeners.len(),
             input_handlers_index: self.window.next_frame.input_handlers.len(),
             cursor_styles_index: self.window.next_frame.cursor_styles.len(),
+            accessibility_nodes_index: self.window.next_frame.accessibility_nodes.len(),
+            #[cfg(any(test, feature = "test-support"))]
+            measured_bounds_index: self.window.next_frame.measured_bounds.len(),
             accessed_element_states_index: self.window.next_frame.accessed_element_states.len(),
             line_layout_index: self.window.text_system.layout_index(),
         }
@@ -1741,6 +2189,19 @@ impl<'a> WindowContext<'a> {
                 .iter()
                 .cloned(),
         );
+        window.next_frame.accessibility_nodes.extend(
+            window.rendered_frame.accessibility_nodes
+                [range.start.accessibility_nodes_index..range.end.accessibility_nodes_index]
+                .iter()
+                .cloned(),
+        );
+        #[cfg(any(test, feature = "test-support"))]
+        window.next_frame.measured_bounds.extend(
+            window.rendered_frame.measured_bounds
+                [range.start.measured_bounds_index..range.end.measured_bounds_index]
+                .iter()
+                .cloned(),
+        );
         window.next_frame.input_handlers.extend(
             window.rendered_frame.input_handlers
                 [range.start.input_handlers_index..range.end.input_handlers_index]
@@ -1768,6 +2229,43 @@ impl<'a> WindowContext<'a> {
         );
     }
 
+    /// Like [`Self::reuse_paint`], but moves the reused primitives and the bounds recorded for
+    /// them by the given offset. See [`Self::reuse_prepaint_with_offset`].
+    pub(crate) fn reuse_paint_with_offset(
+        &mut self,
+        range: Range<PaintIndex>,
+        offset: Point<Pixels>,
+        inherited_mask: &ContentMask<Pixels>,
+    ) {
+        let scale_factor = self.scale_factor();
+        let scene_range = range.start.scene_index..range.end.scene_index;
+        let accessibility_nodes_start = self.window.next_frame.accessibility_nodes.len();
+        #[cfg(any(test, feature = "test-support"))]
+        let measured_bounds_start = self.window.next_frame.measured_bounds.len();
+        // The primitives are replayed with the offset below, rather than as they were.
+        self.reuse_paint(
+            PaintIndex {
+                scene_index: range.end.scene_index,
+                ..range.start
+            }..range.end,
+        );
+
+        let window = &mut self.window;
+        for node in &mut window.next_frame.accessibility_nodes[accessibility_nodes_start..] {
+            node.bounds.origin += offset;
+        }
+        #[cfg(any(test, feature = "test-support"))]
+        for (_, bounds) in &mut window.next_frame.measured_bounds[measured_bounds_start..] {
+            bounds.origin += offset;
+        }
+        window.next_frame.scene.replay_with_offset(
+            scene_range,
+            &window.rendered_frame.scene,
+            offset.scale(scale_factor),
+            &inherited_mask.scale(scale_factor),
+        );
+    }
+
     /// Push a text style onto the stack, and call a function with that style active.
     /// Use [`AppContext::text_style`] to get the current, combined text style. This method
     /// should only be called as part of element drawing.
@@ -1850,6 +2348,23 @@ impl<'a> WindowContext<'a> {
         }
     }
 
+    /// Invoke the given function with everything it paints made translucent by the given opacity,
+    /// between 0.0 (fully transparent) and 1.0 (unchanged). Nested calls multiply their opacities.
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn with_opacity<R>(&mut self, opacity: f32, f: impl FnOnce(&mut Self) -> R) -> R {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+
+        let previous_opacity = self.window.element_opacity;
+        self.window.element_opacity = previous_opacity * opacity.clamp(0., 1.);
+        let result = f(self);
+        self.window.element_opacity = previous_opacity;
+        result
+    }
+
     /// Updates the global element offset relative to the current offset. This is used to implement
     /// scrolling. This method should only be called during the prepaint phase of element drawing.
     pub fn with_element_offset<R>(
@@ -2171,6 +2686,35 @@ impl<'a> WindowContext<'a> {
         }
     }
 
+    /// Updates or initializes state that lives across frames for the element with the given id,
+    /// such as while rendering a component that animates between frames. The id only needs to be
+    /// unique within the element that's being drawn, as it's namespaced like an element's id.
+    ///
+    /// The state is `None` the first time an id is seen, and is dropped once a frame is drawn
+    /// without it. This method should only be called as part of element drawing.
+    pub fn with_retained_state<S, R>(
+        &mut self,
+        id: impl Into<ElementId>,
+        f: impl FnOnce(&mut Option<S>, &mut Self) -> R,
+    ) -> R
+    where
+        S: 'static,
+    {
+        let global_id = GlobalElementId(
+            self.window
+                .element_id_stack
+                .iter()
+                .cloned()
+                .chain(Some(id.into()))
+                .collect(),
+        );
+        self.with_element_state(&global_id, |state: Option<Option<S>>, cx| {
+            let mut state = state.flatten();
+            let result = f(&mut state, cx);
+            (result, state)
+        })
+    }
+
     /// A variant of `with_element_state` that allows the element's id to be optional. This is a convenience
     /// method for elements where the element id may or may not be assigned. Prefer using `with_element_state`
     /// when the element is guaranteed to have an id.
@@ -2211,6 +2755,9 @@ impl<'a> WindowContext<'a> {
     /// at a later time. The `priority` parameter determines the drawing order relative to other deferred elements,
     /// with higher values being drawn on top.
     ///
+    /// Deferred elements are still drawn beneath the window's prompt, the dragged element, and the
+    /// active tooltip, which are drawn last regardless of priority.
+    ///
     /// This method should only be called as part of the prepaint phase of element drawing.
     pub fn defer_draw(
         &mut self,
@@ -2269,6 +2816,8 @@ impl<'a> WindowContext<'a> {
     }
 
     /// Paint one or more drop shadows into the scene for the next frame at the current z-index.
+    /// Shadows without any blur, offset or spread are skipped, as they would be entirely covered
+    /// by the element casting them.
     ///
     /// This method should only be called as part of the paint phase of element drawing.
     pub fn paint_shadows(
@@ -2285,7 +2834,15 @@ impl<'a> WindowContext<'a> {
 
         let scale_factor = self.scale_factor();
         let content_mask = self.content_mask();
+        let opacity = self.window.element_opacity;
         for shadow in shadows {
+            if shadow.blur_radius.is_zero()
+                && shadow.offset == Point::default()
+                && shadow.spread_radius <= Pixels::ZERO
+            {
+                continue;
+            }
+
             let mut shadow_bounds = bounds;
             shadow_bounds.origin += shadow.offset;
             shadow_bounds.dilate(shadow.spread_radius);
@@ -2295,7 +2852,7 @@ impl<'a> WindowContext<'a> {
                 bounds: shadow_bounds.scale(scale_factor),
                 content_mask: content_mask.scale(scale_factor),
                 corner_radii: corner_radii.scale(scale_factor),
-                color: shadow.color,
+                color: shadow.color.opacity(opacity),
             });
         }
     }
@@ -2303,6 +2860,7 @@ impl<'a> WindowContext<'a> {
     /// Paint one or more quads into the scene for the next frame at the current stacking context.
     /// Quads are colored rectangular regions with an optional background, border, and corner radius.
     /// see [`fill`](crate::fill), [`outline`](crate::outline), and [`quad`](crate::quad) to construct this type.
+    /// Corner radii are clamped to half of the quad's smaller side so that adjacent corners don't overlap.
     ///
     /// This method should only be called as part of the paint phase of element drawing.
     pub fn paint_quad(&mut self, quad: PaintQuad) {
@@ -2314,18 +2872,175 @@ impl<'a> WindowContext<'a> {
 
         let scale_factor = self.scale_factor();
         let content_mask = self.content_mask();
+        let opacity = self.window.element_opacity;
         self.window.next_frame.scene.insert_primitive(Quad {
             order: 0,
             pad: 0,
             bounds: quad.bounds.scale(scale_factor),
             content_mask: content_mask.scale(scale_factor),
-            background: quad.background,
-            border_color: quad.border_color,
-            corner_radii: quad.corner_radii.scale(scale_factor),
+            background: quad.background.opacity(opacity),
+            border_color: quad.border_color.opacity(opacity),
+            corner_radii: quad
+                .corner_radii
+                .clamp_radii_for_quad_size(quad.bounds.size)
+                .scale(scale_factor),
             border_widths: quad.border_widths.scale(scale_factor),
         });
     }
 
+    /// Paint a linear gradient into the scene for the next frame, filling the given bounds with
+    /// rounded corners.
+    ///
+    /// The gradient's colors are interpolated in linear color space, which avoids the muddy
+    /// midpoints of blending them in sRGB.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_gradient(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        gradient: &LinearGradient,
+        corner_radii: impl Into<Corners<Pixels>>,
+    ) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+
+        let mut stops = gradient
+            .stops()
+            .iter()
+            .take(MAX_GRADIENT_STOPS)
+            .copied()
+            .collect::<SmallVec<[_; MAX_GRADIENT_STOPS]>>();
+        if stops.is_empty() {
+            return;
+        }
+        stops.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        let scale_factor = self.scale_factor();
+        let content_mask = self.content_mask();
+        let opacity = self.window.element_opacity;
+        let mut gradient_stops = [GradientStop::default(); MAX_GRADIENT_STOPS];
+        for (gradient_stop, (offset, color)) in gradient_stops.iter_mut().zip(&stops) {
+            *gradient_stop = GradientStop {
+                offset: *offset,
+                color: color.opacity(opacity),
+            };
+        }
+        self.window.next_frame.scene.insert_primitive(Gradient {
+            order: 0,
+            pad: 0,
+            bounds: bounds.scale(scale_factor),
+            content_mask: content_mask.scale(scale_factor),
+            corner_radii: corner_radii
+                .into()
+                .clamp_radii_for_quad_size(bounds.size)
+                .scale(scale_factor),
+            angle: gradient.angle().0,
+            stop_count: stops.len() as u32,
+            stops: gradient_stops,
+        });
+    }
+
+    /// Paint a focus ring into the scene for the next frame, indicating that the element with the
+    /// given bounds has keyboard focus.
+    ///
+    /// The ring is a border of the given width, inset by its width from the edges of `bounds` so
+    /// that it stays within the element and doesn't overlap its neighbors. The corner radii are
+    /// shrunk by the same amount, to follow the shape of the element.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_focus_ring(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        corner_radii: impl Into<Corners<Pixels>>,
+        width: Pixels,
+        color: impl Into<Hsla>,
+    ) {
+        self.paint_quad(PaintQuad {
+            bounds: bounds.inset(width),
+            corner_radii: corner_radii
+                .into()
+                .map(|radius| (*radius - width).max(Pixels::ZERO)),
+            background: transparent_black(),
+            border_widths: Edges::all(width),
+            border_color: color.into(),
+        });
+    }
+
+    /// Paint a blinking text cursor into the scene for the next frame, as a thin bar of the
+    /// given height whose top is at `origin`.
+    ///
+    /// The cursor blinks while the window is active, by redrawing the view that painted it each
+    /// time it's shown or hidden. Call [`Self::reset_cursor_blink`] when the text is edited to
+    /// keep it visible while typing.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn paint_cursor(&mut self, origin: Point<Pixels>, height: Pixels, color: impl Into<Hsla>) {
+        let visible = if self.is_window_active() {
+            let view_id = self
+                .parent_view_id()
+                .or_else(|| self.window.root_view.as_ref().map(|view| view.entity_id()));
+            self.window.cursor_views.extend(view_id);
+            if self.window.cursor_blink_task.is_none() {
+                self.start_cursor_blink();
+            }
+            self.window.cursor_blink_visible
+        } else {
+            true
+        };
+
+        if visible {
+            self.paint_quad(fill(
+                Bounds::new(origin, size(px(2.), height)),
+                color.into(),
+            ));
+        }
+    }
+
+    /// Restarts the blink cycle of text cursors painted with [`Self::paint_cursor`], so that they
+    /// are shown immediately.
+    pub fn reset_cursor_blink(&mut self) {
+        let was_visible = mem::replace(&mut self.window.cursor_blink_visible, true);
+        if self.window.cursor_blink_task.is_some() {
+            self.start_cursor_blink();
+            if !was_visible {
+                for view_id in self.window.cursor_views.clone() {
+                    self.notify(view_id);
+                }
+            }
+        }
+    }
+
+    fn start_cursor_blink(&mut self) {
+        self.window.cursor_blink_task = Some(self.spawn(|mut cx| async move {
+            loop {
+                cx.background_executor().timer(CURSOR_BLINK_INTERVAL).await;
+                if !cx.update(|cx| cx.blink_cursors()).unwrap_or(false) {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// Shows or hides the text cursors, redrawing the views that painted them. Stops blinking,
+    /// and returns false, if no cursors were painted since the last blink.
+    fn blink_cursors(&mut self) -> bool {
+        let cursor_views = mem::take(&mut self.window.cursor_views);
+        if cursor_views.is_empty() {
+            self.window.cursor_blink_visible = true;
+            self.window.cursor_blink_task = None;
+            return false;
+        }
+
+        self.window.cursor_blink_visible = !self.window.cursor_blink_visible;
+        for view_id in cursor_views {
+            self.notify(view_id);
+        }
+        true
+    }
+
     /// Paint the given `Path` into the scene for the next frame at the current z-index.
     ///
     /// This method should only be called as part of the paint phase of element drawing.
@@ -2339,7 +3054,8 @@ impl<'a> WindowContext<'a> {
         let scale_factor = self.scale_factor();
         let content_mask = self.content_mask();
         path.content_mask = content_mask;
-        path.color = color.into();
+        let color: Hsla = color.into();
+        path.color = color.opacity(self.window.element_opacity);
         self.window
             .next_frame
             .scene
@@ -2378,7 +3094,10 @@ impl<'a> WindowContext<'a> {
             pad: 0,
             bounds: bounds.scale(scale_factor),
             content_mask: content_mask.scale(scale_factor),
-            color: style.color.unwrap_or_default(),
+            color: style
+                .color
+                .unwrap_or_default()
+                .opacity(self.window.element_opacity),
             thickness: style.thickness.scale(scale_factor),
             wavy: style.wavy,
         });
@@ -2413,7 +3132,10 @@ impl<'a> WindowContext<'a> {
             bounds: bounds.scale(scale_factor),
             content_mask: content_mask.scale(scale_factor),
             thickness: style.thickness.scale(scale_factor),
-            color: style.color.unwrap_or_default(),
+            color: style
+                .color
+                .unwrap_or_default()
+                .opacity(self.window.element_opacity),
             wavy: false,
         });
     }
@@ -2453,10 +3175,17 @@ impl<'a> WindowContext<'a> {
             subpixel_variant,
             scale_factor,
             is_emoji: false,
+            antialiasing: self.text_antialiasing(),
         };
 
         let raster_bounds = self.text_system().raster_bounds(&params)?;
-        if !raster_bounds.is_zero() {
+        let content_mask = self.content_mask().scale(scale_factor);
+        let glyph_bounds = Bounds {
+            origin: glyph_origin.map(|px| px.floor()) + raster_bounds.origin.map(Into::into),
+            size: raster_bounds.size.map(Into::into),
+        };
+        // Skip rasterizing glyphs that would be entirely clipped away.
+        if !raster_bounds.is_zero() && !glyph_bounds.intersect(&content_mask.bounds).is_empty() {
             let tile = self
                 .window
                 .sprite_atlas
@@ -2465,11 +3194,11 @@ impl<'a> WindowContext<'a> {
                     Ok(Some((size, Cow::Owned(bytes))))
                 })?
                 .expect("Callback above only errors or returns Some");
+            self.window.glyph_atlas_usage.record(&params, &tile);
             let bounds = Bounds {
-                origin: glyph_origin.map(|px| px.floor()) + raster_bounds.origin.map(Into::into),
+                origin: glyph_bounds.origin,
                 size: tile.bounds.size.map(Into::into),
             };
-            let content_mask = self.content_mask().scale(scale_factor);
             self.window
                 .next_frame
                 .scene
@@ -2478,7 +3207,7 @@ impl<'a> WindowContext<'a> {
                     pad: 0,
                     bounds,
                     content_mask,
-                    color,
+                    color: color.opacity(self.window.element_opacity),
                     tile,
                     transformation: TransformationMatrix::unit(),
                 });
@@ -2517,10 +3246,17 @@ impl<'a> WindowContext<'a> {
             subpixel_variant: Default::default(),
             scale_factor,
             is_emoji: true,
+            antialiasing: TextAntialiasing::Grayscale,
         };
 
         let raster_bounds = self.text_system().raster_bounds(&params)?;
-        if !raster_bounds.is_zero() {
+        let content_mask = self.content_mask().scale(scale_factor);
+        let glyph_bounds = Bounds {
+            origin: glyph_origin.map(|px| px.floor()) + raster_bounds.origin.map(Into::into),
+            size: raster_bounds.size.map(Into::into),
+        };
+        // Skip rasterizing glyphs that would be entirely clipped away.
+        if !raster_bounds.is_zero() && !glyph_bounds.intersect(&content_mask.bounds).is_empty() {
             let tile = self
                 .window
                 .sprite_atlas
@@ -2529,12 +3265,12 @@ impl<'a> WindowContext<'a> {
                     Ok(Some((size, Cow::Owned(bytes))))
                 })?
                 .expect("Callback above only errors or returns Some");
+            self.window.glyph_atlas_usage.record(&params, &tile);
 
             let bounds = Bounds {
-                origin: glyph_origin.map(|px| px.floor()) + raster_bounds.origin.map(Into::into),
+                origin: glyph_bounds.origin,
                 size: tile.bounds.size.map(Into::into),
             };
-            let content_mask = self.content_mask().scale(scale_factor);
 
             self.window
                 .next_frame
@@ -2542,6 +3278,8 @@ impl<'a> WindowContext<'a> {
                 .insert_primitive(PolychromeSprite {
                     order: 0,
                     grayscale: false,
+                    opacity: self.window.element_opacity,
+                    pad: 0,
                     bounds,
                     corner_radii: Default::default(),
                     content_mask,
@@ -2569,6 +3307,12 @@ impl<'a> WindowContext<'a> {
 
         let scale_factor = self.scale_factor();
         let bounds = bounds.scale(scale_factor);
+        let content_mask = self.content_mask().scale(scale_factor);
+        // Skip rasterizing SVGs that would be entirely clipped away.
+        if bounds.intersect(&content_mask.bounds).is_empty() {
+            return Ok(());
+        }
+
         // Render the SVG at twice the size to get a higher quality result.
         let params = RenderSvgParams {
             path,
@@ -2589,7 +3333,6 @@ impl<'a> WindowContext<'a> {
         else {
             return Ok(());
         };
-        let content_mask = self.content_mask().scale(scale_factor);
 
         self.window
             .next_frame
@@ -2599,7 +3342,7 @@ impl<'a> WindowContext<'a> {
                 pad: 0,
                 bounds,
                 content_mask,
-                color,
+                color: color.opacity(self.window.element_opacity),
                 tile,
                 transformation,
             });
@@ -2626,6 +3369,12 @@ impl<'a> WindowContext<'a> {
 
         let scale_factor = self.scale_factor();
         let bounds = bounds.scale(scale_factor);
+        let content_mask = self.content_mask().scale(scale_factor);
+        // Skip uploading images that would be entirely clipped away.
+        if bounds.intersect(&content_mask.bounds).is_empty() {
+            return Ok(());
+        }
+
         let params = RenderImageParams {
             image_id: data.id,
             frame_index,
@@ -2641,7 +3390,6 @@ impl<'a> WindowContext<'a> {
                 )))
             })?
             .expect("Callback above only returns Some");
-        let content_mask = self.content_mask().scale(scale_factor);
         let corner_radii = corner_radii.scale(scale_factor);
 
         self.window
@@ -2650,6 +3398,8 @@ impl<'a> WindowContext<'a> {
             .insert_primitive(PolychromeSprite {
                 order: 0,
                 grayscale,
+                opacity: self.window.element_opacity,
+                pad: 0,
                 bounds,
                 content_mask,
                 corner_radii,
@@ -2822,6 +3572,23 @@ impl<'a> WindowContext<'a> {
             .set_key_context(context);
     }
 
+    /// Stops keystrokes dispatched to the current element or its descendants from being matched
+    /// against key bindings, so that its key listeners receive every keystroke, such as when
+    /// recording a shortcut.
+    ///
+    /// This method should only be called as part of the paint phase of element drawing.
+    pub fn capture_keystrokes(&mut self) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        self.window
+            .next_frame
+            .dispatch_tree
+            .set_captures_keystrokes();
+    }
+
     /// Sets the focus handle for the current element. This handle will be used to manage focus state
     /// and keyboard event dispatch for the element.
     ///
@@ -2908,6 +3675,24 @@ impl<'a> WindowContext<'a> {
         )));
     }
 
+    /// Register a listener on the window for the next frame, called when the active drag is
+    /// cancelled. When the next frame is rendered the listener will be cleared.
+    pub(crate) fn on_drag_cancel(&mut self, handler: impl Fn(&mut WindowContext) + 'static) {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+
+        self.window.next_frame.mouse_listeners.push(Some(Box::new(
+            move |event: &dyn Any, phase: DispatchPhase, cx: &mut WindowContext<'_>| {
+                if phase == DispatchPhase::Capture && event.is::<DragCancelEvent>() {
+                    handler(cx)
+                }
+            },
+        )));
+    }
+
     /// Register a key event listener on the window for the next frame. The type of event
     /// is determined by the first parameter of the given listener. When the next frame is rendered
     /// the listener will be cleared.
@@ -3144,6 +3929,17 @@ impl<'a> WindowContext<'a> {
                     PlatformInput::FileDrop(FileDropEvent::Exited)
                 }
             },
+            // Pressing escape cancels the active drag, instead of being dispatched to the
+            // focused element.
+            PlatformInput::KeyDown(key_down)
+                if key_down.keystroke.key == "escape" && self.has_active_drag() =>
+            {
+                self.cancel_drag();
+                return DispatchEventResult {
+                    propagate: false,
+                    default_prevented: self.window.default_prevented,
+                };
+            }
             PlatformInput::KeyDown(_) | PlatformInput::KeyUp(_) => event,
         };
 
@@ -3159,6 +3955,22 @@ impl<'a> WindowContext<'a> {
         }
     }
 
+    /// Cancels the active drag without dropping it, after notifying the elements that called
+    /// [`InteractiveElement::on_drag_cancel`](crate::InteractiveElement::on_drag_cancel) for its
+    /// type.
+    ///
+    /// This is done automatically when escape is pressed or the window is deactivated.
+    pub fn cancel_drag(&mut self) {
+        if !self.has_active_drag() {
+            return;
+        }
+
+        self.app.propagate_event = true;
+        self.dispatch_mouse_event(&DragCancelEvent);
+        self.active_drag = None;
+        self.refresh();
+    }
+
     fn dispatch_mouse_event(&mut self, event: &dyn Any) {
         let hit_test = self.window.rendered_frame.hit_test(self.mouse_position());
         if hit_test != self.window.mouse_hit_test {
@@ -3268,6 +4080,18 @@ impl<'a> WindowContext<'a> {
             return;
         };
 
+        if self
+            .window
+            .rendered_frame
+            .dispatch_tree
+            .captures_keystrokes(&dispatch_path)
+        {
+            self.window.pending_input.take();
+            self.pending_input_changed();
+            self.finish_dispatch_key_event(event, dispatch_path);
+            return;
+        }
+
         let mut currently_pending = self.window.pending_input.take().unwrap_or_default();
         if currently_pending.focus.is_some() && currently_pending.focus != self.window.focus {
             currently_pending = PendingInput::default();
@@ -3597,6 +4421,19 @@ impl<'a> WindowContext<'a> {
         self.window.platform_window.toggle_fullscreen();
     }
 
+    /// Present the platform's file dialog for picking a single file or directory, as allowed by
+    /// the given options.
+    ///
+    /// The returned task resolves to the selected path, or `None` if the dialog was cancelled or
+    /// couldn't be opened.
+    pub fn prompt_for_path(&mut self, options: PathPromptOptions) -> Task<Option<PathBuf>> {
+        let paths = self.app.prompt_for_paths(PathPromptOptions {
+            multiple: false,
+            ..options
+        });
+        self.spawn(|_| async move { paths.await.ok()?.log_err()??.into_iter().next() })
+    }
+
     /// Present a platform dialog.
     /// The provided message will be presented, along with buttons for each answer.
     /// When a button is clicked, the returned Receiver will receive the index of the clicked button.
@@ -3630,6 +4467,45 @@ impl<'a> WindowContext<'a> {
         receiver
     }
 
+    /// Shows a transient notification with the given message in the corner of the window, such as
+    /// to report that something running in the background has failed.
+    ///
+    /// The toast is dismissed once `duration` has passed or its close button is clicked. Toasts
+    /// are stacked from the oldest to the newest, with the newer ones moving up as the older ones
+    /// are dismissed.
+    pub fn push_toast(
+        &mut self,
+        level: PromptLevel,
+        message: impl Into<SharedString>,
+        duration: Duration,
+    ) -> ToastId {
+        let toasts = match self.window.toasts.clone() {
+            Some(toasts) => toasts,
+            None => {
+                let toasts = self.new_view(|_| ToastStack::default());
+                self.window.toasts = Some(toasts.clone());
+                toasts
+            }
+        };
+        let message = message.into();
+        toasts.update(self, |toasts, cx| toasts.push(level, message, duration, cx))
+    }
+
+    /// Dismisses a toast shown with [`push_toast`](Self::push_toast), if it's still shown.
+    pub fn dismiss_toast(&mut self, id: ToastId) {
+        if let Some(toasts) = self.window.toasts.clone() {
+            toasts.update(self, |toasts, cx| toasts.dismiss(id, cx));
+        }
+    }
+
+    /// Returns how many toasts are shown.
+    pub fn toast_count(&self) -> usize {
+        self.window
+            .toasts
+            .as_ref()
+            .map_or(0, |toasts| toasts.read(self).len())
+    }
+
     fn build_custom_prompt(
         &mut self,
         prompt_builder: &PromptBuilder,
@@ -3642,6 +4518,7 @@ impl<'a> WindowContext<'a> {
         let handle = PromptHandle::new(sender);
         let handle = (prompt_builder)(level, message, detail, answers, handle, self);
         self.window.prompt = Some(handle);
+        self.refresh();
         receiver
     }
 
@@ -4097,6 +4974,99 @@ impl<'a, V: 'static> ViewContext<'a, V> {
         )
     }
 
+    /// Observe another model or view for changes to its state, like [`Self::observe`], but invoke
+    /// the callback at most once per `interval`.
+    ///
+    /// A notification arriving more than `interval` after the last call is handled immediately.
+    /// Notifications arriving sooner are coalesced into a single trailing call at the end of the
+    /// interval, so the callback always gets to observe the entity's final state.
+    pub fn observe_throttled<V2, E>(
+        &mut self,
+        entity: &E,
+        interval: Duration,
+        on_notify: impl FnMut(&mut V, E, &mut ViewContext<'_, V>) + 'static,
+    ) -> Subscription
+    where
+        V2: 'static,
+        V: 'static,
+        E: Entity<V2> + 'static,
+    {
+        struct Throttle<F> {
+            on_notify: F,
+            last_notified_at: Option<Instant>,
+            trailing_notify_scheduled: bool,
+            trailing_notify: Option<Task<()>>,
+        }
+
+        let view = self.view().downgrade();
+        let entity_id = entity.entity_id();
+        let entity = Rc::new(entity.downgrade());
+        let window_handle = self.window.handle;
+        let throttle = Rc::new(RefCell::new(Throttle {
+            on_notify,
+            last_notified_at: None,
+            trailing_notify_scheduled: false,
+            trailing_notify: None,
+        }));
+        self.app.new_observer(
+            entity_id,
+            Box::new(move |cx| {
+                window_handle
+                    .update(cx, |_, cx| {
+                        let Some(handle) = E::upgrade_from(&entity) else {
+                            return false;
+                        };
+                        view.update(cx, |this, cx| {
+                            let mut throttle_state = throttle.borrow_mut();
+                            if throttle_state.trailing_notify_scheduled {
+                                return;
+                            }
+
+                            let now = cx.background_executor().now();
+                            let next_notify_at = throttle_state
+                                .last_notified_at
+                                .map(|last_notified_at| last_notified_at + interval);
+                            match next_notify_at {
+                                Some(next_notify_at) if now < next_notify_at => {
+                                    // The task only holds onto the throttle weakly, so that it's
+                                    // cancelled when the subscription is dropped.
+                                    let weak_throttle = Rc::downgrade(&throttle);
+                                    let entity = entity.clone();
+                                    throttle_state.trailing_notify_scheduled = true;
+                                    throttle_state.trailing_notify =
+                                        Some(cx.spawn(|view, mut cx| async move {
+                                            cx.background_executor()
+                                                .timer(next_notify_at - now)
+                                                .await;
+                                            let Some(throttle) = weak_throttle.upgrade() else {
+                                                return;
+                                            };
+                                            let Some(handle) = E::upgrade_from(&entity) else {
+                                                return;
+                                            };
+                                            view.update(&mut cx, |this, cx| {
+                                                let mut throttle = throttle.borrow_mut();
+                                                throttle.trailing_notify_scheduled = false;
+                                                throttle.last_notified_at =
+                                                    Some(cx.background_executor().now());
+                                                (throttle.on_notify)(this, handle, cx);
+                                            })
+                                            .ok();
+                                        }));
+                                }
+                                _ => {
+                                    throttle_state.last_notified_at = Some(now);
+                                    (throttle_state.on_notify)(this, handle, cx);
+                                }
+                            }
+                        })
+                        .is_ok()
+                    })
+                    .unwrap_or(false)
+            }),
+        )
+    }
+
     /// Subscribe to events emitted by another model or view.
     /// The entity to which you're subscribing must implement the [`EventEmitter`] trait.
     /// The callback will be invoked with a reference to the current view, a handle to the emitting entity (either a [`View`] or [`Model`]), the event, and a view context for the current view.
@@ -4139,6 +5109,10 @@ impl<'a, V: 'static> ViewContext<'a, V> {
     ///
     /// The callback receives a handle to the view's window. This handle may be
     /// invalid, if the window was closed before the view was released.
+    ///
+    /// The callback runs once the last handle to the view has been dropped, when
+    /// effects are next flushed rather than during the drop, so it can safely update
+    /// the app. Use it to clean up resources such as watchers or timers.
     pub fn on_release(
         &mut self,
         on_release: impl FnOnce(&mut V, AnyWindowHandle, &mut AppContext) + 'static,
@@ -4188,7 +5162,8 @@ impl<'a, V: 'static> ViewContext<'a, V> {
         self.window_cx.notify(self.view.entity_id());
     }
 
-    /// Register a callback to be invoked when the window is resized.
+    /// Register a callback to be invoked when the window is resized or moved, including when it's
+    /// dragged to a new position. Query the new bounds with [`WindowContext::bounds`].
     pub fn observe_window_bounds(
         &mut self,
         mut callback: impl FnMut(&mut V, &mut ViewContext<V>) + 'static,
@@ -0,0 +1,361 @@
+use collections::{FxHashMap, FxHashSet};
+
+use crate::{AtlasKey, AtlasTextureId, AtlasTile, PlatformAtlas, RenderGlyphParams, Scene, TileId};
+
+/// The default number of bytes glyph tiles may occupy in a window's sprite atlas.
+const DEFAULT_GLYPH_ATLAS_BUDGET: usize = 32 * 1024 * 1024;
+
+/// The number of frames a glyph tile has to go unused before it can be evicted, so that tiles
+/// referenced by frames the GPU may still be drawing aren't overwritten.
+const MIN_IDLE_FRAMES: u64 = 3;
+
+/// Occupancy and eviction statistics for the glyph tiles in a window's sprite atlas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GlyphAtlasMetrics {
+    /// The number of glyph tiles in the atlas.
+    pub tile_count: usize,
+    /// The number of bytes occupied by glyph tiles in the atlas.
+    pub occupied_bytes: usize,
+    /// The number of bytes glyph tiles may occupy before the least recently used ones are evicted.
+    pub budget_bytes: usize,
+    /// The number of glyph tiles that have been evicted from the atlas.
+    pub eviction_count: usize,
+}
+
+struct GlyphTileUsage {
+    tile: (AtlasTextureId, TileId),
+    bytes: usize,
+    last_used_frame: u64,
+}
+
+/// Tracks when each glyph tile in a window's sprite atlas was last painted, evicting the least
+/// recently used tiles once they exceed the atlas budget.
+///
+/// Evicted glyphs are rasterized again the next time they're painted.
+pub(crate) struct GlyphAtlasUsage {
+    tiles: FxHashMap<RenderGlyphParams, GlyphTileUsage>,
+    occupied_bytes: usize,
+    budget_bytes: usize,
+    eviction_count: usize,
+    frame: u64,
+    /// The window's scale factor after it last changed, until the tiles rasterized at earlier
+    /// scale factors have all been evicted.
+    new_scale_factor: Option<f32>,
+}
+
+impl GlyphAtlasUsage {
+    pub(crate) fn new() -> Self {
+        Self {
+            tiles: FxHashMap::default(),
+            occupied_bytes: 0,
+            budget_bytes: DEFAULT_GLYPH_ATLAS_BUDGET,
+            eviction_count: 0,
+            frame: 0,
+            new_scale_factor: None,
+        }
+    }
+
+    pub(crate) fn set_budget(&mut self, budget_bytes: usize) {
+        self.budget_bytes = budget_bytes;
+    }
+
+    pub(crate) fn metrics(&self) -> GlyphAtlasMetrics {
+        GlyphAtlasMetrics {
+            tile_count: self.tiles.len(),
+            occupied_bytes: self.occupied_bytes,
+            budget_bytes: self.budget_bytes,
+            eviction_count: self.eviction_count,
+        }
+    }
+
+    /// Evicts the glyphs rasterized at scale factors other than `scale_factor` once they've gone
+    /// unused for a few frames, whatever the budget, as they won't be painted again. This happens
+    /// when the window moves to a display with a different density, for example.
+    pub(crate) fn scale_factor_changed(&mut self, scale_factor: f32) {
+        self.new_scale_factor = Some(scale_factor);
+    }
+
+    /// Records that the glyph rasterized with the given params was painted using `tile`.
+    pub(crate) fn record(&mut self, params: &RenderGlyphParams, tile: &AtlasTile) {
+        if let Some(usage) = self.tiles.get_mut(params) {
+            usage.last_used_frame = self.frame;
+            return;
+        }
+
+        let bytes_per_pixel = if params.is_emoji { 4 } else { 1 };
+        let bytes = (tile.bounds.size.width.0.max(0) as usize)
+            * (tile.bounds.size.height.0.max(0) as usize)
+            * bytes_per_pixel;
+        self.occupied_bytes += bytes;
+        self.tiles.insert(
+            params.clone(),
+            GlyphTileUsage {
+                tile: (tile.texture_id, tile.tile_id),
+                bytes,
+                last_used_frame: self.frame,
+            },
+        );
+    }
+
+    /// Stops tracking the glyphs that don't satisfy the predicate, after they've been removed from
+    /// the atlas.
+    pub(crate) fn retain(&mut self, mut predicate: impl FnMut(&RenderGlyphParams) -> bool) {
+        let occupied_bytes = &mut self.occupied_bytes;
+        self.tiles.retain(|params, usage| {
+            let retain = predicate(params);
+            if !retain {
+                *occupied_bytes -= usage.bytes;
+            }
+            retain
+        });
+    }
+
+    /// Finishes the frame drawn into `scene`, evicting the least recently used glyph tiles from
+    /// `atlas` while they exceed the budget, along with the idle tiles rasterized at a previous
+    /// scale factor.
+    ///
+    /// Tiles referenced by `scene` are never evicted, since its primitives can be reused by the
+    /// next frame without being painted again. That includes the sprites culled from the scene
+    /// for being occluded, which are still replayed.
+    pub(crate) fn finish_frame(&mut self, scene: &Scene, atlas: &dyn PlatformAtlas) {
+        self.frame += 1;
+        if self.occupied_bytes <= self.budget_bytes && self.new_scale_factor.is_none() {
+            return;
+        }
+
+        let tiles_in_use = scene
+            .painted_sprite_tiles()
+            .map(|tile| (tile.texture_id, tile.tile_id))
+            .collect::<FxHashSet<_>>();
+        let is_idle = |usage: &GlyphTileUsage| {
+            usage.last_used_frame + MIN_IDLE_FRAMES <= self.frame
+                && !tiles_in_use.contains(&usage.tile)
+        };
+
+        let mut occupied_bytes = self.occupied_bytes;
+        let mut evicted = FxHashSet::default();
+        if let Some(scale_factor) = self.new_scale_factor {
+            let mut stale_tiles_remain = false;
+            for (params, usage) in &self.tiles {
+                if params.scale_factor == scale_factor {
+                    continue;
+                }
+                if is_idle(usage) {
+                    occupied_bytes -= usage.bytes;
+                    evicted.insert(params.clone());
+                } else {
+                    stale_tiles_remain = true;
+                }
+            }
+            if !stale_tiles_remain {
+                self.new_scale_factor = None;
+            }
+        }
+
+        if occupied_bytes > self.budget_bytes {
+            let mut candidates = self
+                .tiles
+                .iter()
+                .filter(|(params, usage)| is_idle(usage) && !evicted.contains(*params))
+                .map(|(params, usage)| (usage.last_used_frame, usage.bytes, params.clone()))
+                .collect::<Vec<_>>();
+            candidates.sort_unstable_by_key(|(last_used_frame, _, _)| *last_used_frame);
+
+            for (_, bytes, params) in candidates {
+                if occupied_bytes <= self.budget_bytes {
+                    break;
+                }
+                occupied_bytes -= bytes;
+                evicted.insert(params);
+            }
+        }
+        if evicted.is_empty() {
+            return;
+        }
+
+        atlas.retain(&mut |key| match key {
+            AtlasKey::Glyph(params) => !evicted.contains(params),
+            AtlasKey::Svg(_) | AtlasKey::Image(_) => true,
+        });
+        self.eviction_count += evicted.len();
+        self.retain(|params| !evicted.contains(params));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+    use crate::{
+        point, px, red, size, Bounds, ContentMask, DevicePixels, FontId, GlyphId, Hsla,
+        MonochromeSprite, OffscreenAtlas, Point, Quad, ScaledPixels, TextAntialiasing,
+        TransformationMatrix,
+    };
+
+    fn glyph(glyph_id: u32) -> RenderGlyphParams {
+        RenderGlyphParams {
+            font_id: FontId(0),
+            glyph_id: GlyphId(glyph_id),
+            font_size: px(16.),
+            subpixel_variant: Point::default(),
+            scale_factor: 2.,
+            is_emoji: false,
+            antialiasing: TextAntialiasing::Grayscale,
+        }
+    }
+
+    /// Paints the glyph, returning whether it had to be rasterized.
+    fn paint(
+        usage: &mut GlyphAtlasUsage,
+        atlas: &OffscreenAtlas,
+        params: &RenderGlyphParams,
+    ) -> bool {
+        let mut rasterized = false;
+        let tile = atlas
+            .get_or_insert_with(&params.clone().into(), &mut || {
+                rasterized = true;
+                Ok(Some((
+                    size(DevicePixels(10), DevicePixels(10)),
+                    Cow::Owned(vec![0; 100]),
+                )))
+            })
+            .unwrap()
+            .unwrap();
+        usage.record(params, &tile);
+        rasterized
+    }
+
+    #[test]
+    fn test_least_recently_used_glyphs_are_evicted() {
+        let atlas = OffscreenAtlas::new();
+        let scene = Scene::default();
+        let mut usage = GlyphAtlasUsage::new();
+        usage.set_budget(200);
+
+        // Glyphs used recently aren't evicted, even when they exceed the budget.
+        assert!(paint(&mut usage, &atlas, &glyph(1)));
+        assert!(paint(&mut usage, &atlas, &glyph(2)));
+        assert!(paint(&mut usage, &atlas, &glyph(3)));
+        usage.finish_frame(&scene, &atlas);
+        assert_eq!(usage.metrics().occupied_bytes, 300);
+        assert_eq!(usage.metrics().eviction_count, 0);
+
+        // Once it has gone unused for a few frames, the least recently used glyph is evicted.
+        for _ in 0..2 {
+            assert!(!paint(&mut usage, &atlas, &glyph(2)));
+            assert!(!paint(&mut usage, &atlas, &glyph(3)));
+            usage.finish_frame(&scene, &atlas);
+        }
+        assert_eq!(
+            usage.metrics(),
+            GlyphAtlasMetrics {
+                tile_count: 2,
+                occupied_bytes: 200,
+                budget_bytes: 200,
+                eviction_count: 1,
+            }
+        );
+
+        // Painting an evicted glyph rasterizes it again.
+        assert!(paint(&mut usage, &atlas, &glyph(1)));
+        assert!(!paint(&mut usage, &atlas, &glyph(2)));
+        assert_eq!(usage.metrics().tile_count, 3);
+    }
+
+    #[test]
+    fn test_glyphs_of_culled_sprites_are_kept_for_replay() {
+        let atlas = OffscreenAtlas::new();
+        let mut usage = GlyphAtlasUsage::new();
+        let params = glyph(1);
+        assert!(paint(&mut usage, &atlas, &params));
+        let cached_tile = |atlas: &OffscreenAtlas| {
+            atlas
+                .get_or_insert_with(&params.clone().into(), &mut || Ok(None))
+                .unwrap()
+        };
+        let tile = cached_tile(&atlas).unwrap();
+
+        let bounds = Bounds::new(
+            point(ScaledPixels(0.), ScaledPixels(0.)),
+            size(ScaledPixels(10.), ScaledPixels(10.)),
+        );
+        let mut scene = Scene::default();
+        scene.insert_primitive(MonochromeSprite {
+            order: 0,
+            pad: 0,
+            bounds,
+            content_mask: ContentMask { bounds },
+            color: Hsla::default(),
+            tile: tile.clone(),
+            transformation: TransformationMatrix::unit(),
+        });
+        scene.insert_primitive(Quad {
+            bounds,
+            content_mask: ContentMask { bounds },
+            background: red(),
+            ..Default::default()
+        });
+        scene.finish();
+        assert_eq!(scene.cull_occluded(), 1);
+        assert!(scene.monochrome_sprites.is_empty());
+
+        // The glyph isn't painted again while the frame is reused, as a cached view would.
+        usage.set_budget(0);
+        for _ in 0..MIN_IDLE_FRAMES * 2 {
+            usage.finish_frame(&scene, &atlas);
+        }
+        assert_eq!(usage.metrics().eviction_count, 0);
+        assert_eq!(cached_tile(&atlas), Some(tile.clone()));
+
+        // Once the occluder is gone, the replayed sprite still draws the glyph's tile.
+        let mut next_scene = Scene::default();
+        next_scene.replay(0..1, &scene);
+        next_scene.finish();
+        assert_eq!(next_scene.monochrome_sprites[0].tile, tile);
+    }
+
+    #[test]
+    fn test_glyphs_at_a_previous_scale_factor_are_evicted_once_idle() {
+        let atlas = OffscreenAtlas::new();
+        let scene = Scene::default();
+        let mut usage = GlyphAtlasUsage::new();
+        let at_scale_factor = |scale_factor: f32| RenderGlyphParams {
+            scale_factor,
+            ..glyph(1)
+        };
+
+        assert!(paint(&mut usage, &atlas, &at_scale_factor(2.)));
+        usage.finish_frame(&scene, &atlas);
+
+        // The previous frames may still be drawing the old glyph, so it isn't evicted right away,
+        // even though the atlas is well within its budget.
+        usage.scale_factor_changed(1.);
+        assert!(paint(&mut usage, &atlas, &at_scale_factor(1.)));
+        usage.finish_frame(&scene, &atlas);
+        assert_eq!(usage.metrics().tile_count, 2);
+
+        assert!(!paint(&mut usage, &atlas, &at_scale_factor(1.)));
+        usage.finish_frame(&scene, &atlas);
+        assert_eq!(usage.metrics().tile_count, 1);
+        assert_eq!(usage.metrics().eviction_count, 1);
+        assert!(paint(&mut usage, &atlas, &at_scale_factor(2.)));
+    }
+
+    #[test]
+    fn test_antialiasing_modes_are_rasterized_separately() {
+        let atlas = OffscreenAtlas::new();
+        let mut usage = GlyphAtlasUsage::new();
+        let grayscale = glyph(1);
+        let smoothed = RenderGlyphParams {
+            antialiasing: TextAntialiasing::FontSmoothing,
+            ..glyph(1)
+        };
+
+        assert!(paint(&mut usage, &atlas, &grayscale));
+        assert!(paint(&mut usage, &atlas, &smoothed));
+        assert!(!paint(&mut usage, &atlas, &grayscale));
+        assert!(!paint(&mut usage, &atlas, &smoothed));
+        assert_eq!(usage.metrics().tile_count, 2);
+    }
+}
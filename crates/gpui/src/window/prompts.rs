@@ -4,8 +4,8 @@ use futures::channel::oneshot;
 
 use crate::{
     div, opaque_grey, white, AnyView, EventEmitter, FocusHandle, FocusableView, InteractiveElement,
-    IntoElement, ParentElement, PromptLevel, Render, StatefulInteractiveElement, Styled, View,
-    ViewContext, VisualContext, WindowContext,
+    IntoElement, KeyDownEvent, ParentElement, PromptLevel, Render, StatefulInteractiveElement,
+    Styled, View, ViewContext, VisualContext, WindowContext,
 };
 
 /// The event emitted when a prompt's option is selected.
@@ -40,6 +40,7 @@ impl PromptHandle {
             if let Some(sender) = sender.take() {
                 sender.send(e.0).ok();
                 cx.window.prompt.take();
+                cx.refresh();
                 if let Some(previous_focus) = &previous_focus {
                     cx.focus(&previous_focus);
                 }
@@ -84,6 +85,10 @@ pub fn fallback_prompt_renderer(
 }
 
 /// The default GPUI fallback for rendering prompts, when the platform doesn't support it.
+///
+/// The prompt is shown over the rest of the window, which doesn't receive any mouse input until
+/// it's answered. Pressing escape chooses the "Cancel" action, or the last action if there's no
+/// action with that label.
 pub struct FallbackPromptRenderer {
     _level: PromptLevel,
     message: String,
@@ -92,11 +97,27 @@ pub struct FallbackPromptRenderer {
     focus: FocusHandle,
 }
 
+impl FallbackPromptRenderer {
+    fn cancel_index(&self) -> Option<usize> {
+        self.actions
+            .iter()
+            .position(|action| action == "Cancel")
+            .or_else(|| self.actions.len().checked_sub(1))
+    }
+}
+
 impl Render for FallbackPromptRenderer {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let prompt = div()
             .cursor_default()
             .track_focus(&self.focus)
+            .on_key_down(cx.listener(|this, event: &KeyDownEvent, cx| {
+                if event.keystroke.key == "escape" {
+                    if let Some(ix) = this.cancel_index() {
+                        cx.emit(PromptResponse(ix));
+                    }
+                }
+            }))
             .w_72()
             .bg(white())
             .rounded_lg()
@@ -140,6 +161,7 @@ impl Render for FallbackPromptRenderer {
 
         div()
             .size_full()
+            .occlude()
             .child(
                 div()
                     .size_full()
@@ -220,3 +242,60 @@ impl Deref for PromptBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use crate::{
+        self as gpui, div, fallback_prompt_renderer, point, px, size, InteractiveElement,
+        IntoElement, Modifiers, MouseButton, PromptLevel, Render, Styled, TestAppContext,
+        ViewContext,
+    };
+
+    struct ClickCountingView {
+        clicks: Rc<Cell<usize>>,
+    }
+
+    impl Render for ClickCountingView {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            let clicks = self.clicks.clone();
+            div()
+                .size_full()
+                .on_mouse_down(MouseButton::Left, move |_, _| clicks.set(clicks.get() + 1))
+        }
+    }
+
+    #[gpui::test]
+    fn test_fallback_prompt_blocks_input_and_cancels_on_escape(cx: &mut TestAppContext) {
+        cx.update(|cx| cx.set_prompt_builder(fallback_prompt_renderer));
+        let clicks = Rc::new(Cell::new(0));
+        let (_, cx) = cx.add_window_view(|_| ClickCountingView {
+            clicks: clicks.clone(),
+        });
+        cx.simulate_resize(size(px(400.), px(400.)));
+        cx.run_until_parked();
+
+        let mut answer = cx.update(|cx| {
+            cx.prompt(
+                PromptLevel::Warning,
+                "Revert all settings?",
+                None,
+                &["Revert", "Cancel"],
+            )
+        });
+        cx.run_until_parked();
+
+        // The view behind the prompt doesn't receive clicks while it's shown.
+        cx.simulate_click(point(px(5.), px(5.)), Modifiers::none());
+        assert_eq!(clicks.get(), 0);
+        assert_eq!(answer.try_recv(), Ok(None));
+
+        cx.simulate_keystrokes("escape");
+        assert_eq!(answer.try_recv(), Ok(Some(1)));
+
+        // Once it's answered, the prompt is dismissed.
+        cx.simulate_click(point(px(5.), px(5.)), Modifiers::none());
+        assert_eq!(clicks.get(), 1);
+    }
+}
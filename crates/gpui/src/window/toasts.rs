@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use crate::{
+    black, div, opaque_grey, red, white, yellow, Hsla, InteractiveElement, IntoElement,
+    ParentElement, PromptLevel, Render, SharedString, StatefulInteractiveElement, Styled, Task,
+    ViewContext,
+};
+
+/// Identifies a toast shown with [`WindowContext::push_toast`](crate::WindowContext::push_toast),
+/// so that it can be dismissed before it expires.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ToastId(usize);
+
+struct Toast {
+    id: ToastId,
+    level: PromptLevel,
+    message: SharedString,
+    /// Dismisses the toast once its duration has passed. Dismissing it sooner cancels the timer.
+    _expiry: Task<()>,
+}
+
+/// The toasts shown in a window, stacked in its top right corner from the oldest to the newest,
+/// so that the newer ones move up as the older ones expire.
+#[derive(Default)]
+pub(crate) struct ToastStack {
+    toasts: Vec<Toast>,
+    next_id: usize,
+}
+
+impl ToastStack {
+    pub(crate) fn push(
+        &mut self,
+        level: PromptLevel,
+        message: SharedString,
+        duration: Duration,
+        cx: &mut ViewContext<Self>,
+    ) -> ToastId {
+        let id = ToastId(self.next_id);
+        self.next_id += 1;
+        let expiry = cx.spawn(|this, mut cx| async move {
+            cx.background_executor().timer(duration).await;
+            this.update(&mut cx, |this, cx| this.dismiss(id, cx)).ok();
+        });
+        self.toasts.push(Toast {
+            id,
+            level,
+            message,
+            _expiry: expiry,
+        });
+        cx.notify();
+        id
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    pub(crate) fn dismiss(&mut self, id: ToastId, cx: &mut ViewContext<Self>) {
+        let len = self.toasts.len();
+        self.toasts.retain(|toast| toast.id != id);
+        if self.toasts.len() != len {
+            cx.notify();
+        }
+    }
+}
+
+fn level_color(level: PromptLevel) -> Hsla {
+    match level {
+        PromptLevel::Info => opaque_grey(0.5, 1.),
+        PromptLevel::Warning => yellow(),
+        PromptLevel::Critical => red(),
+    }
+}
+
+impl Render for ToastStack {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .flex()
+            .flex_col()
+            .items_end()
+            .gap_2()
+            .p_2()
+            .children(self.toasts.iter().map(|toast| {
+                let id = toast.id;
+                let color = level_color(toast.level);
+
+                div()
+                    .id(("toast", id.0))
+                    .occlude()
+                    .cursor_default()
+                    .w_72()
+                    .bg(white())
+                    .text_color(black())
+                    .border_1()
+                    .border_color(color)
+                    .rounded_md()
+                    .child(
+                        div()
+                            .flex()
+                            .flex_row()
+                            .items_start()
+                            .gap_2()
+                            .p_2()
+                            .child(div().flex_1().text_sm().child(toast.message.clone()))
+                            .child(
+                                div()
+                                    .id("toast-close")
+                                    .cursor_pointer()
+                                    .px_1()
+                                    .rounded_sm()
+                                    .hover(|style| style.bg(opaque_grey(0.5, 0.2)))
+                                    .text_sm()
+                                    .child("×")
+                                    .on_click(cx.listener(move |this, _, cx| this.dismiss(id, cx))),
+                            ),
+                    )
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        self as gpui, div, px, size, IntoElement, Modifiers, PromptLevel, Render, Styled,
+        TestAppContext, ViewContext, VisualTestContext,
+    };
+
+    struct EmptyView;
+
+    impl Render for EmptyView {
+        fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+            div().size_full()
+        }
+    }
+
+    #[gpui::test]
+    fn test_toasts_expire_and_can_be_dismissed(cx: &mut TestAppContext) {
+        let (_, cx) = cx.add_window_view(|_| EmptyView);
+        cx.simulate_resize(size(px(400.), px(400.)));
+
+        let first = cx.update(|cx| {
+            cx.push_toast(
+                PromptLevel::Info,
+                "Settings imported.",
+                Duration::from_secs(5),
+            )
+        });
+        let second = cx.update(|cx| {
+            cx.push_toast(
+                PromptLevel::Critical,
+                "Couldn't save settings.",
+                Duration::from_secs(60),
+            )
+        });
+        assert_ne!(first, second);
+        cx.run_until_parked();
+        let second_bounds = |cx: &mut VisualTestContext| {
+            cx.update(|cx| cx.measured_bounds(("toast", second.0)))
+                .expect("the toast is painted")
+        };
+        let top_before_expiry = second_bounds(cx).top();
+
+        cx.executor().advance_clock(Duration::from_secs(4));
+        cx.run_until_parked();
+        assert_eq!(cx.update(|cx| cx.toast_count()), 2);
+
+        // Once the older toast expires, the newer one moves up into its place.
+        cx.executor().advance_clock(Duration::from_secs(1));
+        cx.run_until_parked();
+        assert_eq!(cx.update(|cx| cx.toast_count()), 1);
+        assert!(second_bounds(cx).top() < top_before_expiry);
+
+        cx.update(|cx| cx.dismiss_toast(second));
+        cx.run_until_parked();
+        assert_eq!(cx.update(|cx| cx.toast_count()), 0);
+
+        // Clicking a toast's close button dismisses it.
+        cx.update(|cx| {
+            cx.push_toast(
+                PromptLevel::Warning,
+                "Invalid import.",
+                Duration::from_secs(60),
+            )
+        });
+        cx.run_until_parked();
+        let close_button = cx
+            .update(|cx| cx.measured_bounds("toast-close"))
+            .expect("the close button is painted");
+        cx.simulate_click(close_button.center(), Modifiers::none());
+        cx.run_until_parked();
+        assert_eq!(cx.update(|cx| cx.toast_count()), 0);
+    }
+}
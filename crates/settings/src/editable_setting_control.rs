@@ -1,12 +1,13 @@
 use fs::Fs;
+use fuzzy::StringMatch;
 use gpui::{AppContext, RenderOnce, SharedString};
 
-use crate::{update_settings_file, Settings};
+use crate::{update_settings_file, Settings, SettingsHistory};
 
 /// A UI control that can be used to edit a setting.
 pub trait EditableSettingControl: RenderOnce {
     /// The type of the setting value.
-    type Value: Send;
+    type Value: Send + Clone + PartialEq;
 
     /// The settings type to which this setting belongs.
     type Settings: Settings;
@@ -14,9 +15,53 @@ pub trait EditableSettingControl: RenderOnce {
     /// Returns the name of this setting.
     fn name(&self) -> SharedString;
 
+    /// Fuzzily matches the given search query against the name of this setting, returning
+    /// `None` if the name doesn't contain every character of the query in order.
+    ///
+    /// The query is matched case-insensitively. An empty query matches every setting, with a score
+    /// of zero.
+    fn query_match(&self, query: &str) -> Option<StringMatch> {
+        fuzzy::match_string(query, &self.name(), false)
+    }
+
+    /// Returns whether this setting matches the given search query.
+    fn matches_query(&self, query: &str) -> bool {
+        self.query_match(query).is_some()
+    }
+
+    /// Returns the byte positions of the characters in the name of this setting that match the
+    /// given search query, for highlighting them.
+    fn query_highlights(&self, query: &str) -> Vec<usize> {
+        self.query_match(query)
+            .map(|mat| mat.positions)
+            .unwrap_or_default()
+    }
+
     /// Reads the setting value from the settings.
     fn read(cx: &AppContext) -> Self::Value;
 
+    /// Returns the value of this setting in the default settings.
+    ///
+    /// Controls that return `None` don't offer to reset the setting to its default value.
+    fn default_value(_cx: &AppContext) -> Option<Self::Value> {
+        None
+    }
+
+    /// Returns the default value of this setting if the current value differs from it, for
+    /// offering to reset the setting.
+    fn reset_value(cx: &AppContext) -> Option<Self::Value> {
+        let default_value = Self::default_value(cx)?;
+        (Self::read(cx) != default_value).then_some(default_value)
+    }
+
+    /// Checks whether the given value can be written to the settings, returning an error
+    /// describing the problem if not.
+    ///
+    /// Controls should show the error next to themselves, as invalid values are never written.
+    fn validate(_value: &Self::Value, _cx: &AppContext) -> Result<(), SharedString> {
+        Ok(())
+    }
+
     /// Applies the given setting file to the settings file contents.
     ///
     /// This will be called when writing the setting value back to the settings file.
@@ -26,8 +71,22 @@ pub trait EditableSettingControl: RenderOnce {
         cx: &AppContext,
     );
 
-    /// Writes the given setting value to the settings files.
-    fn write(value: Self::Value, cx: &AppContext) {
+    /// Writes the given setting value to the settings files, recording the change in the
+    /// [`SettingsHistory`] so that it can be undone.
+    ///
+    /// Values that fail [`validate`](Self::validate) are ignored.
+    fn write(value: Self::Value, cx: &mut AppContext) {
+        if Self::validate(&value, cx).is_err() {
+            return;
+        }
+
+        SettingsHistory::record::<Self>(Self::read(cx), value.clone(), cx);
+        Self::persist(value, cx);
+    }
+
+    /// Writes the given setting value to the settings files without recording it in the
+    /// [`SettingsHistory`].
+    fn persist(value: Self::Value, cx: &AppContext) {
         let fs = <dyn Fs>::global(cx);
 
         update_settings_file::<Self::Settings>(fs, cx, move |settings, cx| {
@@ -35,3 +94,148 @@ pub trait EditableSettingControl: RenderOnce {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+
+    use gpui::{Global, IntoElement, WindowContext};
+    use schemars::JsonSchema;
+    use serde_derive::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::SettingsSources;
+
+    #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+    struct TabSettings {
+        tab_size: Option<u32>,
+    }
+
+    impl Settings for TabSettings {
+        const KEY: Option<&'static str> = None;
+
+        type FileContent = Self;
+
+        fn load(
+            sources: SettingsSources<Self::FileContent>,
+            _: &mut AppContext,
+        ) -> anyhow::Result<Self> {
+            sources.json_merge()
+        }
+    }
+
+    #[derive(IntoElement)]
+    struct TabSizeControl;
+
+    impl EditableSettingControl for TabSizeControl {
+        type Value = String;
+        type Settings = TabSettings;
+
+        fn name(&self) -> SharedString {
+            "Tab Size".into()
+        }
+
+        fn read(_cx: &AppContext) -> Self::Value {
+            "4".into()
+        }
+
+        fn validate(value: &Self::Value, _cx: &AppContext) -> Result<(), SharedString> {
+            value
+                .parse::<u32>()
+                .map(|_| ())
+                .map_err(|_| "Tab size must be a number.".into())
+        }
+
+        fn apply(settings: &mut TabSettings, value: Self::Value, _cx: &AppContext) {
+            settings.tab_size = value.parse().ok();
+        }
+    }
+
+    impl RenderOnce for TabSizeControl {
+        fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+            gpui::Empty
+        }
+    }
+
+    struct CurrentTabSize(String);
+
+    impl Global for CurrentTabSize {}
+
+    #[derive(IntoElement)]
+    struct TrackedTabSizeControl;
+
+    impl EditableSettingControl for TrackedTabSizeControl {
+        type Value = String;
+        type Settings = TabSettings;
+
+        fn name(&self) -> SharedString {
+            "Tab Size".into()
+        }
+
+        fn read(cx: &AppContext) -> Self::Value {
+            cx.global::<CurrentTabSize>().0.clone()
+        }
+
+        fn apply(settings: &mut TabSettings, value: Self::Value, _cx: &AppContext) {
+            settings.tab_size = value.parse().ok();
+        }
+    }
+
+    impl RenderOnce for TrackedTabSizeControl {
+        fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+            gpui::Empty
+        }
+    }
+
+    #[test]
+    fn test_settings_are_matched_by_subsequence() {
+        assert!(TabSizeControl.matches_query(""));
+        assert!(TabSizeControl.matches_query("size"));
+        assert!(TabSizeControl.matches_query("tbsz"));
+        assert!(!TabSizeControl.matches_query("zt"));
+        assert_eq!(
+            TabSizeControl.query_highlights("tsize"),
+            vec![0, 4, 5, 6, 7]
+        );
+
+        // Matching the start of a word scores higher than matching in its middle.
+        let word_start = TabSizeControl.query_match("si").unwrap().score;
+        let word_middle = TabSizeControl.query_match("ab").unwrap().score;
+        assert!(word_start > word_middle);
+    }
+
+    #[gpui::test]
+    fn test_invalid_values_are_not_written(cx: &mut AppContext) {
+        assert_eq!(
+            TabSizeControl::validate(&"four".to_string(), cx),
+            Err("Tab size must be a number.".into())
+        );
+        assert_eq!(TabSizeControl::validate(&"8".to_string(), cx), Ok(()));
+
+        TabSizeControl::write("four".to_string(), cx);
+        assert!(!SettingsHistory::can_undo(cx));
+    }
+
+    #[gpui::test]
+    fn test_changed_settings_are_compared_to_their_first_value(cx: &mut AppContext) {
+        let set_tab_size = |previous: &str, value: &str, cx: &mut AppContext| {
+            SettingsHistory::record::<TrackedTabSizeControl>(previous.into(), value.into(), cx);
+            cx.set_global(CurrentTabSize(value.into()));
+        };
+        cx.set_global(CurrentTabSize("4".into()));
+        assert!(SettingsHistory::changed_settings(cx).is_empty());
+
+        set_tab_size("4", "8", cx);
+        assert_eq!(
+            SettingsHistory::changed_settings(cx),
+            [TypeId::of::<TrackedTabSizeControl>()]
+        );
+
+        set_tab_size("8", "2", cx);
+        assert_eq!(SettingsHistory::changed_settings(cx).len(), 1);
+
+        // Changing the setting back to the value it had before it was edited isn't a change.
+        set_tab_size("2", "4", cx);
+        assert!(SettingsHistory::changed_settings(cx).is_empty());
+    }
+}
@@ -1,7 +1,9 @@
 mod editable_setting_control;
 mod json_schema;
 mod keymap_file;
+mod settings_document;
 mod settings_file;
+mod settings_history;
 mod settings_store;
 
 use gpui::AppContext;
@@ -12,8 +14,12 @@ use util::asset_str;
 pub use editable_setting_control::*;
 pub use json_schema::*;
 pub use keymap_file::KeymapFile;
+pub use settings_document::SettingsDocument;
 pub use settings_file::*;
-pub use settings_store::{Settings, SettingsLocation, SettingsSources, SettingsStore};
+pub use settings_history::SettingsHistory;
+pub use settings_store::{
+    Settings, SettingsFileUpdateError, SettingsLocation, SettingsSources, SettingsStore,
+};
 
 #[derive(RustEmbed)]
 #[folder = "../../assets"]
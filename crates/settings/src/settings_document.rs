@@ -0,0 +1,218 @@
+use std::rc::Rc;
+
+use gpui::{AppContext, SharedString};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use crate::EditableSettingControl;
+
+struct SettingsDocumentEntry {
+    id: SharedString,
+    read: Rc<dyn Fn(&AppContext) -> Value>,
+    apply: Rc<dyn Fn(&Value, &mut AppContext) -> Result<(), serde_json::Error>>,
+}
+
+/// Maps the settings edited by a set of [`EditableSettingControl`]s to a JSON document keyed by
+/// their IDs, for saving and loading them.
+#[derive(Default)]
+pub struct SettingsDocument {
+    entries: Vec<SettingsDocumentEntry>,
+}
+
+impl SettingsDocument {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the setting edited by `C` to the document under the given ID.
+    ///
+    /// Settings are written to the document in the order they're added.
+    pub fn control<C>(mut self, id: impl Into<SharedString>) -> Self
+    where
+        C: EditableSettingControl,
+        C::Value: Serialize + DeserializeOwned + 'static,
+    {
+        self.entries.push(SettingsDocumentEntry {
+            id: id.into(),
+            read: Rc::new(|cx| serde_json::to_value(C::read(cx)).unwrap_or(Value::Null)),
+            apply: Rc::new(|value, cx| {
+                let value = serde_json::from_value::<C::Value>(value.clone())?;
+                if C::read(cx) != value {
+                    C::write(value, cx);
+                }
+                Ok(())
+            }),
+        });
+        self
+    }
+
+    /// Returns a JSON object mapping the ID of each setting to its current value.
+    pub fn to_json(&self, cx: &AppContext) -> Value {
+        let mut document = Map::new();
+        for entry in &self.entries {
+            document.insert(entry.id.to_string(), (entry.read)(cx));
+        }
+        Value::Object(document)
+    }
+
+    /// Writes the values in the given JSON object to the settings with the same IDs.
+    ///
+    /// Keys that aren't the ID of a setting are ignored, and values of the wrong type are logged
+    /// and skipped.
+    pub fn apply_json(&self, document: &Value, cx: &mut AppContext) {
+        let Some(document) = document.as_object() else {
+            log::warn!("settings document is not a JSON object");
+            return;
+        };
+
+        for entry in &self.entries {
+            let Some(value) = document.get(entry.id.as_ref()) else {
+                continue;
+            };
+            if let Err(error) = (entry.apply)(value, cx) {
+                log::warn!("invalid value for setting {:?}: {error}", entry.id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use gpui::{IntoElement, RenderOnce, WindowContext};
+    use schemars::JsonSchema;
+    use serde_derive::{Deserialize, Serialize};
+    use serde_json::json;
+
+    use super::*;
+    use crate::{Settings, SettingsSources};
+
+    #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+    struct GutterSettings {
+        line_numbers: Option<bool>,
+        tab_size: Option<u32>,
+    }
+
+    impl Settings for GutterSettings {
+        const KEY: Option<&'static str> = None;
+
+        type FileContent = Self;
+
+        fn load(
+            sources: SettingsSources<Self::FileContent>,
+            _: &mut AppContext,
+        ) -> anyhow::Result<Self> {
+            sources.json_merge()
+        }
+    }
+
+    thread_local! {
+        static CURRENT: RefCell<GutterSettings> = RefCell::new(GutterSettings {
+            line_numbers: Some(true),
+            tab_size: Some(4),
+        });
+    }
+
+    #[derive(IntoElement)]
+    struct LineNumbersControl;
+
+    impl EditableSettingControl for LineNumbersControl {
+        type Value = bool;
+        type Settings = GutterSettings;
+
+        fn name(&self) -> SharedString {
+            "Line Numbers".into()
+        }
+
+        fn read(_cx: &AppContext) -> Self::Value {
+            CURRENT.with(|current| current.borrow().line_numbers.unwrap_or_default())
+        }
+
+        fn apply(settings: &mut GutterSettings, value: Self::Value, _cx: &AppContext) {
+            settings.line_numbers = Some(value);
+        }
+
+        fn persist(value: Self::Value, cx: &AppContext) {
+            CURRENT.with(|current| Self::apply(&mut current.borrow_mut(), value, cx));
+        }
+    }
+
+    impl RenderOnce for LineNumbersControl {
+        fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+            gpui::Empty
+        }
+    }
+
+    #[derive(IntoElement)]
+    struct TabSizeControl;
+
+    impl EditableSettingControl for TabSizeControl {
+        type Value = u32;
+        type Settings = GutterSettings;
+
+        fn name(&self) -> SharedString {
+            "Tab Size".into()
+        }
+
+        fn read(_cx: &AppContext) -> Self::Value {
+            CURRENT.with(|current| current.borrow().tab_size.unwrap_or_default())
+        }
+
+        fn apply(settings: &mut GutterSettings, value: Self::Value, _cx: &AppContext) {
+            settings.tab_size = Some(value);
+        }
+
+        fn persist(value: Self::Value, cx: &AppContext) {
+            CURRENT.with(|current| Self::apply(&mut current.borrow_mut(), value, cx));
+        }
+    }
+
+    impl RenderOnce for TabSizeControl {
+        fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+            gpui::Empty
+        }
+    }
+
+    fn document() -> SettingsDocument {
+        SettingsDocument::new()
+            .control::<TabSizeControl>("tab-size")
+            .control::<LineNumbersControl>("line-numbers")
+    }
+
+    #[gpui::test]
+    fn test_settings_document_round_trip(cx: &mut AppContext) {
+        let document = document();
+        let saved = document.to_json(cx);
+        assert_eq!(saved, json!({ "tab-size": 4, "line-numbers": true }));
+        // Keys are in the order the settings were added, so saved documents diff cleanly.
+        assert_eq!(
+            saved.as_object().unwrap().keys().collect::<Vec<_>>(),
+            ["tab-size", "line-numbers"]
+        );
+
+        LineNumbersControl::write(false, cx);
+        TabSizeControl::write(2, cx);
+        assert_ne!(document.to_json(cx), saved);
+
+        document.apply_json(&saved, cx);
+        assert_eq!(document.to_json(cx), saved);
+    }
+
+    #[gpui::test]
+    fn test_settings_document_skips_unknown_ids_and_mismatched_values(cx: &mut AppContext) {
+        let document = document();
+        document.apply_json(
+            &json!({
+                "tab-size": "eight",
+                "line-numbers": false,
+                "word-wrap": true,
+            }),
+            cx,
+        );
+        assert_eq!(
+            document.to_json(cx),
+            json!({ "tab-size": 4, "line-numbers": false })
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use std::any::TypeId;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use gpui::{AppContext, Global};
+
+use crate::EditableSettingControl;
+
+/// Edits made to the same control within this interval are coalesced into a single entry, so
+/// that dragging a slider can be undone in one step.
+const COALESCE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The value a setting had before it was first edited.
+struct SettingBaseline {
+    control: TypeId,
+    is_changed: Rc<dyn Fn(&AppContext) -> bool>,
+    /// Returns a callback that restores the setting to its current value.
+    snapshot: Rc<dyn Fn(&AppContext) -> Rc<dyn Fn(&AppContext)>>,
+    revert: Rc<dyn Fn(&AppContext)>,
+}
+
+struct SettingsHistoryEntry {
+    control: TypeId,
+    recorded_at: Instant,
+    undo: Rc<dyn Fn(&AppContext)>,
+    redo: Rc<dyn Fn(&AppContext)>,
+}
+
+/// The history of the edits made through [`EditableSettingControl`]s, for undoing and redoing them.
+#[derive(Default)]
+pub struct SettingsHistory {
+    undo_stack: Vec<SettingsHistoryEntry>,
+    redo_stack: Vec<SettingsHistoryEntry>,
+    baselines: Vec<SettingBaseline>,
+}
+
+impl Global for SettingsHistory {}
+
+impl SettingsHistory {
+    /// Records that the setting edited by `C` was changed from `previous` to `value`.
+    pub fn record<C: EditableSettingControl>(
+        previous: C::Value,
+        value: C::Value,
+        cx: &mut AppContext,
+    ) {
+        let history = cx.default_global::<Self>();
+        let control = TypeId::of::<C>();
+        if !history
+            .baselines
+            .iter()
+            .any(|baseline| baseline.control == control)
+        {
+            let baseline = previous.clone();
+            let revert_to = previous.clone();
+            history.baselines.push(SettingBaseline {
+                control,
+                is_changed: Rc::new(move |cx| C::read(cx) != baseline),
+                snapshot: Rc::new(|cx| {
+                    let value = C::read(cx);
+                    Rc::new(move |cx| C::persist(value.clone(), cx))
+                }),
+                revert: Rc::new(move |cx| C::persist(revert_to.clone(), cx)),
+            });
+        }
+
+        history.push(
+            control,
+            Instant::now(),
+            Rc::new(move |cx| C::persist(previous.clone(), cx)),
+            Rc::new(move |cx| C::persist(value.clone(), cx)),
+        );
+    }
+
+    /// Returns the controls whose settings differ from the values they had before they were
+    /// first edited.
+    pub fn changed_settings(cx: &AppContext) -> Vec<TypeId> {
+        cx.try_global::<Self>()
+            .map(|history| {
+                history
+                    .baselines
+                    .iter()
+                    .filter(|baseline| (baseline.is_changed)(cx))
+                    .map(|baseline| baseline.control)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Restores every changed setting to the value it had before it was first edited, as a
+    /// single edit that can be undone.
+    ///
+    /// Returns `false` if no settings were changed.
+    pub fn revert_all(cx: &mut AppContext) -> bool {
+        let changed = cx.try_global::<Self>().map_or(Vec::new(), |history| {
+            history
+                .baselines
+                .iter()
+                .filter(|baseline| (baseline.is_changed)(cx))
+                .map(|baseline| (baseline.snapshot.clone(), baseline.revert.clone()))
+                .collect::<Vec<_>>()
+        });
+        if changed.is_empty() {
+            return false;
+        }
+
+        let (restores, reverts): (Vec<_>, Vec<_>) = changed
+            .into_iter()
+            .map(|(snapshot, revert)| (snapshot(cx), revert))
+            .unzip();
+        for revert in &reverts {
+            revert(cx);
+        }
+        // This is recorded under the history's own type, so it isn't coalesced with an edit to
+        // any of the settings.
+        cx.default_global::<Self>().push(
+            TypeId::of::<Self>(),
+            Instant::now(),
+            Rc::new(move |cx| restores.iter().for_each(|restore| restore(cx))),
+            Rc::new(move |cx| reverts.iter().for_each(|revert| revert(cx))),
+        );
+        true
+    }
+
+    fn push(
+        &mut self,
+        control: TypeId,
+        recorded_at: Instant,
+        undo: Rc<dyn Fn(&AppContext)>,
+        redo: Rc<dyn Fn(&AppContext)>,
+    ) {
+        self.redo_stack.clear();
+
+        if let Some(last) = self.undo_stack.last_mut() {
+            if last.control == control
+                && recorded_at.saturating_duration_since(last.recorded_at) < COALESCE_INTERVAL
+            {
+                last.recorded_at = recorded_at;
+                last.redo = redo;
+                return;
+            }
+        }
+
+        self.undo_stack.push(SettingsHistoryEntry {
+            control,
+            recorded_at,
+            undo,
+            redo,
+        });
+    }
+
+    /// Returns whether there is an edit that can be undone.
+    pub fn can_undo(cx: &AppContext) -> bool {
+        cx.try_global::<Self>()
+            .map_or(false, |history| !history.undo_stack.is_empty())
+    }
+
+    /// Returns whether there is an undone edit that can be redone.
+    pub fn can_redo(cx: &AppContext) -> bool {
+        cx.try_global::<Self>()
+            .map_or(false, |history| !history.redo_stack.is_empty())
+    }
+
+    /// Restores the setting changed by the most recent edit to its previous value.
+    ///
+    /// Returns `false` if there was nothing to undo.
+    pub fn undo(cx: &mut AppContext) -> bool {
+        let Some(entry) = cx.default_global::<Self>().undo_stack.pop() else {
+            return false;
+        };
+
+        (entry.undo)(cx);
+        cx.default_global::<Self>().redo_stack.push(entry);
+        true
+    }
+
+    /// Reapplies the most recently undone edit.
+    ///
+    /// Returns `false` if there was nothing to redo.
+    pub fn redo(cx: &mut AppContext) -> bool {
+        let Some(entry) = cx.default_global::<Self>().redo_stack.pop() else {
+            return false;
+        };
+
+        (entry.redo)(cx);
+        cx.default_global::<Self>().undo_stack.push(entry);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    fn entry(
+        log: &Rc<RefCell<Vec<String>>>,
+        name: &str,
+    ) -> (Rc<dyn Fn(&AppContext)>, Rc<dyn Fn(&AppContext)>) {
+        let undo_log = log.clone();
+        let undo_name = format!("undo {name}");
+        let redo_log = log.clone();
+        let redo_name = format!("redo {name}");
+        (
+            Rc::new(move |_| undo_log.borrow_mut().push(undo_name.clone())),
+            Rc::new(move |_| redo_log.borrow_mut().push(redo_name.clone())),
+        )
+    }
+
+    #[gpui::test]
+    fn test_settings_history_coalesces_rapid_edits(cx: &mut AppContext) {
+        struct Slider;
+        struct Checkbox;
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let start = Instant::now();
+        let mut history = SettingsHistory::default();
+
+        let (undo, redo) = entry(&log, "slider 1");
+        history.push(TypeId::of::<Slider>(), start, undo, redo);
+        let (undo, redo) = entry(&log, "slider 2");
+        history.push(
+            TypeId::of::<Slider>(),
+            start + Duration::from_millis(100),
+            undo,
+            redo,
+        );
+        let (undo, redo) = entry(&log, "checkbox");
+        history.push(
+            TypeId::of::<Checkbox>(),
+            start + Duration::from_millis(200),
+            undo,
+            redo,
+        );
+        let (undo, redo) = entry(&log, "slider 3");
+        history.push(
+            TypeId::of::<Slider>(),
+            start + Duration::from_millis(300),
+            undo,
+            redo,
+        );
+        assert_eq!(history.undo_stack.len(), 3);
+        cx.set_global(history);
+
+        assert!(SettingsHistory::undo(cx));
+        assert!(SettingsHistory::undo(cx));
+        assert!(SettingsHistory::undo(cx));
+        assert!(!SettingsHistory::undo(cx));
+        assert!(SettingsHistory::can_redo(cx));
+        assert!(SettingsHistory::redo(cx));
+        assert_eq!(
+            log.borrow().as_slice(),
+            [
+                "undo slider 3",
+                "undo checkbox",
+                "undo slider 1",
+                "redo slider 2"
+            ]
+        );
+
+        // Recording a new edit discards the edits that were undone.
+        let (undo, redo) = entry(&log, "checkbox");
+        cx.global_mut::<SettingsHistory>().push(
+            TypeId::of::<Checkbox>(),
+            Instant::now(),
+            undo,
+            redo,
+        );
+        assert!(!SettingsHistory::can_redo(cx));
+    }
+}
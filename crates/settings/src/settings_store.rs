@@ -2,7 +2,9 @@ use anyhow::{anyhow, Context, Result};
 use collections::{btree_map, hash_map, BTreeMap, HashMap};
 use fs::Fs;
 use futures::{channel::mpsc, future::LocalBoxFuture, FutureExt, StreamExt};
-use gpui::{AppContext, AsyncAppContext, BorrowAppContext, Global, Task, UpdateGlobal};
+use gpui::{
+    AppContext, AsyncAppContext, BorrowAppContext, Global, SharedString, Task, UpdateGlobal,
+};
 use lazy_static::lazy_static;
 use schemars::{gen::SchemaGenerator, schema::RootSchema, JsonSchema};
 use serde::{de::DeserializeOwned, Deserialize as _, Serialize};
@@ -82,6 +84,16 @@ pub trait Settings: 'static + Send + Sync {
         cx.global::<SettingsStore>().get(None)
     }
 
+    /// Returns the value of this setting as given by the default settings alone, ignoring any
+    /// user, extension, or project customizations.
+    #[track_caller]
+    fn get_default(cx: &AppContext) -> &Self
+    where
+        Self: Sized,
+    {
+        cx.global::<SettingsStore>().get_default()
+    }
+
     #[track_caller]
     fn try_read_global<R>(cx: &AsyncAppContext, f: impl FnOnce(&Self) -> R) -> Option<R>
     where
@@ -113,6 +125,19 @@ pub struct SettingsSources<'a, T> {
     pub project: &'a [&'a T],
 }
 
+impl<'a, T> SettingsSources<'a, T> {
+    /// Returns sources consisting solely of the given default settings.
+    pub fn defaults(default: &'a T) -> Self {
+        Self {
+            default,
+            extensions: None,
+            user: None,
+            release_channel: None,
+            project: &[],
+        }
+    }
+}
+
 impl<'a, T: Serialize> SettingsSources<'a, T> {
     /// Returns an iterator over the default settings as well as all settings customizations.
     pub fn defaults_and_customizations(&self) -> impl Iterator<Item = &T> {
@@ -175,9 +200,17 @@ pub struct SettingsStore {
 
 impl Global for SettingsStore {}
 
+/// The most recent failure to write a change to the settings file, which is set each time a write
+/// fails so that observers of this global can report it.
+#[derive(Clone, Debug)]
+pub struct SettingsFileUpdateError(pub SharedString);
+
+impl Global for SettingsFileUpdateError {}
+
 #[derive(Debug)]
 struct SettingValue<T> {
     global_value: Option<T>,
+    default_value: Option<T>,
     local_values: Vec<(usize, Arc<Path>, T)>,
 }
 
@@ -192,6 +225,8 @@ trait AnySettingValue: 'static + Send + Sync {
     ) -> Result<Box<dyn Any>>;
     fn value_for_path(&self, path: Option<SettingsLocation>) -> &dyn Any;
     fn set_global_value(&mut self, value: Box<dyn Any>);
+    fn default_value(&self) -> &dyn Any;
+    fn set_default_value(&mut self, value: Box<dyn Any>);
     fn set_local_value(&mut self, root_id: usize, path: Arc<Path>, value: Box<dyn Any>);
     fn json_schema(
         &self,
@@ -216,7 +251,11 @@ impl SettingsStore {
             setting_file_updates_tx,
             _setting_file_updates: cx.spawn(|cx| async move {
                 while let Some(setting_file_update) = setting_file_updates_rx.next().await {
-                    (setting_file_update)(cx.clone()).await.log_err();
+                    if let Err(error) = (setting_file_update)(cx.clone()).await {
+                        log::error!("{error:?}");
+                        let error = SettingsFileUpdateError(format!("{error:#}").into());
+                        cx.update(|cx| cx.set_global(error)).log_err();
+                    }
                 }
             }),
         }
@@ -239,6 +278,7 @@ impl SettingsStore {
 
         let setting_value = entry.or_insert(Box::new(SettingValue::<T> {
             global_value: None,
+            default_value: None,
             local_values: Vec::new(),
         }));
 
@@ -280,6 +320,13 @@ impl SettingsStore {
             {
                 setting_value.set_global_value(setting);
             }
+
+            if let Some(setting) = setting_value
+                .load_setting(SettingsSources::defaults(&default_settings), cx)
+                .log_err()
+            {
+                setting_value.set_default_value(setting);
+            }
         }
     }
 
@@ -296,6 +343,18 @@ impl SettingsStore {
             .expect("no default value for setting type")
     }
 
+    /// Get the value of a setting as given by the default settings alone.
+    ///
+    /// Panics if the given setting type has not been registered.
+    pub fn get_default<T: Settings>(&self) -> &T {
+        self.setting_values
+            .get(&TypeId::of::<T>())
+            .unwrap_or_else(|| panic!("unregistered setting type {}", type_name::<T>()))
+            .default_value()
+            .downcast_ref::<T>()
+            .expect("no default value for setting type")
+    }
+
     /// Override the global value for a setting.
     ///
     /// The given value will be overwritten if the user settings file changes.
@@ -719,6 +778,13 @@ impl SettingsStore {
                 {
                     setting_value.set_global_value(value);
                 }
+
+                if let Some(value) = setting_value
+                    .load_setting(SettingsSources::defaults(&default_settings), cx)
+                    .log_err()
+                {
+                    setting_value.set_default_value(value);
+                }
             }
 
             // Reload the local values for the setting.
@@ -858,6 +924,16 @@ impl<T: Settings> AnySettingValue for SettingValue<T> {
         self.global_value = Some(*value.downcast().unwrap());
     }
 
+    fn default_value(&self) -> &dyn Any {
+        self.default_value
+            .as_ref()
+            .unwrap_or_else(|| panic!("no default value for setting {}", self.setting_type_name()))
+    }
+
+    fn set_default_value(&mut self, value: Box<dyn Any>) {
+        self.default_value = Some(*value.downcast().unwrap());
+    }
+
     fn set_local_value(&mut self, root_id: usize, path: Arc<Path>, value: Box<dyn Any>) {
         let value = *value.downcast().unwrap();
         match self
@@ -1267,6 +1343,24 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_setting_store_default_values(cx: &mut AppContext) {
+        let mut store = SettingsStore::new(cx);
+        store.register_setting::<TurboSetting>(cx);
+        store
+            .set_default_settings(r#"{ "turbo": false }"#, cx)
+            .unwrap();
+        store.set_user_settings(r#"{ "turbo": true }"#, cx).unwrap();
+
+        assert_eq!(store.get::<TurboSetting>(None), &TurboSetting(true));
+        assert_eq!(store.get_default::<TurboSetting>(), &TurboSetting(false));
+
+        store
+            .set_default_settings(r#"{ "turbo": true }"#, cx)
+            .unwrap();
+        assert_eq!(store.get_default::<TurboSetting>(), &TurboSetting(true));
+    }
+
     #[gpui::test]
     fn test_setting_store_update(cx: &mut AppContext) {
         let mut store = SettingsStore::new(cx);
@@ -1,51 +1,317 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
-use gpui::{AppContext, FontFeatures, FontWeight};
+use collections::HashSet;
+use gpui::{AppContext, FontFeatures, FontWeight, Hsla, ScrollHandle};
 use settings::{EditableSettingControl, Settings};
 use theme::{FontFamilyCache, SystemAppearance, ThemeMode, ThemeRegistry, ThemeSettings};
 use ui::{
-    prelude::*, CheckboxWithLabel, ContextMenu, DropdownMenu, NumericStepper, SettingsContainer,
-    SettingsGroup, ToggleButton,
+    prelude::*, CheckboxWithLabel, ColorPicker, ContextMenu, DropdownMenu, HighlightedLabel,
+    NumericStepper, PopoverMenuHandle, SettingsContainer, SettingsFocus, SettingsGroup,
+    SettingsGroupOrder, SettingsItem, ToggleButton,
 };
 
 #[derive(IntoElement)]
-pub struct AppearanceSettingsControls {}
+pub struct AppearanceSettingsControls {
+    query: SharedString,
+    collapsed_groups: HashSet<SharedString>,
+    highlighted_group: Option<SharedString>,
+    revealed_group: Option<(SharedString, ScrollHandle)>,
+    on_toggle_group: Option<Arc<dyn Fn(&SharedString, &mut WindowContext) + 'static>>,
+    group_order: Option<SettingsGroupOrder>,
+    focus: Option<SettingsFocus>,
+    state_hash: Option<u64>,
+}
 
 impl AppearanceSettingsControls {
+    /// The headers and IDs of the groups, in the order they're shown.
+    const GROUPS: [(&'static str, &'static str); 2] =
+        [("Theme", "appearance-theme"), ("Font", "appearance-font")];
+
     pub fn new() -> Self {
-        Self {}
+        Self {
+            query: SharedString::default(),
+            collapsed_groups: HashSet::default(),
+            highlighted_group: None,
+            revealed_group: None,
+            on_toggle_group: None,
+            group_order: None,
+            focus: None,
+            state_hash: None,
+        }
+    }
+
+    /// Returns whether one of the groups has the given ID.
+    pub fn has_group(group_id: &str) -> bool {
+        Self::GROUPS.iter().any(|(_, id)| *id == group_id)
+    }
+
+    /// Only shows the settings that fuzzily match the given search query, with the groups that
+    /// match best shown first and the matching characters highlighted.
+    ///
+    /// When the query matches the name of a group, every setting in that group is shown.
+    pub fn query(mut self, query: impl Into<SharedString>) -> Self {
+        self.query = query.into();
+        self
+    }
+
+    /// Sets the IDs of the groups that are collapsed.
+    ///
+    /// Groups are always expanded while a search query is active.
+    pub fn collapsed_groups(mut self, collapsed_groups: HashSet<SharedString>) -> Self {
+        self.collapsed_groups = collapsed_groups;
+        self
+    }
+
+    /// Highlights the group with the given ID, such as after it has been scrolled to.
+    pub fn highlighted_group(mut self, group_id: impl Into<SharedString>) -> Self {
+        self.highlighted_group = Some(group_id.into());
+        self
+    }
+
+    /// Scrolls the element tracked by the given handle so that the group with the given ID is at
+    /// its top.
+    pub fn reveal_group(
+        mut self,
+        group_id: impl Into<SharedString>,
+        scroll_handle: ScrollHandle,
+    ) -> Self {
+        self.revealed_group = Some((group_id.into(), scroll_handle));
+        self
+    }
+
+    /// Sets the handler called with the group ID when a group header is clicked.
+    pub fn on_toggle_group(
+        mut self,
+        on_toggle_group: impl Fn(&SharedString, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.on_toggle_group = Some(Arc::new(on_toggle_group));
+        self
+    }
+
+    /// Lets the groups be reordered by dragging their headers, keeping their order in the given
+    /// [`SettingsGroupOrder`].
+    ///
+    /// Groups can't be reordered while a search query is active, as some of them may be hidden.
+    pub fn group_order(mut self, group_order: SettingsGroupOrder) -> Self {
+        self.group_order = Some(group_order);
+        self
+    }
+
+    /// Lets the settings and group headers be focused from the keyboard with the given focus.
+    pub fn focus(mut self, focus: SettingsFocus) -> Self {
+        self.focus = Some(focus);
+        self
+    }
+
+    /// Reuses what each group drew in the previous frame while the given hash is unchanged,
+    /// instead of rendering its settings again. The hash should change whenever the settings do.
+    pub fn cache(mut self, state_hash: u64) -> Self {
+        self.state_hash = Some(state_hash);
+        self
+    }
+
+    fn reorderable_group_order(&self) -> Option<SettingsGroupOrder> {
+        self.group_order.clone().filter(|_| self.query.is_empty())
+    }
+
+    /// Returns whether any of the settings match the current query.
+    pub fn has_matches(&self) -> bool {
+        self.matches("Theme", &ThemeControl::default())
+            || self.matches("Theme", &ThemeModeControl)
+            || self.matches("Theme", &AccentColorControl(self.query.clone()))
+            || self.matches("Font", &UiFontFamilyControl::default())
+            || self.matches("Font", &UiFontWeightControl::default())
+            || self.matches("Font", &UiFontSizeControl)
+            || self.matches("Font", &UiFontLigaturesControl(self.query.clone()))
+    }
+
+    /// Returns the ID of the group with the given header, which is static so that rendering the
+    /// group doesn't allocate it.
+    fn group_id(header: &str) -> SharedString {
+        let (_, id) = Self::GROUPS
+            .iter()
+            .find(|(group_header, _)| *group_header == header)
+            .expect("unknown settings group");
+        SharedString::from(*id)
+    }
+
+    /// Returns the group with the given header, ranked among the others by the given search score.
+    fn group(&self, header: &'static str, score: f64) -> SettingsGroup {
+        let id = Self::group_id(header);
+        let collapsed = self.query.is_empty() && self.collapsed_groups.contains(&id);
+        let header_highlights = fuzzy::match_string(&self.query, header, false)
+            .map(|mat| mat.positions)
+            .unwrap_or_default();
+
+        SettingsGroup::new(header)
+            .header_highlights(header_highlights)
+            .search_score(score)
+            .when(self.reorderable_group_order().is_some(), |this| {
+                this.draggable(id.clone())
+            })
+            .collapsible(true)
+            .collapsed(collapsed)
+            .highlighted(self.highlighted_group.as_ref() == Some(&id))
+            .when_some(
+                self.revealed_group
+                    .as_ref()
+                    .filter(|(group_id, _)| *group_id == id),
+                |this, (_, scroll_handle)| this.reveal(scroll_handle.clone()),
+            )
+            .when_some(self.focus.clone(), |this, focus| {
+                this.focus(focus, id.clone())
+            })
+            .when_some(self.state_hash, |this, state_hash| {
+                // The settings in the group highlight the query in their names.
+                let mut hasher = DefaultHasher::new();
+                (state_hash, &self.query).hash(&mut hasher);
+                this.cache(hasher.finish())
+            })
+            .when_some(self.on_toggle_group.clone(), |this, on_toggle_group| {
+                this.on_toggle(move |_, cx| on_toggle_group(&id, cx))
+            })
+    }
+
+    /// Returns the item for the setting with the given ID, which can be focused from the
+    /// keyboard.
+    fn item(&self, id: &'static str, control: impl IntoElement) -> SettingsItem {
+        SettingsItem::new(id, control)
+            .when_some(self.focus.clone(), |this, focus| this.focus(focus))
+    }
+
+    /// Returns how well the setting in the given group matches the current query, or `None` if
+    /// it doesn't.
+    ///
+    /// When the query matches the header of the group, the setting matches at least as well as
+    /// the header does.
+    fn score(&self, group: &str, control: &impl EditableSettingControl) -> Option<f64> {
+        let group_score = fuzzy::match_string(&self.query, group, false).map(|mat| mat.score);
+        let control_score = control.query_match(&self.query).map(|mat| mat.score);
+        best_score([group_score, control_score])
+    }
+
+    fn matches(&self, group: &str, control: &impl EditableSettingControl) -> bool {
+        self.score(group, control).is_some()
     }
 }
 
+/// Returns the best of the given search scores, or `None` if none of them matched.
+fn best_score(scores: impl IntoIterator<Item = Option<f64>>) -> Option<f64> {
+    scores.into_iter().flatten().reduce(f64::max)
+}
+
 impl RenderOnce for AppearanceSettingsControls {
     fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+        // The controls that show the name of their setting are given the query, so that they can
+        // highlight the characters of the name that match it.
+        let theme_control = ThemeControl::default();
+        let font_family_control = UiFontFamilyControl::default();
+        let font_weight_control = UiFontWeightControl::default();
+        let theme = self.score("Theme", &theme_control);
+        let theme_mode = self.score("Theme", &ThemeModeControl);
+        let accent_color = self.score("Theme", &AccentColorControl(self.query.clone()));
+        let font_family = self.score("Font", &font_family_control);
+        let font_weight = self.score("Font", &font_weight_control);
+        let font_size = self.score("Font", &UiFontSizeControl);
+        let font_ligatures = self.score("Font", &UiFontLigaturesControl(self.query.clone()));
+
         SettingsContainer::new()
-            .child(
-                SettingsGroup::new("Theme").child(
-                    h_flex()
-                        .gap_2()
-                        .justify_between()
-                        .child(ThemeControl)
-                        .child(ThemeModeControl),
-                ),
+            .when_some(self.reorderable_group_order(), |this, group_order| {
+                this.reorderable(group_order)
+            })
+            .when_some(
+                best_score([theme, theme_mode, accent_color]),
+                |this, score| {
+                    this.child_group(
+                        self.group("Theme", score)
+                            .when(theme.is_some() || theme_mode.is_some(), |this| {
+                                this.child(
+                                    h_flex()
+                                        .gap_2()
+                                        .justify_between()
+                                        .when(theme.is_some(), |this| {
+                                            this.child({
+                                                let menu_handle = theme_control.menu_handle.clone();
+                                                self.item("theme", theme_control)
+                                                    .on_activate(move |cx| menu_handle.show(cx))
+                                            })
+                                        })
+                                        .when(theme_mode.is_some(), |this| {
+                                            this.child(
+                                                self.item("theme-mode", ThemeModeControl)
+                                                    .on_activate(ThemeModeControl::cycle),
+                                            )
+                                        }),
+                                )
+                            })
+                            .when(accent_color.is_some(), |this| {
+                                this.child(
+                                    self.item(
+                                        "accent-color",
+                                        AccentColorControl(self.query.clone()),
+                                    ),
+                                )
+                            }),
+                    )
+                },
             )
-            .child(
-                SettingsGroup::new("Font")
-                    .child(
-                        h_flex()
-                            .gap_2()
-                            .justify_between()
-                            .child(UiFontFamilyControl)
-                            .child(UiFontWeightControl),
+            .when_some(
+                best_score([font_family, font_weight, font_size, font_ligatures]),
+                |this, score| {
+                    this.child_group(
+                        self.group("Font", score)
+                            .when(font_family.is_some() || font_weight.is_some(), |this| {
+                                this.child(
+                                    h_flex()
+                                        .gap_2()
+                                        .justify_between()
+                                        .when(font_family.is_some(), |this| {
+                                            this.child({
+                                                let menu_handle =
+                                                    font_family_control.menu_handle.clone();
+                                                self.item("ui-font-family", font_family_control)
+                                                    .on_activate(move |cx| menu_handle.show(cx))
+                                            })
+                                        })
+                                        .when(font_weight.is_some(), |this| {
+                                            this.child({
+                                                let menu_handle =
+                                                    font_weight_control.menu_handle.clone();
+                                                self.item("ui-font-weight", font_weight_control)
+                                                    .on_activate(move |cx| menu_handle.show(cx))
+                                            })
+                                        }),
+                                )
+                            })
+                            .when(font_size.is_some(), |this| {
+                                this.child(self.item("ui-font-size", UiFontSizeControl))
+                            })
+                            .when(font_ligatures.is_some(), |this| {
+                                this.child(
+                                    self.item(
+                                        "ui-font-ligatures",
+                                        UiFontLigaturesControl(self.query.clone()),
+                                    )
+                                    .on_activate(|cx| {
+                                        UiFontLigaturesControl::write(
+                                            !UiFontLigaturesControl::read(cx),
+                                            cx,
+                                        )
+                                    }),
+                                )
+                            }),
                     )
-                    .child(UiFontSizeControl)
-                    .child(UiFontLigaturesControl),
+                },
             )
     }
 }
 
-#[derive(IntoElement)]
-struct ThemeControl;
+#[derive(IntoElement, Default)]
+struct ThemeControl {
+    menu_handle: PopoverMenuHandle<ContextMenu>,
+}
 
 impl EditableSettingControl for ThemeControl {
     type Value = String;
@@ -104,6 +370,7 @@ impl RenderOnce for ThemeControl {
             }),
         )
         .full_width(true)
+        .with_handle(self.menu_handle)
     }
 }
 
@@ -136,6 +403,18 @@ impl EditableSettingControl for ThemeModeControl {
     }
 }
 
+impl ThemeModeControl {
+    /// Selects the mode after the current one, for activating the control from the keyboard.
+    fn cycle(cx: &mut WindowContext) {
+        let mode = match Self::read(cx) {
+            ThemeMode::Light => ThemeMode::System,
+            ThemeMode::System => ThemeMode::Dark,
+            ThemeMode::Dark => ThemeMode::Light,
+        };
+        Self::write(mode, cx);
+    }
+}
+
 impl RenderOnce for ThemeModeControl {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         let value = Self::read(cx);
@@ -169,7 +448,54 @@ impl RenderOnce for ThemeModeControl {
 }
 
 #[derive(IntoElement)]
-struct UiFontFamilyControl;
+struct AccentColorControl(SharedString);
+
+impl EditableSettingControl for AccentColorControl {
+    type Value = Hsla;
+    type Settings = ThemeSettings;
+
+    fn name(&self) -> SharedString {
+        "Accent Color".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        cx.theme().colors().text_accent
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings
+            .theme_overrides
+            .get_or_insert_with(Default::default)
+            .colors
+            .text_accent = Some(value.to_rgb().to_hex_string());
+    }
+}
+
+impl RenderOnce for AccentColorControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+
+        v_flex()
+            .gap_1()
+            .child(HighlightedLabel::new(
+                self.name(),
+                self.query_highlights(&self.0),
+            ))
+            .child(
+                ColorPicker::new("accent-color", value)
+                    .on_change(|value, cx| Self::write(value, cx)),
+            )
+    }
+}
+
+#[derive(IntoElement, Default)]
+struct UiFontFamilyControl {
+    menu_handle: PopoverMenuHandle<ContextMenu>,
+}
 
 impl EditableSettingControl for UiFontFamilyControl {
     type Value = SharedString;
@@ -197,10 +523,8 @@ impl RenderOnce for UiFontFamilyControl {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         let value = Self::read(cx);
 
-        h_flex()
-            .gap_2()
-            .child(Icon::new(IconName::Font))
-            .child(DropdownMenu::new(
+        h_flex().gap_2().child(Icon::new(IconName::Font)).child(
+            DropdownMenu::new(
                 "ui-font-family",
                 value.clone(),
                 ContextMenu::build(cx, |mut menu, cx| {
@@ -223,7 +547,9 @@ impl RenderOnce for UiFontFamilyControl {
 
                     menu
                 }),
-            ))
+            )
+            .with_handle(self.menu_handle),
+        )
     }
 }
 
@@ -272,8 +598,10 @@ impl RenderOnce for UiFontSizeControl {
     }
 }
 
-#[derive(IntoElement)]
-struct UiFontWeightControl;
+#[derive(IntoElement, Default)]
+struct UiFontWeightControl {
+    menu_handle: PopoverMenuHandle<ContextMenu>,
+}
 
 impl EditableSettingControl for UiFontWeightControl {
     type Value = FontWeight;
@@ -304,29 +632,32 @@ impl RenderOnce for UiFontWeightControl {
         h_flex()
             .gap_2()
             .child(Icon::new(IconName::FontWeight))
-            .child(DropdownMenu::new(
-                "ui-font-weight",
-                value.0.to_string(),
-                ContextMenu::build(cx, |mut menu, _cx| {
-                    for weight in FontWeight::ALL {
-                        menu = menu.custom_entry(
-                            move |_cx| Label::new(weight.0.to_string()).into_any_element(),
-                            {
-                                move |cx| {
-                                    Self::write(weight, cx);
-                                }
-                            },
-                        )
-                    }
-
-                    menu
-                }),
-            ))
+            .child(
+                DropdownMenu::new(
+                    "ui-font-weight",
+                    value.0.to_string(),
+                    ContextMenu::build(cx, |mut menu, _cx| {
+                        for weight in FontWeight::ALL {
+                            menu = menu.custom_entry(
+                                move |_cx| Label::new(weight.0.to_string()).into_any_element(),
+                                {
+                                    move |cx| {
+                                        Self::write(weight, cx);
+                                    }
+                                },
+                            )
+                        }
+
+                        menu
+                    }),
+                )
+                .with_handle(self.menu_handle),
+            )
     }
 }
 
 #[derive(IntoElement)]
-struct UiFontLigaturesControl;
+struct UiFontLigaturesControl(SharedString);
 
 impl EditableSettingControl for UiFontLigaturesControl {
     type Value = bool;
@@ -388,5 +719,6 @@ impl RenderOnce for UiFontLigaturesControl {
                 );
             },
         )
+        .label_highlights(self.query_highlights(&self.0))
     }
 }
@@ -0,0 +1,233 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use collections::HashSet;
+use gpui::{AppContext, ScrollHandle};
+use journal::JournalSettings;
+use settings::{EditableSettingControl, Settings};
+use ui::{
+    prelude::*, FileOrDirectory, HighlightedLabel, PathPicker, SettingsContainer, SettingsFocus,
+    SettingsGroup, SettingsItem,
+};
+
+#[derive(IntoElement)]
+pub struct JournalSettingsControls {
+    query: SharedString,
+    collapsed_groups: HashSet<SharedString>,
+    highlighted_group: Option<SharedString>,
+    revealed_group: Option<(SharedString, ScrollHandle)>,
+    on_toggle_group: Option<Arc<dyn Fn(&SharedString, &mut WindowContext) + 'static>>,
+    focus: Option<SettingsFocus>,
+}
+
+impl JournalSettingsControls {
+    /// The headers and IDs of the groups, in the order they're shown.
+    const GROUPS: [(&'static str, &'static str); 1] = [("Journal", "journal-journal")];
+
+    pub fn new() -> Self {
+        Self {
+            query: SharedString::default(),
+            collapsed_groups: HashSet::default(),
+            highlighted_group: None,
+            revealed_group: None,
+            on_toggle_group: None,
+            focus: None,
+        }
+    }
+
+    /// Returns whether one of the groups has the given ID.
+    pub fn has_group(group_id: &str) -> bool {
+        Self::GROUPS.iter().any(|(_, id)| *id == group_id)
+    }
+
+    /// Only shows the settings that fuzzily match the given search query, with the matching
+    /// characters highlighted.
+    ///
+    /// When the query matches the name of a group, every setting in that group is shown.
+    pub fn query(mut self, query: impl Into<SharedString>) -> Self {
+        self.query = query.into();
+        self
+    }
+
+    /// Sets the IDs of the groups that are collapsed.
+    ///
+    /// Groups are always expanded while a search query is active.
+    pub fn collapsed_groups(mut self, collapsed_groups: HashSet<SharedString>) -> Self {
+        self.collapsed_groups = collapsed_groups;
+        self
+    }
+
+    /// Highlights the group with the given ID, such as after it has been scrolled to.
+    pub fn highlighted_group(mut self, group_id: impl Into<SharedString>) -> Self {
+        self.highlighted_group = Some(group_id.into());
+        self
+    }
+
+    /// Scrolls the element tracked by the given handle so that the group with the given ID is at
+    /// its top.
+    pub fn reveal_group(
+        mut self,
+        group_id: impl Into<SharedString>,
+        scroll_handle: ScrollHandle,
+    ) -> Self {
+        self.revealed_group = Some((group_id.into(), scroll_handle));
+        self
+    }
+
+    /// Sets the handler called with the group ID when a group header is clicked.
+    pub fn on_toggle_group(
+        mut self,
+        on_toggle_group: impl Fn(&SharedString, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.on_toggle_group = Some(Arc::new(on_toggle_group));
+        self
+    }
+
+    /// Lets the setting and group header be focused from the keyboard with the given focus.
+    pub fn focus(mut self, focus: SettingsFocus) -> Self {
+        self.focus = Some(focus);
+        self
+    }
+
+    /// Returns whether any of the settings match the current query.
+    pub fn has_matches(&self) -> bool {
+        self.matches("Journal", &JournalDirectoryControl(self.query.clone()))
+    }
+
+    /// Returns the ID of the group with the given header, which is static so that rendering the
+    /// group doesn't allocate it.
+    fn group_id(header: &str) -> SharedString {
+        let (_, id) = Self::GROUPS
+            .iter()
+            .find(|(group_header, _)| *group_header == header)
+            .expect("unknown settings group");
+        SharedString::from(*id)
+    }
+
+    /// Returns the group with the given header, ranked among the others by the given search score.
+    fn group(&self, header: &'static str, score: f64) -> SettingsGroup {
+        let id = Self::group_id(header);
+        let collapsed = self.query.is_empty() && self.collapsed_groups.contains(&id);
+        let header_highlights = fuzzy::match_string(&self.query, header, false)
+            .map(|mat| mat.positions)
+            .unwrap_or_default();
+
+        SettingsGroup::new(header)
+            .header_highlights(header_highlights)
+            .search_score(score)
+            .collapsible(true)
+            .collapsed(collapsed)
+            .highlighted(self.highlighted_group.as_ref() == Some(&id))
+            .when_some(
+                self.revealed_group
+                    .as_ref()
+                    .filter(|(group_id, _)| *group_id == id),
+                |this, (_, scroll_handle)| this.reveal(scroll_handle.clone()),
+            )
+            .when_some(self.focus.clone(), |this, focus| {
+                this.focus(focus, id.clone())
+            })
+            .when_some(self.on_toggle_group.clone(), |this, on_toggle_group| {
+                this.on_toggle(move |_, cx| on_toggle_group(&id, cx))
+            })
+    }
+
+    /// Returns how well the setting in the given group matches the current query, or `None` if
+    /// it doesn't.
+    ///
+    /// When the query matches the header of the group, the setting matches at least as well as
+    /// the header does.
+    fn score(&self, group: &str, control: &impl EditableSettingControl) -> Option<f64> {
+        let group_score = fuzzy::match_string(&self.query, group, false).map(|mat| mat.score);
+        let control_score = control.query_match(&self.query).map(|mat| mat.score);
+        group_score
+            .into_iter()
+            .chain(control_score)
+            .reduce(f64::max)
+    }
+
+    fn matches(&self, group: &str, control: &impl EditableSettingControl) -> bool {
+        self.score(group, control).is_some()
+    }
+}
+
+impl RenderOnce for JournalSettingsControls {
+    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+        // The control is given the query, so that it can highlight the characters of its
+        // setting's name that match it.
+        let journal_directory = self.score("Journal", &JournalDirectoryControl(self.query.clone()));
+
+        SettingsContainer::new().when_some(journal_directory, |this, score| {
+            this.child_group(
+                self.group("Journal", score).child(
+                    SettingsItem::new(
+                        "journal-directory-item",
+                        JournalDirectoryControl(self.query.clone()),
+                    )
+                    .action("Learn More", IconName::Book, |cx| {
+                        cx.open_url("https://zed.dev/docs/configuring-zed#journal")
+                    })
+                    .when_some(self.focus.clone(), |this, focus| this.focus(focus)),
+                ),
+            )
+        })
+    }
+}
+
+#[derive(IntoElement)]
+struct JournalDirectoryControl(SharedString);
+
+impl EditableSettingControl for JournalDirectoryControl {
+    type Value = String;
+    type Settings = JournalSettings;
+
+    fn name(&self) -> SharedString {
+        "Journal Directory".into()
+    }
+
+    fn read(cx: &AppContext) -> Self::Value {
+        let settings = JournalSettings::get_global(cx);
+        settings.path.clone().unwrap_or_else(|| "~".to_string())
+    }
+
+    fn validate(value: &Self::Value, _cx: &AppContext) -> Result<(), SharedString> {
+        // The path may start with `~` or contain environment variables, as the journal expands them.
+        let exists =
+            shellexpand::full(value).map_or(false, |path| Path::new(path.as_ref()).is_dir());
+        if exists {
+            Ok(())
+        } else {
+            Err("This directory doesn't exist.".into())
+        }
+    }
+
+    fn apply(
+        settings: &mut <Self::Settings as Settings>::FileContent,
+        value: Self::Value,
+        _cx: &AppContext,
+    ) {
+        settings.path = Some(value);
+    }
+}
+
+impl RenderOnce for JournalDirectoryControl {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let value = Self::read(cx);
+        let error = Self::validate(&value, cx).err();
+
+        v_flex()
+            .gap_1()
+            .child(HighlightedLabel::new(
+                self.name(),
+                self.query_highlights(&self.0),
+            ))
+            .child(
+                PathPicker::new("journal-directory", Some(value))
+                    .kind(FileOrDirectory::Directory)
+                    .error(error)
+                    .on_change(|path, cx| {
+                        Self::write(path.to_string_lossy().into_owned(), cx);
+                    }),
+            )
+    }
+}
@@ -1,16 +1,26 @@
 mod appearance_settings_controls;
+mod journal_settings_controls;
 
 use std::any::TypeId;
+use std::time::Duration;
 
+use collections::{HashMap, HashSet};
 use command_palette_hooks::CommandPaletteFilter;
-use editor::EditorSettingsControls;
+use editor::actions::MoveToEnd;
+use editor::{Editor, EditorElement, EditorEvent, EditorSettingsControls, EditorStyle};
 use feature_flags::{FeatureFlag, FeatureFlagViewExt};
-use gpui::{actions, AppContext, EventEmitter, FocusHandle, FocusableView, View};
-use ui::prelude::*;
+use gpui::{
+    actions, canvas, AppContext, EventEmitter, FocusHandle, FocusableView, KeyDownEvent,
+    PromptLevel, ScrollHandle, Task, TextStyle, View,
+};
+use settings::{Settings, SettingsFileUpdateError, SettingsHistory, SettingsStore};
+use theme::ThemeSettings;
+use ui::{prelude::*, Scrollbar, SettingsFocus, SettingsGroupOrder};
 use workspace::item::{Item, ItemEvent};
 use workspace::Workspace;
 
 use crate::appearance_settings_controls::AppearanceSettingsControls;
+use crate::journal_settings_controls::JournalSettingsControls;
 
 pub struct SettingsUiFeatureFlag;
 
@@ -19,6 +29,23 @@ impl FeatureFlag for SettingsUiFeatureFlag {
 }
 
 actions!(zed, [OpenSettingsEditor]);
+actions!(
+    settings_ui,
+    [
+        UndoSettingsChange,
+        RedoSettingsChange,
+        SelectNextSetting,
+        SelectPreviousSetting,
+        FocusNextSetting,
+        FocusPreviousSetting,
+        ToggleSetting
+    ]
+);
+
+/// How long a group stays highlighted after it has been scrolled to.
+const GROUP_HIGHLIGHT_DURATION: Duration = Duration::from_millis(1500);
+/// How long a failure to save the settings is reported for.
+const SAVE_ERROR_TOAST_DURATION: Duration = Duration::from_secs(8);
 
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(|workspace: &mut Workspace, cx| {
@@ -59,23 +86,397 @@ pub fn init(cx: &mut AppContext) {
     .detach();
 }
 
+/// A tab of the settings page, each showing the settings of one section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SettingsTab {
+    Appearance,
+    Editor,
+    Journal,
+}
+
+impl SettingsTab {
+    const ALL: [Self; 3] = [Self::Appearance, Self::Editor, Self::Journal];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Appearance => "Appearance",
+            Self::Editor => "Editor",
+            Self::Journal => "Journal",
+        }
+    }
+
+    /// Returns whether any of the settings in this tab match the given query.
+    fn has_matches(&self, query: &str) -> bool {
+        let query = section_query(self.label(), query);
+        match self {
+            Self::Appearance => AppearanceSettingsControls::new().query(query).has_matches(),
+            Self::Editor => EditorSettingsControls::new().query(query).has_matches(),
+            Self::Journal => JournalSettingsControls::new().query(query).has_matches(),
+        }
+    }
+
+    /// Returns whether this tab contains the group with the given ID.
+    fn has_group(&self, group_id: &str) -> bool {
+        match self {
+            Self::Appearance => AppearanceSettingsControls::has_group(group_id),
+            Self::Editor => EditorSettingsControls::has_group(group_id),
+            Self::Journal => JournalSettingsControls::has_group(group_id),
+        }
+    }
+}
+
 pub struct SettingsPage {
     focus_handle: FocusHandle,
+    query_editor: View<Editor>,
+    active_tab: SettingsTab,
+    /// The scroll position of each tab, kept while other tabs are shown.
+    scroll_handles: HashMap<SettingsTab, ScrollHandle>,
+    /// The order of the groups in each tab, as they've been rearranged by dragging their headers.
+    group_orders: HashMap<SettingsTab, SettingsGroupOrder>,
+    collapsed_groups: HashSet<SharedString>,
+    highlighted_group: Option<SharedString>,
+    /// The group to scroll to the top of the active tab the next time it's rendered.
+    revealed_group: Option<SharedString>,
+    highlight_task: Option<Task<()>>,
+    /// The setting focused from the keyboard, which keeps its place while the search field is
+    /// focused.
+    settings_focus: SettingsFocus,
+    /// Counts the changes to the settings, so that the cached settings groups are drawn again
+    /// when any of them changes.
+    settings_generation: u64,
 }
 
 impl SettingsPage {
     pub fn new(_workspace: &Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
-        cx.new_view(|cx| Self {
-            focus_handle: cx.focus_handle(),
+        cx.new_view(|cx| {
+            let query_editor = cx.new_view(|cx| {
+                let mut input = Editor::single_line(cx);
+                input.set_placeholder_text("Search settings...", cx);
+                input
+            });
+            cx.subscribe(&query_editor, Self::on_query_change).detach();
+            // Re-render when the settings change, including edits made to the settings file directly.
+            cx.observe_global::<SettingsStore>(|this, cx| {
+                this.settings_generation += 1;
+                cx.notify()
+            })
+            .detach();
+            cx.observe_global::<SettingsFileUpdateError>(|_, cx| {
+                let error = cx.global::<SettingsFileUpdateError>().0.clone();
+                cx.push_toast(
+                    PromptLevel::Critical,
+                    format!("Couldn't save your settings: {error}"),
+                    SAVE_ERROR_TOAST_DURATION,
+                );
+            })
+            .detach();
+
+            Self {
+                focus_handle: cx.focus_handle(),
+                query_editor,
+                active_tab: SettingsTab::Appearance,
+                scroll_handles: HashMap::default(),
+                group_orders: HashMap::default(),
+                collapsed_groups: HashSet::default(),
+                highlighted_group: None,
+                revealed_group: None,
+                highlight_task: None,
+                settings_focus: SettingsFocus::new(cx.focus_handle()),
+                settings_generation: 0,
+            }
         })
     }
+
+    fn on_query_change(
+        &mut self,
+        _: View<Editor>,
+        event: &EditorEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let EditorEvent::Edited { .. } = event {
+            // Jump to the first tab with a match when the active one has none.
+            let query = self.query_editor.read(cx).text(cx);
+            let query = query.trim();
+            if !self.active_tab.has_matches(query) {
+                if let Some(tab) = SettingsTab::ALL
+                    .into_iter()
+                    .find(|tab| tab.has_matches(query))
+                {
+                    self.active_tab = tab;
+                }
+            }
+            cx.notify();
+        }
+    }
+
+    fn activate_tab(&mut self, tab: SettingsTab, cx: &mut ViewContext<Self>) {
+        self.active_tab = tab;
+        cx.notify();
+    }
+
+    fn render_tabs(&self, query: &str, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .gap_1()
+            .children(SettingsTab::ALL.into_iter().map(|tab| {
+                Button::new(
+                    SharedString::from(format!("settings-tab-{}", tab.label())),
+                    tab.label(),
+                )
+                .style(ButtonStyle::Subtle)
+                .selected(tab == self.active_tab)
+                // While searching, only the tabs with matches can be switched to.
+                .disabled(!query.is_empty() && !tab.has_matches(query))
+                .on_click(cx.listener(move |this, _, cx| this.activate_tab(tab, cx)))
+            }))
+    }
+
+    /// Scrolls the group with the given ID to the top of the page and briefly highlights it,
+    /// returning whether the group was found.
+    ///
+    /// The tab containing the group is activated, the group is expanded, and the search is
+    /// cleared so that the group is shown.
+    pub fn scroll_to_group(&mut self, group_id: &str, cx: &mut ViewContext<Self>) -> bool {
+        let Some(tab) = SettingsTab::ALL
+            .into_iter()
+            .find(|tab| tab.has_group(group_id))
+        else {
+            return false;
+        };
+
+        let group_id = SharedString::from(group_id.to_string());
+        if !self.query_editor.read(cx).text(cx).is_empty() {
+            self.query_editor.update(cx, |editor, cx| editor.clear(cx));
+        }
+        self.active_tab = tab;
+        self.collapsed_groups.remove(&group_id);
+        self.highlighted_group = Some(group_id.clone());
+        self.revealed_group = Some(group_id);
+        self.highlight_task = Some(cx.spawn(|this, mut cx| async move {
+            cx.background_executor()
+                .timer(GROUP_HIGHLIGHT_DURATION)
+                .await;
+            this.update(&mut cx, |this, cx| {
+                this.highlighted_group = None;
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+        true
+    }
+
+    fn toggle_group(&mut self, group_id: &SharedString, cx: &mut ViewContext<Self>) {
+        if !self.collapsed_groups.remove(group_id) {
+            self.collapsed_groups.insert(group_id.clone());
+        }
+        cx.notify();
+    }
+
+    /// Moves the keyboard focus to the settings, returning whether they were already focused.
+    fn focus_settings(&mut self, cx: &mut ViewContext<Self>) -> bool {
+        let was_focused = self.settings_focus.focus_handle().is_focused(cx);
+        cx.focus(self.settings_focus.focus_handle());
+        cx.notify();
+        was_focused
+    }
+
+    fn select_next_setting(&mut self, _: &SelectNextSetting, cx: &mut ViewContext<Self>) {
+        if self.focus_settings(cx) {
+            self.settings_focus.focus_next();
+        } else {
+            self.settings_focus.focus_first();
+        }
+    }
+
+    fn select_previous_setting(&mut self, _: &SelectPreviousSetting, cx: &mut ViewContext<Self>) {
+        // Moving up from the first setting goes back to the search field above it.
+        if self.settings_focus.is_first_focused() {
+            cx.focus_view(&self.query_editor);
+            cx.notify();
+        } else if self.focus_settings(cx) {
+            self.settings_focus.focus_previous();
+        } else {
+            self.settings_focus.focus_last();
+        }
+    }
+
+    fn focus_next_setting(&mut self, _: &FocusNextSetting, cx: &mut ViewContext<Self>) {
+        if self.focus_settings(cx) {
+            self.settings_focus.focus_next_wrapping();
+        } else {
+            self.settings_focus.focus_first();
+        }
+    }
+
+    fn focus_previous_setting(&mut self, _: &FocusPreviousSetting, cx: &mut ViewContext<Self>) {
+        if self.focus_settings(cx) {
+            self.settings_focus.focus_previous_wrapping();
+        } else {
+            self.settings_focus.focus_last();
+        }
+    }
+
+    fn toggle_setting(&mut self, _: &ToggleSetting, cx: &mut ViewContext<Self>) {
+        // Leave the key to whatever is focused inside the settings, such as an open dropdown.
+        if !self.settings_focus.focus_handle().is_focused(cx) {
+            cx.propagate();
+            return;
+        }
+        self.settings_focus.activate(cx);
+        cx.notify();
+    }
+
+    /// Types the text of a keystroke that isn't bound to anything into the search field, so that
+    /// the settings can be searched without first moving back to it.
+    fn type_into_search(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
+        let modifiers = event.keystroke.modifiers;
+        if !self.settings_focus.focus_handle().is_focused(cx)
+            || modifiers.control
+            || modifiers.platform
+            || modifiers.function
+        {
+            return;
+        }
+        let Some(text) = event.keystroke.ime_key.as_ref() else {
+            return;
+        };
+
+        cx.focus_view(&self.query_editor);
+        self.query_editor.update(cx, |editor, cx| {
+            editor.move_to_end(&MoveToEnd, cx);
+            editor.handle_input(text, cx);
+        });
+        cx.stop_propagation();
+    }
+
+    fn undo(&mut self, _: &UndoSettingsChange, cx: &mut ViewContext<Self>) {
+        SettingsHistory::undo(cx);
+    }
+
+    fn redo(&mut self, _: &RedoSettingsChange, cx: &mut ViewContext<Self>) {
+        SettingsHistory::redo(cx);
+    }
+
+    /// Returns a banner summarizing the settings that have been changed, with a button for
+    /// reverting them, or `None` if no settings differ from their values before they were edited.
+    fn render_changes(&self, cx: &mut ViewContext<Self>) -> Option<impl IntoElement> {
+        let change_count = SettingsHistory::changed_settings(cx).len();
+        if change_count == 0 {
+            return None;
+        }
+
+        Some(
+            h_flex()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .justify_between()
+                .rounded_md()
+                .bg(cx.theme().colors().element_background)
+                .child(
+                    Label::new(if change_count == 1 {
+                        "1 change".to_string()
+                    } else {
+                        format!("{change_count} changes")
+                    })
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+                )
+                .child(
+                    Button::new("revert-all-settings", "Revert All")
+                        .icon(IconName::RotateCcw)
+                        .icon_position(IconPosition::Start)
+                        .icon_size(IconSize::Small)
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener(|_, _, cx| {
+                            let answer = cx.prompt(
+                                PromptLevel::Warning,
+                                "Revert all changed settings?",
+                                Some("This can be undone."),
+                                &["Revert All", "Cancel"],
+                            );
+                            cx.spawn(|_, mut cx| async move {
+                                if answer.await == Ok(0) {
+                                    cx.update(|cx| SettingsHistory::revert_all(cx)).ok();
+                                }
+                            })
+                            .detach();
+                        })),
+                ),
+        )
+    }
+
+    fn render_search(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let settings = ThemeSettings::get_global(cx);
+        let text_style = TextStyle {
+            color: cx.theme().colors().text,
+            font_family: settings.ui_font.family.clone(),
+            font_features: settings.ui_font.features.clone(),
+            font_fallbacks: settings.ui_font.fallbacks.clone(),
+            font_size: rems(0.875).into(),
+            font_weight: settings.ui_font.weight,
+            line_height: relative(1.3),
+            ..Default::default()
+        };
+
+        let focus_ring = self
+            .query_editor
+            .read(cx)
+            .focus_handle(cx)
+            .is_focused(cx)
+            .then(|| {
+                let color = cx.theme().colors().focus_ring;
+                let width = cx.theme().focus_ring_width();
+                let corner_radius = rems(0.5).to_pixels(cx.rem_size());
+                canvas(
+                    |_, _| {},
+                    move |bounds, _, cx| cx.paint_focus_ring(bounds, corner_radius, width, color),
+                )
+                .absolute()
+                .top_0()
+                .left_0()
+                .size_full()
+            });
+
+        h_flex()
+            .relative()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .rounded_lg()
+            .child(Icon::new(IconName::MagnifyingGlass))
+            .child(EditorElement::new(
+                &self.query_editor,
+                EditorStyle {
+                    background: cx.theme().colors().editor_background,
+                    local_player: cx.theme().players().local(),
+                    text: text_style,
+                    ..Default::default()
+                },
+            ))
+            .children(focus_ring)
+    }
+}
+
+/// Returns the query that should be used to filter the settings in the given section.
+///
+/// A query matching the name of the section shows every setting in it.
+fn section_query(section: &str, query: &str) -> String {
+    if section.to_lowercase().contains(&query.to_lowercase()) {
+        String::new()
+    } else {
+        query.to_string()
+    }
 }
 
 impl EventEmitter<ItemEvent> for SettingsPage {}
 
 impl FocusableView for SettingsPage {
-    fn focus_handle(&self, _cx: &AppContext) -> FocusHandle {
-        self.focus_handle.clone()
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.query_editor.read(cx).focus_handle(cx)
     }
 }
 
@@ -101,24 +502,223 @@ impl Item for SettingsPage {
 
 impl Render for SettingsPage {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let query = self.query_editor.read(cx).text(cx);
+        let query = query.trim();
+        let active_tab = self.active_tab;
+        let tab_query = section_query(active_tab.label(), query);
+        let scroll_handle = self
+            .scroll_handles
+            .entry(active_tab)
+            .or_insert_with(ScrollHandle::new)
+            .clone();
+        let group_order = self
+            .group_orders
+            .entry(active_tab)
+            .or_insert_with(|| {
+                SettingsGroupOrder::new(cx.listener(|_, _: &[SharedString], cx| cx.notify()))
+            })
+            .clone();
+        let highlighted_group = self.highlighted_group.clone();
+        // The settings register themselves with the focus as they're rendered below.
+        let settings_focus = self.settings_focus.clone();
+        settings_focus.clear_settings();
+        settings_focus.track_scroll(&scroll_handle);
+        // The group only needs to be scrolled to once, after which it can be scrolled away from.
+        let revealed_group = self.revealed_group.take();
+        let settings = match active_tab {
+            SettingsTab::Appearance => {
+                let settings = AppearanceSettingsControls::new()
+                    .query(tab_query)
+                    .group_order(group_order)
+                    .collapsed_groups(self.collapsed_groups.clone())
+                    .when_some(highlighted_group, |this, group_id| {
+                        this.highlighted_group(group_id)
+                    })
+                    .when_some(revealed_group, |this, group_id| {
+                        this.reveal_group(group_id, scroll_handle.clone())
+                    })
+                    .on_toggle_group(cx.listener(Self::toggle_group))
+                    .focus(settings_focus.clone())
+                    .cache(self.settings_generation);
+                settings.has_matches().then(|| settings.into_any_element())
+            }
+            SettingsTab::Editor => {
+                let settings = EditorSettingsControls::new()
+                    .query(tab_query)
+                    .group_order(group_order)
+                    .collapsed_groups(self.collapsed_groups.clone())
+                    .when_some(highlighted_group, |this, group_id| {
+                        this.highlighted_group(group_id)
+                    })
+                    .when_some(revealed_group, |this, group_id| {
+                        this.reveal_group(group_id, scroll_handle.clone())
+                    })
+                    .on_toggle_group(cx.listener(Self::toggle_group))
+                    .focus(settings_focus.clone())
+                    .cache(self.settings_generation);
+                settings.has_matches().then(|| settings.into_any_element())
+            }
+            SettingsTab::Journal => {
+                let settings = JournalSettingsControls::new()
+                    .query(tab_query)
+                    .collapsed_groups(self.collapsed_groups.clone())
+                    .when_some(highlighted_group, |this, group_id| {
+                        this.highlighted_group(group_id)
+                    })
+                    .when_some(revealed_group, |this, group_id| {
+                        this.reveal_group(group_id, scroll_handle.clone())
+                    })
+                    .on_toggle_group(cx.listener(Self::toggle_group))
+                    .focus(settings_focus.clone());
+                settings.has_matches().then(|| settings.into_any_element())
+            }
+        };
+
         v_flex()
+            .key_context("SettingsPage")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::undo))
+            .on_action(cx.listener(Self::redo))
+            .on_action(cx.listener(Self::select_next_setting))
+            .on_action(cx.listener(Self::select_previous_setting))
+            .on_action(cx.listener(Self::focus_next_setting))
+            .on_action(cx.listener(Self::focus_previous_setting))
+            .on_action(cx.listener(Self::toggle_setting))
             .p_4()
             .size_full()
             .gap_4()
             .child(Label::new("Settings").size(LabelSize::Large))
+            .child(self.render_search(cx))
+            .children(self.render_changes(cx))
+            .child(self.render_tabs(query, cx))
             .child(
-                v_flex().gap_1().child(Label::new("Appearance")).child(
-                    v_flex()
-                        .elevation_2(cx)
-                        .child(AppearanceSettingsControls::new()),
-                ),
-            )
-            .child(
-                v_flex().gap_1().child(Label::new("Editor")).child(
-                    v_flex()
-                        .elevation_2(cx)
-                        .child(EditorSettingsControls::new()),
-                ),
+                div()
+                    .relative()
+                    .flex_1()
+                    .child(
+                        v_flex()
+                            .id(SharedString::from(format!(
+                                "settings-tab-content-{}",
+                                active_tab.label()
+                            )))
+                            .key_context("SettingsList")
+                            .track_focus(settings_focus.focus_handle())
+                            .on_key_down(cx.listener(Self::type_into_search))
+                            .size_full()
+                            .overflow_y_scroll()
+                            .track_scroll(&scroll_handle)
+                            .map(|this| match settings {
+                                Some(settings) => {
+                                    this.child(v_flex().elevation_2(cx).child(settings))
+                                }
+                                None => this.child(
+                                    Label::new("No settings match your search.")
+                                        .color(Color::Muted),
+                                ),
+                            }),
+                    )
+                    .child(Scrollbar::vertical(
+                        "settings-scrollbar",
+                        scroll_handle.clone(),
+                    )),
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use editor::EditorSettings;
+    use fs::{FakeFs, Fs};
+    use gpui::{Action, TestAppContext, VisualTestContext};
+    use journal::JournalSettings;
+    use project::Project;
+    use serde_json::json;
+    use settings::{handle_settings_file_changes, watch_config_file};
+    use workspace::AppState;
+
+    use super::*;
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<FakeFs> {
+        let fs = FakeFs::new(cx.executor());
+        cx.update(|cx| {
+            AppState::test(cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            JournalSettings::register(cx);
+            <dyn Fs>::set_global(fs.clone(), cx);
+        });
+        fs
+    }
+
+    fn focused_setting(page: &View<SettingsPage>, cx: &mut VisualTestContext) -> Option<ElementId> {
+        page.update(cx, |page, _| page.settings_focus.focused())
+    }
+
+    fn dispatch_times(action: impl Action + Clone, times: usize, cx: &mut VisualTestContext) {
+        for _ in 0..times {
+            cx.dispatch_action(action.clone());
+        }
+    }
+
+    #[gpui::test]
+    async fn test_moving_the_focus_and_toggling_settings(cx: &mut TestAppContext) {
+        let fs = init_test(cx);
+        fs.insert_tree(paths::config_dir(), json!({ "settings.json": "{}" }))
+            .await;
+        cx.update(|cx| {
+            let settings_rx = watch_config_file(
+                cx.background_executor(),
+                fs.clone(),
+                paths::settings_file().clone(),
+            );
+            handle_settings_file_changes(settings_rx, cx);
+        });
+
+        let project = Project::test(fs.clone(), [], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+        let page = workspace.update(cx, |workspace, cx| {
+            let page = SettingsPage::new(workspace, cx);
+            workspace.add_item_to_active_pane(Box::new(page.clone()), None, true, cx);
+            page
+        });
+        page.update(cx, |page, cx| page.activate_tab(SettingsTab::Editor, cx));
+        cx.run_until_parked();
+        assert_eq!(focused_setting(&page, cx), None);
+
+        // Moving down from the search field focuses the first group's header, then its items, and
+        // then the header of the next group.
+        cx.dispatch_action(SelectNextSetting);
+        assert_eq!(focused_setting(&page, cx), Some("editor-font".into()));
+        assert!(page.update(cx, |page, cx| {
+            page.settings_focus.is_focused(&"editor-font".into(), cx)
+        }));
+        dispatch_times(SelectNextSetting, 4, cx);
+        assert_eq!(
+            focused_setting(&page, cx),
+            Some("buffer-font-ligatures".into())
+        );
+        cx.dispatch_action(SelectNextSetting);
+        assert_eq!(focused_setting(&page, cx), Some("editor-editor".into()));
+        dispatch_times(SelectPreviousSetting, 5, cx);
+        assert_eq!(focused_setting(&page, cx), Some("editor-font".into()));
+
+        // Tab wraps around from the last setting to the first, and Shift-Tab back.
+        cx.dispatch_action(FocusPreviousSetting);
+        assert_eq!(focused_setting(&page, cx), Some("scrollbar-markers".into()));
+        cx.dispatch_action(FocusNextSetting);
+        assert_eq!(focused_setting(&page, cx), Some("editor-font".into()));
+
+        // Activating a checkbox toggles its setting.
+        dispatch_times(SelectNextSetting, 10, cx);
+        assert_eq!(focused_setting(&page, cx), Some("line-numbers".into()));
+        assert!(cx.update(|cx| EditorSettings::get_global(cx).gutter.line_numbers));
+        cx.dispatch_action(ToggleSetting);
+        cx.run_until_parked();
+        assert!(!cx.update(|cx| EditorSettings::get_global(cx).gutter.line_numbers));
+        assert_eq!(focused_setting(&page, cx), Some("line-numbers".into()));
+    }
+}
@@ -21,6 +21,7 @@ impl ThemeColors {
             border: neutral().light().step_6(),
             border_variant: neutral().light().step_5(),
             border_focused: blue().light().step_5(),
+            focus_ring: blue().light().step_8(),
             border_selected: blue().light().step_5(),
             border_transparent: system.transparent,
             border_disabled: neutral().light().step_3(),
@@ -119,6 +120,7 @@ impl ThemeColors {
             border: neutral().dark().step_6(),
             border_variant: neutral().dark().step_5(),
             border_focused: blue().dark().step_5(),
+            focus_ring: blue().dark().step_8(),
             border_selected: blue().dark().step_5(),
             border_transparent: system.transparent,
             border_disabled: neutral().dark().step_3(),
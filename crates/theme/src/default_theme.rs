@@ -8,7 +8,7 @@ use crate::{
     default_color_scales,
     one_themes::{one_dark, one_family},
     Appearance, PlayerColors, StatusColors, SyntaxTheme, SystemColors, Theme, ThemeColors,
-    ThemeFamily, ThemeStyles,
+    ThemeFamily, ThemeStyles, DEFAULT_FOCUS_RING_WIDTH,
 };
 
 fn zed_pro_daylight() -> Theme {
@@ -18,6 +18,7 @@ fn zed_pro_daylight() -> Theme {
         appearance: Appearance::Light,
         styles: ThemeStyles {
             window_background_appearance: WindowBackgroundAppearance::Opaque,
+            focus_ring_width: DEFAULT_FOCUS_RING_WIDTH,
             system: SystemColors::default(),
             colors: ThemeColors::light(),
             status: StatusColors::light(),
@@ -35,6 +36,7 @@ pub(crate) fn zed_pro_moonlight() -> Theme {
         appearance: Appearance::Dark,
         styles: ThemeStyles {
             window_background_appearance: WindowBackgroundAppearance::Opaque,
+            focus_ring_width: DEFAULT_FOCUS_RING_WIDTH,
             system: SystemColors::default(),
             colors: ThemeColors::dark(),
             status: StatusColors::dark(),
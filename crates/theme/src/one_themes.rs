@@ -4,7 +4,7 @@ use gpui::{hsla, FontStyle, FontWeight, HighlightStyle, WindowBackgroundAppearan
 
 use crate::{
     default_color_scales, AccentColors, Appearance, PlayerColors, StatusColors, SyntaxTheme,
-    SystemColors, Theme, ThemeColors, ThemeFamily, ThemeStyles,
+    SystemColors, Theme, ThemeColors, ThemeFamily, ThemeStyles, DEFAULT_FOCUS_RING_WIDTH,
 };
 
 // Note: This theme family is not the one you see in Zed at the moment.
@@ -41,12 +41,14 @@ pub(crate) fn one_dark() -> Theme {
         appearance: Appearance::Dark,
         styles: ThemeStyles {
             window_background_appearance: WindowBackgroundAppearance::Opaque,
+            focus_ring_width: DEFAULT_FOCUS_RING_WIDTH,
             system: SystemColors::default(),
             accents: AccentColors(vec![blue, orange, purple, teal, red, green, yellow]),
             colors: ThemeColors {
                 border: hsla(225. / 360., 13. / 100., 12. / 100., 1.),
                 border_variant: hsla(228. / 360., 8. / 100., 25. / 100., 1.),
                 border_focused: hsla(223. / 360., 78. / 100., 65. / 100., 1.),
+                focus_ring: hsla(223. / 360., 78. / 100., 65. / 100., 1.),
                 border_selected: hsla(222.6 / 360., 77.5 / 100., 65.1 / 100., 1.0),
                 border_transparent: SystemColors::default().transparent,
                 border_disabled: hsla(222.0 / 360., 11.6 / 100., 33.7 / 100., 1.0),
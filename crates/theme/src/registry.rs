@@ -6,7 +6,7 @@ use collections::HashMap;
 use derive_more::{Deref, DerefMut};
 use fs::Fs;
 use futures::StreamExt;
-use gpui::{AppContext, AssetSource, Global, HighlightStyle, SharedString};
+use gpui::{px, AppContext, AssetSource, Global, HighlightStyle, SharedString};
 use parking_lot::RwLock;
 use refineable::Refineable;
 use util::ResultExt;
@@ -14,7 +14,7 @@ use util::ResultExt;
 use crate::{
     try_parse_color, AccentColors, Appearance, AppearanceContent, PlayerColors, StatusColors,
     SyntaxTheme, SystemColors, Theme, ThemeColors, ThemeContent, ThemeFamily, ThemeFamilyContent,
-    ThemeStyles,
+    ThemeStyles, DEFAULT_FOCUS_RING_WIDTH,
 };
 
 #[derive(Debug, Clone)]
@@ -156,6 +156,11 @@ impl ThemeRegistry {
                 .window_background_appearance
                 .map(Into::into)
                 .unwrap_or_default();
+            let focus_ring_width = user_theme
+                .style
+                .focus_ring_width
+                .map(px)
+                .unwrap_or(DEFAULT_FOCUS_RING_WIDTH);
 
             Theme {
                 id: uuid::Uuid::new_v4().to_string(),
@@ -167,6 +172,7 @@ impl ThemeRegistry {
                 styles: ThemeStyles {
                     system: SystemColors::default(),
                     window_background_appearance,
+                    focus_ring_width,
                     accents: accent_colors,
                     colors: theme_colors,
                     status: status_colors,
@@ -75,6 +75,10 @@ pub struct ThemeStyleContent {
     #[serde(default, rename = "background.appearance")]
     pub window_background_appearance: Option<WindowBackgroundContent>,
 
+    /// The width of the ring drawn around the element that has keyboard focus, in pixels.
+    #[serde(default, rename = "focus_ring.width")]
+    pub focus_ring_width: Option<f32>,
+
     #[serde(default)]
     pub accents: Vec<AccentContent>,
 
@@ -150,6 +154,10 @@ pub struct ThemeColorsContent {
     #[serde(rename = "border.focused")]
     pub border_focused: Option<String>,
 
+    /// Border color. Used for the ring drawn around the element that has keyboard focus.
+    #[serde(rename = "focus_ring")]
+    pub focus_ring: Option<String>,
+
     /// Border color. Used for selected elements, like an active search filter or selected checkbox.
     #[serde(rename = "border.selected")]
     pub border_selected: Option<String>,
@@ -550,6 +558,10 @@ impl ThemeColorsContent {
                 .border_focused
                 .as_ref()
                 .and_then(|color| try_parse_color(color).ok()),
+            focus_ring: self
+                .focus_ring
+                .as_ref()
+                .and_then(|color| try_parse_color(color).ok()),
             border_selected: self
                 .border_selected
                 .as_ref()
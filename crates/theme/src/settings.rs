@@ -398,6 +398,10 @@ impl ThemeSettings {
                     window_background_appearance.into();
             }
 
+            if let Some(focus_ring_width) = theme_overrides.focus_ring_width {
+                base_theme.styles.focus_ring_width = px(focus_ring_width);
+            }
+
             base_theme
                 .styles
                 .colors
@@ -1,4 +1,4 @@
-use gpui::{Hsla, WindowBackgroundAppearance};
+use gpui::{Hsla, Pixels, WindowBackgroundAppearance};
 use refineable::Refineable;
 use std::sync::Arc;
 
@@ -15,6 +15,8 @@ pub struct ThemeColors {
     pub border_variant: Hsla,
     /// Border color. Used for focused elements, like keyboard focused list item.
     pub border_focused: Hsla,
+    /// Border color. Used for the ring drawn around the element that has keyboard focus.
+    pub focus_ring: Hsla,
     /// Border color. Used for selected elements, like an active search filter or selected checkbox.
     pub border_selected: Hsla,
     /// Border color. Used for transparent borders. Used for placeholder borders when an element gains a border on state change.
@@ -249,6 +251,9 @@ pub struct ThemeStyles {
     /// Example: Player colors, rainbow brackets and indent guides, etc.
     pub accents: AccentColors,
 
+    /// The width of the ring drawn around the element that has keyboard focus.
+    pub focus_ring_width: Pixels,
+
     #[refineable]
     pub colors: ThemeColors,
 
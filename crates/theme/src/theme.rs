@@ -43,6 +43,8 @@ pub enum Appearance {
 
 pub const CLIENT_SIDE_DECORATION_ROUNDING: Pixels = px(10.0);
 pub const CLIENT_SIDE_DECORATION_SHADOW: Pixels = px(10.0);
+/// The width of the focus ring for themes that don't specify one.
+pub const DEFAULT_FOCUS_RING_WIDTH: Pixels = px(2.0);
 
 impl Appearance {
     pub fn is_light(&self) -> bool {
@@ -179,6 +181,12 @@ impl Theme {
     pub fn window_background_appearance(&self) -> WindowBackgroundAppearance {
         self.styles.window_background_appearance
     }
+
+    /// Returns the width of the ring drawn around the element that has keyboard focus.
+    #[inline(always)]
+    pub fn focus_ring_width(&self) -> Pixels {
+        self.styles.focus_ring_width
+    }
 }
 
 pub fn color_alpha(color: Hsla, alpha: f32) -> Hsla {
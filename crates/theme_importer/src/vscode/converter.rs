@@ -57,6 +57,7 @@ impl VsCodeThemeConverter {
             appearance,
             style: ThemeStyleContent {
                 window_background_appearance: Some(theme::WindowBackgroundContent::Opaque),
+                focus_ring_width: None,
                 accents: Vec::new(), //TODO can we read this from the theme?
                 colors: theme_colors,
                 status: status_colors,
@@ -128,6 +129,7 @@ impl VsCodeThemeConverter {
             border: vscode_panel_border.clone(),
             border_variant: vscode_panel_border.clone(),
             border_focused: vscode_colors.focus_border.clone(),
+            focus_ring: vscode_colors.focus_border.clone(),
             border_selected: vscode_panel_border.clone(),
             border_transparent: vscode_panel_border.clone(),
             border_disabled: vscode_panel_border.clone(),
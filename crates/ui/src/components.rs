@@ -1,6 +1,8 @@
 mod avatar;
+mod badge;
 mod button;
 mod checkbox;
+mod color_picker;
 mod context_menu;
 mod disclosure;
 mod divider;
@@ -9,17 +11,25 @@ mod facepile;
 mod icon;
 mod indicator;
 mod keybinding;
+mod keystroke_input;
 mod label;
 mod list;
 mod modal;
+mod number_input;
 mod numeric_stepper;
+mod path_picker;
 mod popover;
 mod popover_menu;
 mod radio;
 mod right_click_menu;
+mod scrollbar;
 mod settings_container;
+mod settings_focus;
 mod settings_group;
+mod settings_item;
+mod slider;
 mod stack;
+mod switch;
 mod tab;
 mod tab_bar;
 mod tool_strip;
@@ -29,8 +39,10 @@ mod tooltip;
 mod stories;
 
 pub use avatar::*;
+pub use badge::*;
 pub use button::*;
 pub use checkbox::*;
+pub use color_picker::*;
 pub use context_menu::*;
 pub use disclosure::*;
 pub use divider::*;
@@ -39,17 +51,25 @@ pub use facepile::*;
 pub use icon::*;
 pub use indicator::*;
 pub use keybinding::*;
+pub use keystroke_input::*;
 pub use label::*;
 pub use list::*;
 pub use modal::*;
+pub use number_input::*;
 pub use numeric_stepper::*;
+pub use path_picker::*;
 pub use popover::*;
 pub use popover_menu::*;
 pub use radio::*;
 pub use right_click_menu::*;
+pub use scrollbar::*;
 pub use settings_container::*;
+pub use settings_focus::*;
 pub use settings_group::*;
+pub use settings_item::*;
+pub use slider::*;
 pub use stack::*;
+pub use switch::*;
 pub use tab::*;
 pub use tab_bar::*;
 pub use tool_strip::*;
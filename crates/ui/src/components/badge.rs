@@ -0,0 +1,69 @@
+use crate::prelude::*;
+
+/// The semantic color of a [`Badge`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum BadgeColor {
+    /// For neutral annotations.
+    #[default]
+    Info,
+    /// For recently added features, e.g. "New".
+    Success,
+    /// For experimental or unstable features, e.g. "Beta".
+    Warning,
+}
+
+/// # Badge
+///
+/// A small pill used to annotate an element, such as a setting that was recently added or is
+/// still experimental.
+#[derive(IntoElement)]
+pub struct Badge {
+    label: SharedString,
+    color: BadgeColor,
+}
+
+impl Badge {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            color: BadgeColor::default(),
+        }
+    }
+
+    pub fn color(mut self, color: BadgeColor) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl RenderOnce for Badge {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let status = cx.theme().status();
+        let (text_color, background, border) = match self.color {
+            BadgeColor::Info => (Color::Info, status.info_background, status.info_border),
+            BadgeColor::Success => (
+                Color::Success,
+                status.success_background,
+                status.success_border,
+            ),
+            BadgeColor::Warning => (
+                Color::Warning,
+                status.warning_background,
+                status.warning_border,
+            ),
+        };
+
+        div()
+            .flex_none()
+            .px_1()
+            .rounded_full()
+            .border_1()
+            .border_color(border)
+            .bg(background)
+            .child(
+                Label::new(self.label)
+                    .size(LabelSize::XSmall)
+                    .color(text_color),
+            )
+    }
+}
@@ -106,7 +106,11 @@ impl RenderOnce for Checkbox {
             )
             .when_some(
                 self.on_click.filter(|_| !self.disabled),
-                |this, on_click| this.on_click(move |_, cx| on_click(&self.checked.inverse(), cx)),
+                |this, on_click| {
+                    // The box is small, so clicks just outside of it toggle it too.
+                    this.hit_area_outset(Spacing::Small.px(cx))
+                        .on_click(move |_, cx| on_click(&self.checked.inverse(), cx))
+                },
             )
     }
 }
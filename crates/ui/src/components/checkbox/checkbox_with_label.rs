@@ -1,13 +1,18 @@
 use std::sync::Arc;
 
-use crate::{prelude::*, Checkbox};
+use gpui::{AccessibilityNode, AccessibilityRole};
+
+use crate::{prelude::*, Checkbox, HighlightedLabel};
 
 /// A [`Checkbox`] that has a [`Label`].
 #[derive(IntoElement)]
 pub struct CheckboxWithLabel {
     id: ElementId,
     label: Label,
+    label_highlights: Vec<usize>,
+    icon: Option<IconName>,
     checked: Selection,
+    disabled: bool,
     on_click: Arc<dyn Fn(&Selection, &mut WindowContext) + 'static>,
 }
 
@@ -21,29 +26,83 @@ impl CheckboxWithLabel {
         Self {
             id: id.into(),
             label,
+            label_highlights: Vec::new(),
+            icon: None,
             checked,
+            disabled: false,
             on_click: Arc::new(on_click),
         }
     }
+
+    /// Highlights the characters of the label at the given byte positions, such as those matching
+    /// a search query.
+    pub fn label_highlights(mut self, label_highlights: Vec<usize>) -> Self {
+        self.label_highlights = label_highlights;
+        self
+    }
+
+    /// Sets an icon to show before the label, such as one hinting at what the setting affects.
+    pub fn icon(mut self, icon: IconName) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+}
+
+impl Disableable for CheckboxWithLabel {
+    fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
 }
 
 impl RenderOnce for CheckboxWithLabel {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let color = if self.disabled {
+            Color::Disabled
+        } else {
+            Color::Muted
+        };
+
+        let accessibility = AccessibilityNode::new(AccessibilityRole::Checkbox, self.label.text())
+            .checked(match self.checked {
+                Selection::Selected => Some(true),
+                Selection::Unselected => Some(false),
+                Selection::Indeterminate => None,
+            })
+            .disabled(self.disabled);
+
+        // The whole row toggles the checkbox, so that clicks on the label or between it and the
+        // checkbox aren't lost.
         h_flex()
+            .id(SharedString::from(format!("{}-row", self.id)))
             .gap(Spacing::Large.rems(cx))
-            .child(Checkbox::new(self.id.clone(), self.checked).on_click({
+            .accessibility(accessibility)
+            .when(!self.disabled, |this| {
                 let on_click = self.on_click.clone();
-                move |checked, cx| {
-                    (on_click)(checked, cx);
-                }
-            }))
+                let checked = self.checked;
+                this.cursor_pointer()
+                    .on_click(move |_event, cx| (on_click)(&checked.inverse(), cx))
+            })
+            .child(Checkbox::new(self.id.clone(), self.checked).disabled(self.disabled))
             .child(
-                div()
-                    .id(SharedString::from(format!("{}-label", self.id)))
-                    .on_click(move |_event, cx| {
-                        (self.on_click)(&self.checked.inverse(), cx);
+                h_flex()
+                    .gap(Spacing::Medium.rems(cx))
+                    .when_some(self.icon, |this, icon| {
+                        this.child(Icon::new(icon).size(IconSize::Small).color(color))
                     })
-                    .child(self.label),
+                    .map(|this| {
+                        if self.label_highlights.is_empty() {
+                            this.child(
+                                self.label
+                                    .when(self.disabled, |label| label.color(Color::Disabled)),
+                            )
+                        } else {
+                            this.child(
+                                HighlightedLabel::new(self.label.text(), self.label_highlights)
+                                    .when(self.disabled, |label| label.color(Color::Disabled)),
+                            )
+                        }
+                    }),
             )
     }
 }
@@ -0,0 +1,113 @@
+use std::rc::Rc;
+
+use gpui::Hsla;
+
+use crate::{prelude::*, Slider};
+
+/// # ColorPicker
+///
+/// Shows a swatch of an HSLA color alongside its hex value, with sliders for adjusting its hue,
+/// saturation, and lightness.
+#[derive(IntoElement)]
+pub struct ColorPicker {
+    id: ElementId,
+    color: Hsla,
+    disabled: bool,
+    on_change: Option<Rc<dyn Fn(Hsla, &mut WindowContext) + 'static>>,
+}
+
+impl ColorPicker {
+    pub fn new(id: impl Into<ElementId>, color: impl Into<Hsla>) -> Self {
+        Self {
+            id: id.into(),
+            color: color.into(),
+            disabled: false,
+            on_change: None,
+        }
+    }
+
+    pub fn on_change(mut self, handler: impl Fn(Hsla, &mut WindowContext) + 'static) -> Self {
+        self.on_change = Some(Rc::new(handler));
+        self
+    }
+
+    fn render_slider(
+        &self,
+        name: &str,
+        label: &'static str,
+        value: f32,
+        max: f32,
+        update: impl Fn(Hsla, f32) -> Hsla + 'static,
+    ) -> impl IntoElement {
+        let color = self.color;
+
+        h_flex()
+            .gap_2()
+            .child(
+                div()
+                    .w(rems(1.))
+                    .child(Label::new(label).size(LabelSize::Small)),
+            )
+            .child(
+                Slider::new(
+                    SharedString::from(format!("{}-{}", self.id, name)),
+                    (value * max).round(),
+                    0.,
+                    max,
+                    1.,
+                )
+                .disabled(self.disabled)
+                .when_some(self.on_change.clone(), |this, on_change| {
+                    this.on_change(move |value, cx| on_change(update(color, value / max), cx))
+                }),
+            )
+    }
+}
+
+impl Disableable for ColorPicker {
+    fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+impl RenderOnce for ColorPicker {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let color = self.color;
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .size_5()
+                            .rounded_md()
+                            .border_1()
+                            .border_color(cx.theme().colors().border)
+                            .bg(color),
+                    )
+                    .child(
+                        Label::new(color.to_rgb().to_hex_string()).color(if self.disabled {
+                            Color::Disabled
+                        } else {
+                            Color::Default
+                        }),
+                    ),
+            )
+            .child(self.render_slider("hue", "H", color.h, 360., |color, h| Hsla { h, ..color }))
+            .child(
+                self.render_slider("saturation", "S", color.s, 100., |color, s| Hsla {
+                    s,
+                    ..color
+                }),
+            )
+            .child(
+                self.render_slider("lightness", "L", color.l, 100., |color, l| Hsla {
+                    l,
+                    ..color
+                }),
+            )
+    }
+}
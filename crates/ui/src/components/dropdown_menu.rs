@@ -1,12 +1,13 @@
 use gpui::{AnchorCorner, ClickEvent, CursorStyle, MouseButton, View};
 
-use crate::{prelude::*, ContextMenu, PopoverMenu};
+use crate::{prelude::*, ContextMenu, PopoverMenu, PopoverMenuHandle};
 
 #[derive(IntoElement)]
 pub struct DropdownMenu {
     id: ElementId,
     label: SharedString,
     menu: View<ContextMenu>,
+    handle: Option<PopoverMenuHandle<ContextMenu>>,
     full_width: bool,
     disabled: bool,
 }
@@ -21,6 +22,7 @@ impl DropdownMenu {
             id: id.into(),
             label: label.into(),
             menu,
+            handle: None,
             full_width: false,
             disabled: false,
         }
@@ -30,6 +32,13 @@ impl DropdownMenu {
         self.full_width = full_width;
         self
     }
+
+    /// Lets the menu be opened with the given handle, such as from the keyboard, unless the
+    /// dropdown is disabled.
+    pub fn with_handle(mut self, handle: PopoverMenuHandle<ContextMenu>) -> Self {
+        self.handle = Some(handle);
+        self
+    }
 }
 
 impl Disableable for DropdownMenu {
@@ -46,6 +55,12 @@ impl RenderOnce for DropdownMenu {
             .menu(move |_cx| Some(self.menu.clone()))
             .trigger(DropdownMenuTrigger::new(self.label).full_width(self.full_width))
             .attach(AnchorCorner::BottomLeft)
+            // Lists of options can be long, so they're shown in a window of their own, which can
+            // extend past the bounds of the trigger's window.
+            .in_window(true)
+            .when_some(self.handle.filter(|_| !self.disabled), |this, handle| {
+                this.with_handle(handle)
+            })
     }
 }
 
@@ -0,0 +1,154 @@
+use std::slice;
+
+use gpui::{
+    AppContext, EventEmitter, FocusHandle, FocusableView, KeyDownEvent, Keystroke, Render,
+    Subscription,
+};
+
+use crate::prelude::*;
+
+/// Emitted by a [`KeystrokeInput`] when it records a new keystroke.
+pub struct KeystrokeRecorded(pub Keystroke);
+
+/// # KeystrokeInput
+///
+/// Records a shortcut. Once clicked, the next keystroke is captured, without triggering the
+/// action it's bound to, and shown as a chord such as "⌘K". Pressing escape cancels recording.
+///
+/// Warns when the recorded keystroke is already bound to an action.
+pub struct KeystrokeInput {
+    focus_handle: FocusHandle,
+    keystroke: Option<Keystroke>,
+    recording: bool,
+    _on_blur_subscription: Subscription,
+}
+
+impl KeystrokeInput {
+    pub fn new(keystroke: Option<Keystroke>, cx: &mut ViewContext<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+        let _on_blur_subscription = cx.on_blur(&focus_handle, |this: &mut Self, cx| {
+            this.cancel_recording(cx)
+        });
+
+        Self {
+            focus_handle,
+            keystroke,
+            recording: false,
+            _on_blur_subscription,
+        }
+    }
+
+    pub fn keystroke(&self) -> Option<&Keystroke> {
+        self.keystroke.as_ref()
+    }
+
+    /// Returns the recorded keystroke in the syntax used by keymap files, such as `cmd-k`.
+    pub fn value(&self) -> Option<String> {
+        self.keystroke.as_ref().map(Keystroke::unparse)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Focuses the input and records the next keystroke.
+    pub fn start_recording(&mut self, cx: &mut ViewContext<Self>) {
+        self.recording = true;
+        cx.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    /// Stops recording, keeping the previously recorded keystroke.
+    pub fn cancel_recording(&mut self, cx: &mut ViewContext<Self>) {
+        if self.recording {
+            self.recording = false;
+            cx.notify();
+        }
+    }
+
+    fn handle_key_down(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
+        if !self.recording {
+            return;
+        }
+        cx.stop_propagation();
+
+        if event.keystroke.key == "escape" && !event.keystroke.modifiers.modified() {
+            self.cancel_recording(cx);
+            return;
+        }
+
+        let keystroke = Keystroke {
+            ime_key: None,
+            ..event.keystroke.clone()
+        };
+        self.keystroke = Some(keystroke.clone());
+        self.recording = false;
+        cx.emit(KeystrokeRecorded(keystroke));
+        cx.notify();
+    }
+
+    /// Returns the names of the actions already bound to the recorded keystroke.
+    fn conflicts(&self, cx: &AppContext) -> Vec<String> {
+        let Some(keystroke) = self.keystroke.as_ref() else {
+            return Vec::new();
+        };
+
+        let mut conflicts = Vec::new();
+        for binding in cx.bindings_for_keystrokes(slice::from_ref(keystroke)) {
+            let name = binding.action().name().to_string();
+            if !conflicts.contains(&name) {
+                conflicts.push(name);
+            }
+        }
+        conflicts
+    }
+}
+
+impl EventEmitter<KeystrokeRecorded> for KeystrokeInput {}
+
+impl FocusableView for KeystrokeInput {
+    fn focus_handle(&self, _cx: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for KeystrokeInput {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let (label, color): (SharedString, _) = if self.recording {
+            ("Press a shortcut…".into(), Color::Accent)
+        } else if let Some(keystroke) = self.keystroke.as_ref() {
+            (keystroke.to_string().into(), Color::Default)
+        } else {
+            ("None".into(), Color::Placeholder)
+        };
+        let conflicts = self.conflicts(cx);
+
+        v_flex()
+            .gap_1()
+            .child(
+                div()
+                    .id("keystroke-input")
+                    .track_focus(&self.focus_handle)
+                    .capture_keystrokes(self.recording)
+                    .on_key_down(cx.listener(Self::handle_key_down))
+                    .on_click(cx.listener(|this, _, cx| this.start_recording(cx)))
+                    .px_2()
+                    .py_0p5()
+                    .rounded_md()
+                    .border_1()
+                    .border_color(if self.recording {
+                        cx.theme().colors().border_focused
+                    } else {
+                        cx.theme().colors().border
+                    })
+                    .child(Label::new(label).size(LabelSize::Small).color(color)),
+            )
+            .when(!conflicts.is_empty(), |this| {
+                this.child(
+                    Label::new(format!("Also bound to {}.", conflicts.join(", ")))
+                        .size(LabelSize::Small)
+                        .color(Color::Warning),
+                )
+            })
+    }
+}
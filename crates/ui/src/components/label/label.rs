@@ -68,6 +68,11 @@ impl Label {
         self.single_line = true;
         self
     }
+
+    /// Returns the text of the label.
+    pub(crate) fn text(&self) -> SharedString {
+        self.label.clone()
+    }
 }
 
 // Style methods.
@@ -1,12 +1,16 @@
 use std::sync::Arc;
 
-use crate::{h_flex, prelude::*, Disclosure, Label};
+use crate::{h_flex, prelude::*, Disclosure, HighlightedLabel, Label};
 use gpui::{AnyElement, ClickEvent};
 
 #[derive(IntoElement)]
 pub struct ListHeader {
     /// The label of the header.
     label: SharedString,
+    label_color: Color,
+    /// The byte positions of the characters of the label to highlight, such as those matching a
+    /// search query.
+    label_highlights: Vec<usize>,
     /// A slot for content that appears before the label, like an icon or avatar.
     start_slot: Option<AnyElement>,
     /// A slot for content that appears after the label, usually on the other side of the header.
@@ -25,6 +29,8 @@ impl ListHeader {
     pub fn new(label: impl Into<SharedString>) -> Self {
         Self {
             label: label.into(),
+            label_color: Color::Muted,
+            label_highlights: Vec::new(),
             start_slot: None,
             end_slot: None,
             end_hover_slot: None,
@@ -35,6 +41,18 @@ impl ListHeader {
         }
     }
 
+    /// Sets the color of the label, which is muted by default.
+    pub fn label_color(mut self, label_color: Color) -> Self {
+        self.label_color = label_color;
+        self
+    }
+
+    /// Highlights the characters of the label at the given byte positions.
+    pub fn label_highlights(mut self, label_highlights: Vec<usize>) -> Self {
+        self.label_highlights = label_highlights;
+        self
+    }
+
     pub fn toggle(mut self, toggle: impl Into<Option<bool>>) -> Self {
         self.toggle = toggle.into();
         self
@@ -109,7 +127,22 @@ impl RenderOnce for ListHeader {
                                     .gap_1()
                                     .items_center()
                                     .children(self.start_slot)
-                                    .child(Label::new(self.label.clone()).color(Color::Muted))
+                                    .map(|this| {
+                                        if self.label_highlights.is_empty() {
+                                            this.child(
+                                                Label::new(self.label.clone())
+                                                    .color(self.label_color),
+                                            )
+                                        } else {
+                                            this.child(
+                                                HighlightedLabel::new(
+                                                    self.label.clone(),
+                                                    self.label_highlights,
+                                                )
+                                                .color(self.label_color),
+                                            )
+                                        }
+                                    })
                                     .when_some(self.on_toggle, |this, on_toggle| {
                                         this.on_click(move |event, cx| on_toggle(event, cx))
                                     }),
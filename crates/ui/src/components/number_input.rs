@@ -0,0 +1,646 @@
+use std::ops::Range;
+use std::time::Duration;
+
+use gpui::{
+    canvas, fill, point, size, AppContext, Bounds, ElementInputHandler, EventEmitter, FocusHandle,
+    FocusableView, KeyDownEvent, MouseButton, Render, ShapedLine, Subscription, Task,
+    ViewInputHandler,
+};
+
+use crate::{prelude::*, IconButtonShape};
+
+/// How long a stepper button has to be held before it starts repeating.
+const REPEAT_DELAY: Duration = Duration::from_millis(400);
+/// How often a held stepper button steps the value once it's repeating.
+const REPEAT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Emitted by a [`NumberInput`] when its value changes, with the new value formatted to the
+/// input's precision.
+pub struct NumberChanged(pub SharedString);
+
+/// A value being typed in, which may contain text that's still being composed with an input
+/// method, such as the kana typed before they're converted to a number.
+#[derive(Debug, PartialEq)]
+struct Draft {
+    text: String,
+    /// Where the caret is in the text.
+    cursor: usize,
+    /// The part of the text being composed, which isn't part of the value until it's committed.
+    marked_range: Option<Range<usize>>,
+}
+
+impl Draft {
+    fn new(text: String) -> Self {
+        Self {
+            cursor: text.len(),
+            text,
+            marked_range: None,
+        }
+    }
+
+    /// Returns the value entered so far, without the text being composed.
+    fn committed_text(&self) -> String {
+        match &self.marked_range {
+            Some(range) => [&self.text[..range.start], &self.text[range.end..]].concat(),
+            None => self.text.clone(),
+        }
+    }
+
+    /// Deletes the character before the caret.
+    fn backspace(&mut self) {
+        if let Some((index, _)) = self.text[..self.cursor].char_indices().next_back() {
+            self.text.replace_range(index..self.cursor, "");
+            self.cursor = index;
+        }
+    }
+
+    /// Replaces the text being composed with `new_text`, or inserts it at the caret if nothing is
+    /// being composed. `new_cursor` is where the caret is within `new_text`.
+    fn compose(&mut self, range: Option<Range<usize>>, new_text: &str, new_cursor: Option<usize>) {
+        let range = range
+            .or(self.marked_range.clone())
+            .unwrap_or(self.cursor..self.cursor);
+        self.text.replace_range(range.clone(), new_text);
+        // Composing nothing cancels the composition, which leaves nothing behind.
+        self.marked_range =
+            (!new_text.is_empty()).then(|| range.start..range.start + new_text.len());
+        self.cursor =
+            range.start + new_cursor.map_or(new_text.len(), |cursor| cursor.min(new_text.len()));
+    }
+
+    /// Replaces the text being composed with `new_text`, or inserts it at the caret if nothing is
+    /// being composed, keeping only the characters that can be part of a number.
+    fn commit(&mut self, range: Option<Range<usize>>, new_text: &str) {
+        let range = range
+            .or(self.marked_range.take())
+            .unwrap_or(self.cursor..self.cursor);
+        self.marked_range = None;
+        let new_text = new_text.chars().filter_map(number_char).collect::<String>();
+        self.text.replace_range(range.clone(), &new_text);
+        self.cursor = range.start + new_text.len();
+    }
+
+    /// Commits the text being composed as it is.
+    fn unmark(&mut self) {
+        if let Some(range) = self.marked_range.clone() {
+            let text = self.text[range.clone()].to_string();
+            self.commit(Some(range), &text);
+        }
+    }
+
+    fn offset_from_utf16(&self, offset: usize) -> usize {
+        offset_from_utf16(&self.text, offset)
+    }
+
+    fn offset_to_utf16(&self, offset: usize) -> usize {
+        self.text[..offset].chars().map(char::len_utf16).sum()
+    }
+
+    fn range_from_utf16(&self, range: &Range<usize>) -> Range<usize> {
+        self.offset_from_utf16(range.start)..self.offset_from_utf16(range.end)
+    }
+
+    fn range_to_utf16(&self, range: &Range<usize>) -> Range<usize> {
+        self.offset_to_utf16(range.start)..self.offset_to_utf16(range.end)
+    }
+}
+
+fn offset_from_utf16(text: &str, offset: usize) -> usize {
+    let mut utf8_offset = 0;
+    let mut utf16_offset = 0;
+    for c in text.chars() {
+        if utf16_offset >= offset {
+            break;
+        }
+        utf16_offset += c.len_utf16();
+        utf8_offset += c.len_utf8();
+    }
+    utf8_offset
+}
+
+/// Returns the character as it's used in a number, if it can be, such as the digits that
+/// input methods for CJK languages type in full width.
+fn number_char(c: char) -> Option<char> {
+    match c {
+        '0'..='9' | '.' | '-' => Some(c),
+        '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32),
+        '．' => Some('.'),
+        '－' => Some('-'),
+        _ => None,
+    }
+}
+
+/// # NumberInput
+///
+/// Edits a number within `[min, max]`, shown with a fixed number of decimal places between
+/// buttons that decrement and increment it by `step`. Holding a button keeps stepping the value.
+///
+/// Clicking the value lets a new one be typed in, which is applied with enter and discarded with
+/// escape. Values that aren't numbers or are out of range are rejected, with an error shown
+/// beneath the input. Text composed with an input method is underlined until it's committed, and
+/// only becomes part of the value then.
+pub struct NumberInput {
+    focus_handle: FocusHandle,
+    value: SharedString,
+    min: f64,
+    max: f64,
+    step: f64,
+    precision: usize,
+    /// The text typed in so far, while a new value is being entered.
+    draft: Option<Draft>,
+    /// The layout of the draft when it was last painted, for placing input method windows.
+    last_draft_layout: Option<ShapedLine>,
+    /// Keeps stepping the value while a stepper button is held, until it's dropped.
+    repeat_task: Option<Task<()>>,
+    _on_blur_subscription: Subscription,
+}
+
+impl NumberInput {
+    pub fn new(
+        value: f64,
+        min: f64,
+        max: f64,
+        step: f64,
+        precision: usize,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let focus_handle = cx.focus_handle();
+        let _on_blur_subscription =
+            cx.on_blur(&focus_handle, |this: &mut Self, cx| this.confirm(cx));
+
+        Self {
+            focus_handle,
+            value: format_value(value.clamp(min, max), precision),
+            min,
+            max,
+            step,
+            precision,
+            draft: None,
+            last_draft_layout: None,
+            repeat_task: None,
+            _on_blur_subscription,
+        }
+    }
+
+    /// Returns the value, formatted to the input's precision.
+    pub fn value(&self) -> SharedString {
+        self.value.clone()
+    }
+
+    pub fn number(&self) -> f64 {
+        self.value.parse().unwrap_or(self.min)
+    }
+
+    /// Sets the value, clamped to the input's range, without emitting [`NumberChanged`].
+    pub fn set_number(&mut self, value: f64, cx: &mut ViewContext<Self>) {
+        self.value = format_value(value.clamp(self.min, self.max), self.precision);
+        cx.notify();
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.draft.is_some()
+    }
+
+    fn set_value(&mut self, value: SharedString, cx: &mut ViewContext<Self>) {
+        if value != self.value {
+            self.value = value.clone();
+            cx.emit(NumberChanged(value));
+        }
+        cx.notify();
+    }
+
+    fn step_by(&mut self, direction: f64, cx: &mut ViewContext<Self>) {
+        let value = stepped_value(
+            self.number(),
+            direction * self.step,
+            self.min,
+            self.max,
+            self.precision,
+        );
+        self.set_value(value, cx);
+    }
+
+    /// Steps the value in the given direction, `1.` to increment it and `-1.` to decrement it,
+    /// and keeps stepping it until [`Self::stop_repeating`] is called.
+    fn start_repeating(&mut self, direction: f64, cx: &mut ViewContext<Self>) {
+        self.draft = None;
+        self.step_by(direction, cx);
+        self.repeat_task = Some(cx.spawn(|this, mut cx| async move {
+            cx.background_executor().timer(REPEAT_DELAY).await;
+            loop {
+                if this
+                    .update(&mut cx, |this, cx| this.step_by(direction, cx))
+                    .is_err()
+                {
+                    break;
+                }
+                cx.background_executor().timer(REPEAT_INTERVAL).await;
+            }
+        }));
+    }
+
+    fn stop_repeating(&mut self) {
+        self.repeat_task = None;
+    }
+
+    /// Focuses the input and starts entering a new value, beginning with the current one.
+    pub fn start_editing(&mut self, cx: &mut ViewContext<Self>) {
+        self.draft = Some(Draft::new(self.value.to_string()));
+        cx.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    /// Applies the value entered so far, unless it's invalid, in which case it's discarded.
+    fn confirm(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(draft) = self.draft.take() else {
+            return;
+        };
+
+        let text = draft.committed_text();
+        if let Ok(value) = parse_value(&text, self.min, self.max, self.precision) {
+            self.set_value(value, cx);
+        } else {
+            cx.notify();
+        }
+    }
+
+    fn handle_key_down(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
+        // Keys pressed while composing text are left to the input method, and typed text arrives
+        // through the input handler.
+        let Some(draft) = self
+            .draft
+            .as_mut()
+            .filter(|draft| draft.marked_range.is_none())
+        else {
+            return;
+        };
+
+        match event.keystroke.key.as_str() {
+            "escape" => self.draft = None,
+            "enter" => {
+                // Invalid values are kept, along with their error, so that they can be corrected.
+                if let Ok(value) = parse_value(&draft.text, self.min, self.max, self.precision) {
+                    self.draft = None;
+                    self.set_value(value, cx);
+                }
+            }
+            "backspace" => draft.backspace(),
+            _ => return,
+        }
+        cx.stop_propagation();
+        cx.notify();
+    }
+
+    /// Paints the text typed in so far, with the caret and an underline beneath the text being
+    /// composed, and receives typed text while it's focused.
+    fn render_draft(&self, draft: &Draft, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let colors = cx.theme().colors();
+        let mut style = cx.text_style();
+        style.font_size = TextSize::default().rems(cx).into();
+        style.color = colors.text;
+        let font_size = style.font_size.to_pixels(cx.rem_size());
+        let line_height = style.line_height_in_pixels(cx.rem_size());
+        let runs = if draft.text.is_empty() {
+            Vec::new()
+        } else {
+            vec![style.to_run(draft.text.len())]
+        };
+        let line = cx
+            .text_system()
+            .shape_line(draft.text.clone().into(), font_size, &runs)
+            .ok();
+        let width = line.as_ref().map_or(px(0.), |line| line.width);
+
+        let view = cx.view().clone();
+        let focus_handle = self.focus_handle.clone();
+        let marked_range = draft.marked_range.clone();
+        let cursor = draft.cursor;
+        let (text_color, cursor_color) = (colors.text, cx.theme().players().local().cursor);
+        canvas(
+            |_, _| {},
+            move |bounds, _, cx| {
+                cx.handle_input(
+                    &focus_handle,
+                    ElementInputHandler::new(bounds, view.clone()),
+                );
+                let Some(line) = line else {
+                    return;
+                };
+                line.paint(bounds.origin, line_height, cx).ok();
+                let x_for_index = |index| bounds.left() + line.x_for_index(index);
+                if let Some(marked_range) = marked_range {
+                    cx.paint_quad(fill(
+                        Bounds::from_corners(
+                            point(x_for_index(marked_range.start), bounds.bottom() - px(1.)),
+                            point(x_for_index(marked_range.end), bounds.bottom()),
+                        ),
+                        text_color,
+                    ));
+                }
+                cx.paint_quad(fill(
+                    Bounds::new(
+                        point(x_for_index(cursor), bounds.top()),
+                        size(px(1.), line_height),
+                    ),
+                    cursor_color,
+                ));
+                view.update(cx, |input, _| input.last_draft_layout = Some(line));
+            },
+        )
+        // Leave room for the caret after the text.
+        .w(width + px(1.))
+        .h(line_height)
+    }
+
+    fn render_stepper(
+        &self,
+        id: &'static str,
+        icon: IconName,
+        direction: f64,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(id)
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, _, cx| {
+                    cx.stop_propagation();
+                    this.start_repeating(direction, cx)
+                }),
+            )
+            .child(
+                IconButton::new(id, icon)
+                    .shape(IconButtonShape::Square)
+                    .icon_size(IconSize::Small),
+            )
+    }
+}
+
+/// Formats `value` with `precision` decimal places.
+fn format_value(value: f64, precision: usize) -> SharedString {
+    format!("{value:.precision$}").into()
+}
+
+/// Returns `value` moved by `delta` and clamped to `[min, max]`, formatted to `precision`.
+fn stepped_value(value: f64, delta: f64, min: f64, max: f64, precision: usize) -> SharedString {
+    format_value((value + delta).clamp(min, max), precision)
+}
+
+/// Parses a typed-in value, formatting it to `precision`, or returns why it can't be used.
+fn parse_value(
+    text: &str,
+    min: f64,
+    max: f64,
+    precision: usize,
+) -> Result<SharedString, SharedString> {
+    let value = text
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| SharedString::from("Enter a number."))?;
+
+    if value < min || value > max {
+        return Err(format!(
+            "Enter a number from {} to {}.",
+            format_value(min, precision),
+            format_value(max, precision)
+        )
+        .into());
+    }
+
+    Ok(format_value(value, precision))
+}
+
+impl ViewInputHandler for NumberInput {
+    fn text_for_range(
+        &mut self,
+        range_utf16: Range<usize>,
+        _cx: &mut ViewContext<Self>,
+    ) -> Option<String> {
+        let draft = self.draft.as_ref()?;
+        let range = draft.range_from_utf16(&range_utf16);
+        draft.text.get(range).map(ToString::to_string)
+    }
+
+    fn selected_text_range(&mut self, _cx: &mut ViewContext<Self>) -> Option<Range<usize>> {
+        let draft = self.draft.as_ref()?;
+        let cursor = draft.offset_to_utf16(draft.cursor);
+        Some(cursor..cursor)
+    }
+
+    fn marked_text_range(&self, _cx: &mut ViewContext<Self>) -> Option<Range<usize>> {
+        let draft = self.draft.as_ref()?;
+        draft
+            .marked_range
+            .as_ref()
+            .map(|range| draft.range_to_utf16(range))
+    }
+
+    fn unmark_text(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(draft) = self.draft.as_mut() {
+            draft.unmark();
+            cx.notify();
+        }
+    }
+
+    fn replace_text_in_range(
+        &mut self,
+        range_utf16: Option<Range<usize>>,
+        text: &str,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let Some(draft) = self.draft.as_mut() {
+            let range = range_utf16.map(|range| draft.range_from_utf16(&range));
+            draft.commit(range, text);
+            cx.notify();
+        }
+    }
+
+    fn replace_and_mark_text_in_range(
+        &mut self,
+        range_utf16: Option<Range<usize>>,
+        new_text: &str,
+        new_selected_range_utf16: Option<Range<usize>>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let Some(draft) = self.draft.as_mut() {
+            let range = range_utf16.map(|range| draft.range_from_utf16(&range));
+            let cursor =
+                new_selected_range_utf16.map(|range| offset_from_utf16(new_text, range.end));
+            draft.compose(range, new_text, cursor);
+            cx.notify();
+        }
+    }
+
+    fn bounds_for_range(
+        &mut self,
+        range_utf16: Range<usize>,
+        element_bounds: Bounds<Pixels>,
+        _cx: &mut ViewContext<Self>,
+    ) -> Option<Bounds<Pixels>> {
+        let draft = self.draft.as_ref()?;
+        let layout = self.last_draft_layout.as_ref()?;
+        let range = draft.range_from_utf16(&range_utf16);
+        Some(Bounds::from_corners(
+            point(
+                element_bounds.left() + layout.x_for_index(range.start),
+                element_bounds.top(),
+            ),
+            point(
+                element_bounds.left() + layout.x_for_index(range.end),
+                element_bounds.bottom(),
+            ),
+        ))
+    }
+}
+
+impl EventEmitter<NumberChanged> for NumberInput {}
+
+impl FocusableView for NumberInput {
+    fn focus_handle(&self, _cx: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for NumberInput {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let error = self.draft.as_ref().and_then(|draft| {
+            parse_value(&draft.committed_text(), self.min, self.max, self.precision).err()
+        });
+        let value = match self.draft.as_ref() {
+            Some(draft) => self.render_draft(draft, cx).into_any_element(),
+            None => Label::new(self.value.clone()).into_any_element(),
+        };
+        let border_color = if error.is_some() {
+            cx.theme().status().error_border
+        } else if self.draft.is_some() {
+            cx.theme().colors().border_focused
+        } else {
+            cx.theme().colors().border
+        };
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .id("number-input")
+                    .gap_1()
+                    .px_1()
+                    .rounded_sm()
+                    .bg(cx.theme().colors().editor_background)
+                    // Releasing a held stepper button anywhere stops it from repeating.
+                    .on_mouse_up(
+                        MouseButton::Left,
+                        cx.listener(|this, _, _| this.stop_repeating()),
+                    )
+                    .on_mouse_up_out(
+                        MouseButton::Left,
+                        cx.listener(|this, _, _| this.stop_repeating()),
+                    )
+                    .child(self.render_stepper("decrement", IconName::Dash, -1., cx))
+                    .child(
+                        div()
+                            .id("number-input-value")
+                            .track_focus(&self.focus_handle)
+                            .capture_keystrokes(self.draft.is_some())
+                            .on_key_down(cx.listener(Self::handle_key_down))
+                            .on_click(cx.listener(|this, _, cx| {
+                                if !this.is_editing() {
+                                    this.start_editing(cx)
+                                }
+                            }))
+                            .min_w(rems(3.))
+                            .px_1()
+                            .rounded_sm()
+                            .border_1()
+                            .border_color(border_color)
+                            .cursor_text()
+                            .child(value),
+                    )
+                    .child(self.render_stepper("increment", IconName::Plus, 1., cx)),
+            )
+            .when_some(error, |this, error| {
+                this.child(Label::new(error).size(LabelSize::Small).color(Color::Error))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stepping_clamps_to_the_range() {
+        assert_eq!(stepped_value(4., 1., 1., 16., 0), "5");
+        assert_eq!(stepped_value(16., 1., 1., 16., 0), "16");
+        assert_eq!(stepped_value(1., -1., 1., 16., 0), "1");
+        assert_eq!(stepped_value(0.95, 0.1, 0., 1., 2), "1.00");
+        assert_eq!(stepped_value(0.5, -0.1, 0., 1., 1), "0.4");
+    }
+
+    #[test]
+    fn test_composing_text_with_an_input_method() {
+        let mut draft = Draft::new("1".into());
+
+        // Composed text is shown, but isn't part of the value until it's committed.
+        draft.compose(None, "に", None);
+        draft.compose(None, "にじゅう", Some(6));
+        assert_eq!(draft.text, "1にじゅう");
+        assert_eq!(draft.marked_range, Some(1..13));
+        assert_eq!(draft.cursor, 7);
+        assert_eq!(draft.committed_text(), "1");
+
+        // Committing keeps the characters that can be part of a number, in their ASCII form.
+        draft.commit(None, "２０");
+        assert_eq!(draft, Draft::new("120".into()));
+
+        // Cancelling a composition leaves nothing behind.
+        draft.compose(None, "ご", None);
+        draft.compose(None, "", None);
+        draft.unmark();
+        assert_eq!(draft, Draft::new("120".into()));
+
+        // Text that's still being composed when it's unmarked is committed as it is.
+        draft.compose(None, "．５", None);
+        draft.unmark();
+        assert_eq!(draft, Draft::new("120.5".into()));
+    }
+
+    #[test]
+    fn test_backspace_deletes_before_the_caret() {
+        let mut draft = Draft::new("1２5".into());
+        draft.cursor = 4;
+        draft.backspace();
+        assert_eq!(draft.text, "15");
+        assert_eq!(draft.cursor, 1);
+
+        draft.cursor = 0;
+        draft.backspace();
+        assert_eq!(draft.text, "15");
+        assert_eq!(draft.cursor, 0);
+
+        draft.cursor = 2;
+        draft.backspace();
+        assert_eq!(draft, Draft::new("1".into()));
+    }
+
+    #[test]
+    fn test_entered_values_are_validated() {
+        assert_eq!(parse_value("12", 1., 16., 0), Ok("12".into()));
+        assert_eq!(parse_value(" 1.256 ", 0., 2., 2), Ok("1.26".into()));
+        assert_eq!(parse_value("", 1., 16., 0), Err("Enter a number.".into()));
+        assert_eq!(
+            parse_value("1-2", 1., 16., 0),
+            Err("Enter a number.".into())
+        );
+        assert_eq!(
+            parse_value("17", 1., 16., 0),
+            Err("Enter a number from 1 to 16.".into())
+        );
+        assert_eq!(
+            parse_value("-0.5", 0., 1., 1),
+            Err("Enter a number from 0.0 to 1.0.".into())
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::path::Path;
+use std::rc::Rc;
+
+use gpui::PathPromptOptions;
+
+use crate::prelude::*;
+
+/// The kind of path a [`PathPicker`] selects.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum FileOrDirectory {
+    #[default]
+    File,
+    Directory,
+}
+
+/// # PathPicker
+///
+/// Shows a path alongside a "Browse…" button that picks a new one with the platform's file
+/// dialog, along with an optional error describing what's wrong with the current path.
+#[derive(IntoElement)]
+pub struct PathPicker {
+    id: ElementId,
+    path: Option<SharedString>,
+    kind: FileOrDirectory,
+    placeholder: SharedString,
+    error: Option<SharedString>,
+    disabled: bool,
+    on_change: Option<Rc<dyn Fn(&Path, &mut WindowContext) + 'static>>,
+}
+
+impl PathPicker {
+    pub fn new(id: impl Into<ElementId>, path: Option<impl Into<SharedString>>) -> Self {
+        Self {
+            id: id.into(),
+            path: path.map(Into::into),
+            kind: FileOrDirectory::default(),
+            placeholder: "None".into(),
+            error: None,
+            disabled: false,
+            on_change: None,
+        }
+    }
+
+    /// Sets whether a file or a directory is picked.
+    pub fn kind(mut self, kind: FileOrDirectory) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the text shown when there is no path.
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Sets the error shown beneath the path, such as when it doesn't exist.
+    pub fn error(mut self, error: Option<impl Into<SharedString>>) -> Self {
+        self.error = error.map(Into::into);
+        self
+    }
+
+    pub fn on_change(mut self, handler: impl Fn(&Path, &mut WindowContext) + 'static) -> Self {
+        self.on_change = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl Disableable for PathPicker {
+    fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+impl RenderOnce for PathPicker {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let kind = self.kind;
+        let (label, color) = match self.path {
+            Some(path) => (path, Color::Default),
+            None => (self.placeholder, Color::Placeholder),
+        };
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_1()
+                            .px_2()
+                            .py_0p5()
+                            .overflow_hidden()
+                            .rounded_md()
+                            .border_1()
+                            .border_color(if self.error.is_some() {
+                                cx.theme().status().error_border
+                            } else {
+                                cx.theme().colors().border
+                            })
+                            .child(Label::new(label).size(LabelSize::Small).color(
+                                if self.disabled {
+                                    Color::Disabled
+                                } else {
+                                    color
+                                },
+                            )),
+                    )
+                    .child(
+                        Button::new(self.id, "Browse…")
+                            .style(ButtonStyle::Filled)
+                            .disabled(self.disabled)
+                            .when_some(self.on_change, |this, on_change| {
+                                this.on_click(move |_, cx| {
+                                    let path = cx.prompt_for_path(PathPromptOptions {
+                                        files: kind == FileOrDirectory::File,
+                                        directories: kind == FileOrDirectory::Directory,
+                                        multiple: false,
+                                    });
+                                    let on_change = on_change.clone();
+                                    cx.spawn(|mut cx| async move {
+                                        if let Some(path) = path.await {
+                                            cx.update(|cx| on_change(&path, cx)).ok();
+                                        }
+                                    })
+                                    .detach();
+                                })
+                            }),
+                    ),
+            )
+            .when_some(self.error, |this, error| {
+                this.child(Label::new(error).size(LabelSize::Small).color(Color::Error))
+            })
+    }
+}
@@ -1,10 +1,15 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 use gpui::{
     anchored, deferred, div, point, prelude::FluentBuilder, px, size, AnchorCorner, AnyElement,
-    Bounds, DismissEvent, DispatchPhase, Element, ElementId, GlobalElementId, HitboxId,
-    InteractiveElement, IntoElement, LayoutId, Length, ManagedView, MouseDownEvent, ParentElement,
-    Pixels, Point, Style, View, VisualContext, WindowContext,
+    AnyWindowHandle, AvailableSpace, Bounds, DismissEvent, DispatchPhase, Element, ElementId,
+    GlobalElementId, HitboxId, InteractiveElement, IntoElement, LayoutId, Length, ManagedView,
+    MouseDownEvent, ParentElement, Pixels, Point, Render, Size, Style, Subscription, View,
+    ViewContext, VisualContext, WindowBackgroundAppearance, WindowBounds, WindowContext,
+    WindowDecorations, WindowKind, WindowOptions,
 };
 
 use crate::prelude::*;
@@ -30,12 +35,13 @@ impl<M> Default for PopoverMenuHandle<M> {
 struct PopoverMenuHandleState<M> {
     menu_builder: Rc<dyn Fn(&mut WindowContext) -> Option<View<M>>>,
     menu: Rc<RefCell<Option<View<M>>>>,
+    in_window: bool,
 }
 
 impl<M: ManagedView> PopoverMenuHandle<M> {
     pub fn show(&self, cx: &mut WindowContext) {
         if let Some(state) = self.0.borrow().as_ref() {
-            show_menu(&state.menu_builder, &state.menu, cx);
+            show_menu(&state.menu_builder, &state.menu, state.in_window, cx);
         }
     }
 
@@ -65,6 +71,7 @@ pub struct PopoverMenu<M: ManagedView> {
             dyn FnOnce(
                     Rc<RefCell<Option<View<M>>>>,
                     Option<Rc<dyn Fn(&mut WindowContext) -> Option<View<M>> + 'static>>,
+                    bool,
                 ) -> AnyElement
                 + 'static,
         >,
@@ -75,6 +82,7 @@ pub struct PopoverMenu<M: ManagedView> {
     offset: Option<Point<Pixels>>,
     trigger_handle: Option<PopoverMenuHandle<M>>,
     full_width: bool,
+    in_window: bool,
 }
 
 impl<M: ManagedView> PopoverMenu<M> {
@@ -89,6 +97,7 @@ impl<M: ManagedView> PopoverMenu<M> {
             offset: None,
             trigger_handle: None,
             full_width: false,
+            in_window: false,
         }
     }
 
@@ -102,17 +111,29 @@ impl<M: ManagedView> PopoverMenu<M> {
         self
     }
 
+    /// Shows the menu in a window of its own rather than over the window the trigger is in, so
+    /// that it can extend past that window's bounds.
+    ///
+    /// The menu's window is placed where the menu would otherwise be shown, kept on the trigger's
+    /// display, and closed when the menu is dismissed, when it loses focus, such as from a click
+    /// outside of it, or when the trigger's window is closed. Platforms that don't let windows
+    /// choose their position, like Wayland, place it themselves.
+    pub fn in_window(mut self, in_window: bool) -> Self {
+        self.in_window = in_window;
+        self
+    }
+
     pub fn with_handle(mut self, handle: PopoverMenuHandle<M>) -> Self {
         self.trigger_handle = Some(handle);
         self
     }
 
     pub fn trigger<T: PopoverTrigger>(mut self, t: T) -> Self {
-        self.child_builder = Some(Box::new(|menu, builder| {
+        self.child_builder = Some(Box::new(|menu, builder, in_window| {
             let open = menu.borrow().is_some();
             t.selected(open)
                 .when_some(builder, |el, builder| {
-                    el.on_click(move |_, cx| show_menu(&builder, &menu, cx))
+                    el.on_click(move |_, cx| show_menu(&builder, &menu, in_window, cx))
                 })
                 .into_any_element()
         }));
@@ -162,6 +183,7 @@ impl<M: ManagedView> PopoverMenu<M> {
 fn show_menu<M: ManagedView>(
     builder: &Rc<dyn Fn(&mut WindowContext) -> Option<View<M>>>,
     menu: &Rc<RefCell<Option<View<M>>>>,
+    in_window: bool,
     cx: &mut WindowContext,
 ) {
     let Some(new_menu) = (builder)(cx) else {
@@ -180,14 +202,149 @@ fn show_menu<M: ManagedView>(
         cx.refresh();
     })
     .detach();
-    cx.focus_view(&new_menu);
+    // A menu shown in a window of its own is focused in that window once it's opened.
+    if !in_window {
+        cx.focus_view(&new_menu);
+    }
     *menu.borrow_mut() = Some(new_menu);
     cx.refresh();
 }
 
+/// The root view of the window that a [`PopoverMenu`] is shown in when it's
+/// [`in_window`](PopoverMenu::in_window), which dismisses the menu when the window loses focus,
+/// and closes once the menu is dismissed.
+struct PopoverMenuWindow<M> {
+    menu: View<M>,
+    _subscriptions: [Subscription; 2],
+}
+
+impl<M: ManagedView> PopoverMenuWindow<M> {
+    fn new(
+        menu: View<M>,
+        window: Rc<Cell<Option<AnyWindowHandle>>>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        cx.focus_view(&menu);
+        let subscriptions = [
+            cx.subscribe(&menu, move |_, _, _: &DismissEvent, cx| {
+                window.take();
+                cx.remove_window();
+            }),
+            cx.observe_window_activation(|this, cx| {
+                if !cx.is_window_active() {
+                    this.menu.update(cx, |_, cx| cx.emit(DismissEvent));
+                }
+            }),
+        ];
+
+        Self {
+            menu,
+            _subscriptions: subscriptions,
+        }
+    }
+}
+
+impl<M: ManagedView> Render for PopoverMenuWindow<M> {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div().size_full().child(self.menu.clone())
+    }
+}
+
+/// Opens a window showing the given menu, anchored to the trigger's window, with the same
+/// bounds as the menu would have in that window.
+fn open_menu_window<M: ManagedView>(
+    menu: View<M>,
+    anchor: AnchorCorner,
+    position: Point<Pixels>,
+    window: Rc<Cell<Option<AnyWindowHandle>>>,
+    cx: &mut WindowContext,
+) {
+    let mut element = div().child(menu.clone()).into_any_element();
+    let menu_size = element.layout_as_root(
+        size(AvailableSpace::MaxContent, AvailableSpace::MaxContent),
+        cx,
+    );
+
+    // The window's bounds include its titlebar, if it has one, while the position is relative to
+    // its content.
+    let window_bounds = cx.bounds();
+    let content_origin = window_bounds.origin
+        + point(
+            px(0.),
+            window_bounds.size.height - cx.viewport_size().height,
+        );
+    let mut bounds = anchor_bounds(anchor, content_origin + position, menu_size);
+    let display = cx.display();
+    if let Some(display) = display.as_ref() {
+        // Keep the menu clear of system UI such as the menu bar and dock.
+        bounds = fit_within(bounds, display.visible_bounds());
+    }
+
+    let options = WindowOptions {
+        window_bounds: Some(WindowBounds::Windowed(bounds)),
+        titlebar: None,
+        focus: true,
+        show: true,
+        kind: WindowKind::PopUp,
+        is_movable: false,
+        display_id: display.map(|display| display.id()),
+        window_background: WindowBackgroundAppearance::Transparent,
+        window_decorations: Some(WindowDecorations::Client),
+        parent: Some(cx.window_handle()),
+        ..Default::default()
+    };
+    // Windows can't be opened while this one is being drawn.
+    cx.defer(move |cx| {
+        let handle = cx.open_window(options, |cx| {
+            cx.new_view(|cx| PopoverMenuWindow::new(menu.clone(), window.clone(), cx))
+        });
+        match handle {
+            Ok(handle) => window.set(Some(handle.into())),
+            // Dismiss the menu rather than leaving its trigger selected.
+            Err(_) => menu.update(cx, |_, cx| cx.emit(DismissEvent)),
+        }
+    });
+}
+
+/// Returns the bounds of the given size whose `anchor` corner is at `position`.
+fn anchor_bounds(
+    anchor: AnchorCorner,
+    position: Point<Pixels>,
+    size: Size<Pixels>,
+) -> Bounds<Pixels> {
+    let origin = match anchor {
+        AnchorCorner::TopLeft => position,
+        AnchorCorner::TopRight => point(position.x - size.width, position.y),
+        AnchorCorner::BottomLeft => point(position.x, position.y - size.height),
+        AnchorCorner::BottomRight => position - point(size.width, size.height),
+    };
+    Bounds { origin, size }
+}
+
+/// Moves the bounds as little as possible to fit them within `container`, keeping their top left
+/// corner within it if they're too big.
+fn fit_within(bounds: Bounds<Pixels>, container: Bounds<Pixels>) -> Bounds<Pixels> {
+    let x = bounds
+        .origin
+        .x
+        .min(container.right() - bounds.size.width)
+        .max(container.left());
+    let y = bounds
+        .origin
+        .y
+        .min(container.bottom() - bounds.size.height)
+        .max(container.top());
+    Bounds {
+        origin: point(x, y),
+        size: bounds.size,
+    }
+}
+
 pub struct PopoverMenuElementState<M> {
     menu: Rc<RefCell<Option<View<M>>>>,
     child_bounds: Option<Bounds<Pixels>>,
+    /// The window the menu is shown in, while it's open in a window of its own.
+    window: Rc<Cell<Option<AnyWindowHandle>>>,
 }
 
 impl<M> Clone for PopoverMenuElementState<M> {
@@ -195,6 +352,7 @@ impl<M> Clone for PopoverMenuElementState<M> {
         Self {
             menu: Rc::clone(&self.menu),
             child_bounds: self.child_bounds,
+            window: Rc::clone(&self.window),
         }
     }
 }
@@ -204,6 +362,7 @@ impl<M> Default for PopoverMenuElementState<M> {
         Self {
             menu: Rc::default(),
             child_bounds: None,
+            window: Rc::default(),
         }
     }
 }
@@ -212,6 +371,8 @@ pub struct PopoverMenuFrameState {
     child_layout_id: Option<LayoutId>,
     child_element: Option<AnyElement>,
     menu_element: Option<AnyElement>,
+    /// Whether the menu is open in a window of its own.
+    menu_open_in_window: bool,
 }
 
 impl<M: ManagedView> Element for PopoverMenu<M> {
@@ -232,24 +393,36 @@ impl<M: ManagedView> Element for PopoverMenu<M> {
             |element_state: Option<PopoverMenuElementState<M>>, cx| {
                 let element_state = element_state.unwrap_or_default();
                 let mut menu_layout_id = None;
+                let menu_open_in_window = self.in_window && element_state.menu.borrow().is_some();
 
-                let menu_element = element_state.menu.borrow_mut().as_mut().map(|menu| {
-                    let mut anchored = anchored().snap_to_window().anchor(self.anchor);
-                    if let Some(child_bounds) = element_state.child_bounds {
-                        anchored = anchored.position(
-                            self.resolved_attach().corner(child_bounds) + self.resolved_offset(cx),
-                        );
-                    }
-                    let mut element = deferred(anchored.child(div().occlude().child(menu.clone())))
-                        .with_priority(1)
-                        .into_any();
-
-                    menu_layout_id = Some(element.request_layout(cx));
-                    element
-                });
+                let menu_element = element_state
+                    .menu
+                    .borrow_mut()
+                    .as_mut()
+                    .filter(|_| !self.in_window)
+                    .map(|menu| {
+                        let mut anchored = anchored().snap_to_window().anchor(self.anchor);
+                        if let Some(child_bounds) = element_state.child_bounds {
+                            anchored = anchored.position(
+                                self.resolved_attach().corner(child_bounds)
+                                    + self.resolved_offset(cx),
+                            );
+                        }
+                        let mut element =
+                            deferred(anchored.child(div().occlude().child(menu.clone())))
+                                .with_priority(1)
+                                .into_any();
+
+                        menu_layout_id = Some(element.request_layout(cx));
+                        element
+                    });
 
                 let mut child_element = self.child_builder.take().map(|child_builder| {
-                    (child_builder)(element_state.menu.clone(), self.menu_builder.clone())
+                    (child_builder)(
+                        element_state.menu.clone(),
+                        self.menu_builder.clone(),
+                        self.in_window,
+                    )
                 });
 
                 if let Some(trigger_handle) = self.trigger_handle.take() {
@@ -257,6 +430,7 @@ impl<M: ManagedView> Element for PopoverMenu<M> {
                         *trigger_handle.0.borrow_mut() = Some(PopoverMenuHandleState {
                             menu_builder,
                             menu: element_state.menu.clone(),
+                            in_window: self.in_window,
                         });
                     }
                 }
@@ -280,6 +454,7 @@ impl<M: ManagedView> Element for PopoverMenu<M> {
                             child_element,
                             child_layout_id,
                             menu_element,
+                            menu_open_in_window,
                         },
                     ),
                     element_state,
@@ -305,9 +480,23 @@ impl<M: ManagedView> Element for PopoverMenu<M> {
 
         let hitbox_id = request_layout.child_layout_id.map(|layout_id| {
             let bounds = cx.layout_bounds(layout_id);
-            cx.with_element_state(global_id.unwrap(), |element_state, _cx| {
+            cx.with_element_state(global_id.unwrap(), |element_state, cx| {
                 let mut element_state: PopoverMenuElementState<M> = element_state.unwrap();
                 element_state.child_bounds = Some(bounds);
+                if request_layout.menu_open_in_window && element_state.window.get().is_none() {
+                    let menu = element_state.menu.borrow().clone();
+                    if let Some(menu) = menu {
+                        let position =
+                            self.resolved_attach().corner(bounds) + self.resolved_offset(cx);
+                        open_menu_window(
+                            menu,
+                            self.anchor,
+                            position,
+                            element_state.window.clone(),
+                            cx,
+                        );
+                    }
+                }
                 ((), element_state)
             });
 
@@ -329,9 +518,14 @@ impl<M: ManagedView> Element for PopoverMenu<M> {
             child.paint(cx);
         }
 
-        if let Some(mut menu) = request_layout.menu_element.take() {
-            menu.paint(cx);
-
+        let menu_open = match request_layout.menu_element.take() {
+            Some(mut menu) => {
+                menu.paint(cx);
+                true
+            }
+            None => request_layout.menu_open_in_window,
+        };
+        if menu_open {
             if let Some(child_hitbox) = *child_hitbox {
                 // Mouse-downing outside the menu dismisses it, so we don't
                 // want a click on the toggle to re-open it.
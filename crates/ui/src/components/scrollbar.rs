@@ -0,0 +1,487 @@
+use std::cell::Cell;
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use gpui::{
+    fill, Along, Axis, Bounds, GlobalElementId, Hitbox, LayoutId, MouseButton, MouseDownEvent,
+    MouseMoveEvent, MouseUpEvent, Point, Position, ScrollHandle, Style, Task,
+};
+
+use crate::prelude::*;
+
+/// How long a [`Scrollbar`] stays visible once its content stops scrolling.
+const HIDE_DELAY: Duration = Duration::from_secs(1);
+/// How long a [`Scrollbar`] takes to fade in or out.
+const FADE_DURATION: Duration = Duration::from_millis(150);
+/// The width of a vertical [`Scrollbar`], or the height of a horizontal one.
+const THICKNESS: Pixels = px(8.);
+/// The shortest a [`Scrollbar`] thumb can be, so that it stays easy to grab.
+const MIN_THUMB_LENGTH: Pixels = px(24.);
+
+#[derive(Default)]
+struct ScrollbarState {
+    /// The scroll offset when the scrollbar was last painted, used to detect scrolling.
+    last_offset: Point<Pixels>,
+    /// When the scrollbar started fading in, if it's visible.
+    shown_at: Option<Instant>,
+    /// When the content was last scrolled, or the scrollbar was last hovered or dragged.
+    last_active_at: Option<Instant>,
+    /// While the thumb is being dragged, the distance from its start to the mouse.
+    drag_offset: Rc<Cell<Option<Pixels>>>,
+    /// Refreshes the window when the scrollbar is due to start fading out, at the time it's due.
+    hide_timer: Option<(Instant, Task<()>)>,
+}
+
+impl ScrollbarState {
+    fn activate(&mut self, now: Instant) {
+        self.shown_at.get_or_insert(now);
+        self.last_active_at = Some(now);
+    }
+
+    /// Returns the opacity of the scrollbar, hiding it once it has faded out.
+    fn opacity(&mut self, now: Instant) -> f32 {
+        let (Some(shown_at), Some(last_active_at)) = (self.shown_at, self.last_active_at) else {
+            return 0.;
+        };
+
+        let fade_in =
+            now.saturating_duration_since(shown_at).as_secs_f32() / FADE_DURATION.as_secs_f32();
+        let idle = now
+            .saturating_duration_since(last_active_at)
+            .saturating_sub(HIDE_DELAY);
+        let fade_out = 1. - idle.as_secs_f32() / FADE_DURATION.as_secs_f32();
+        let opacity = fade_in.min(fade_out).clamp(0., 1.);
+        if opacity == 0. && fade_out <= 0. {
+            self.shown_at = None;
+        }
+        opacity
+    }
+}
+
+/// Returns the range the thumb occupies along a track of the given length, for content scrolled
+/// by `offset` out of `max_offset` in a viewport of the given length.
+fn thumb_range(
+    track_length: Pixels,
+    viewport_length: Pixels,
+    max_offset: Pixels,
+    offset: Pixels,
+) -> Range<Pixels> {
+    let content_length = viewport_length + max_offset;
+    let length = (track_length * (viewport_length / content_length))
+        .max(MIN_THUMB_LENGTH)
+        .min(track_length);
+    let fraction = (-offset / max_offset).clamp(0., 1.);
+    let start = (track_length - length) * fraction;
+
+    start..start + length
+}
+
+/// Returns the scroll offset that moves the start of the thumb to `thumb_start` along the track.
+fn offset_for_thumb_start(
+    thumb_start: Pixels,
+    thumb_length: Pixels,
+    track_length: Pixels,
+    max_offset: Pixels,
+) -> Pixels {
+    let travel = track_length - thumb_length;
+    if travel <= px(0.) {
+        return px(0.);
+    }
+
+    -max_offset * (thumb_start / travel).clamp(0., 1.)
+}
+
+/// # Scrollbar
+///
+/// Shows how far the element tracked by a [`ScrollHandle`] is scrolled, with a thumb sized from
+/// the fraction of its content that's visible, which can be dragged to scroll. Clicking the
+/// track centers the thumb on the mouse.
+///
+/// The scrollbar is positioned along the right or bottom edge of its parent, which should be
+/// `relative`, and is only shown while the content overflows. It fades in when the content is
+/// scrolled or the scrollbar is hovered, and fades out after a second of inactivity.
+pub struct Scrollbar {
+    id: ElementId,
+    axis: Axis,
+    scroll_handle: ScrollHandle,
+}
+
+impl Scrollbar {
+    /// Creates a scrollbar along the right edge of its parent, for content that scrolls
+    /// vertically.
+    pub fn vertical(id: impl Into<ElementId>, scroll_handle: ScrollHandle) -> Self {
+        Self {
+            id: id.into(),
+            axis: Axis::Vertical,
+            scroll_handle,
+        }
+    }
+
+    /// Creates a scrollbar along the bottom edge of its parent, for content that scrolls
+    /// horizontally.
+    pub fn horizontal(id: impl Into<ElementId>, scroll_handle: ScrollHandle) -> Self {
+        Self {
+            id: id.into(),
+            axis: Axis::Horizontal,
+            scroll_handle,
+        }
+    }
+}
+
+impl Element for Scrollbar {
+    type RequestLayoutState = ();
+    type PrepaintState = Option<Hitbox>;
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut style = Style::default();
+        style.position = Position::Absolute;
+        match self.axis {
+            Axis::Vertical => {
+                style.inset.top = px(0.).into();
+                style.inset.bottom = px(0.).into();
+                style.inset.right = px(0.).into();
+                style.size.width = THICKNESS.into();
+            }
+            Axis::Horizontal => {
+                style.inset.left = px(0.).into();
+                style.inset.right = px(0.).into();
+                style.inset.bottom = px(0.).into();
+                style.size.height = THICKNESS.into();
+            }
+        }
+
+        (cx.request_layout(style, None), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        let overflows = self.scroll_handle.max_offset().along(self.axis) > px(0.);
+        // The hitbox isn't opaque, so while the scrollbar is hidden it only detects the mouse
+        // hovering it, without taking the hover from the content beneath.
+        overflows.then(|| cx.insert_hitbox(bounds, false))
+    }
+
+    fn paint(
+        &mut self,
+        id: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        hitbox: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        let Some(hitbox) = hitbox.clone() else {
+            return;
+        };
+
+        let axis = self.axis;
+        let scroll_handle = self.scroll_handle.clone();
+        let offset = scroll_handle.offset();
+        let max_offset = scroll_handle.max_offset().along(axis);
+        let track_start = bounds.origin.along(axis);
+        let track_length = bounds.size.along(axis);
+        let thumb = thumb_range(
+            track_length,
+            scroll_handle.bounds().size.along(axis),
+            max_offset,
+            offset.along(axis),
+        );
+        let thumb_length = thumb.end - thumb.start;
+        let thumb_bounds = Bounds {
+            origin: bounds
+                .origin
+                .apply_along(axis, |origin| origin + thumb.start),
+            size: bounds.size.apply_along(axis, |_| thumb_length),
+        };
+        let scroll_to = move |thumb_start: Pixels| {
+            let offset =
+                offset_for_thumb_start(thumb_start, thumb_length, track_length, max_offset);
+            scroll_handle.set_offset(scroll_handle.offset().apply_along(axis, |_| offset));
+        };
+
+        cx.with_element_state(id.unwrap(), |state, cx| {
+            let mut state: ScrollbarState = state.unwrap_or_default();
+            let now = cx.background_executor().now();
+            let hovered = hitbox.is_hovered(cx);
+            let dragging = state.drag_offset.get().is_some();
+            if offset != state.last_offset || hovered || dragging {
+                state.activate(now);
+            }
+            state.last_offset = offset;
+
+            let opacity = state.opacity(now);
+            if opacity > 0. {
+                let colors = cx.theme().colors();
+                let track_color = colors.scrollbar_track_background;
+                let thumb_color = if hovered || dragging {
+                    colors.scrollbar_thumb_hover_background
+                } else {
+                    colors.scrollbar_thumb_background
+                };
+                cx.paint_quad(fill(bounds, track_color.opacity(opacity)));
+                cx.paint_quad(
+                    fill(thumb_bounds, thumb_color.opacity(opacity)).corner_radii(THICKNESS / 2.),
+                );
+            }
+
+            // Frames are only requested while the scrollbar is fading. Once it's fully shown, it's
+            // painted again when it's due to fade out, unless it's still in use by then.
+            if state.shown_at.is_some() && opacity < 1. {
+                cx.request_animation_frame();
+            } else if opacity == 1. && !hovered && !dragging {
+                let fade_out_at = state.last_active_at.unwrap_or(now) + HIDE_DELAY;
+                if state
+                    .hide_timer
+                    .as_ref()
+                    .map_or(true, |(due_at, _)| *due_at != fade_out_at)
+                {
+                    let delay = fade_out_at.saturating_duration_since(now);
+                    let timer = cx.spawn(|mut cx| async move {
+                        cx.background_executor().timer(delay).await;
+                        cx.update(|cx| cx.refresh()).ok();
+                    });
+                    state.hide_timer = Some((fade_out_at, timer));
+                }
+            }
+
+            // While the scrollbar is hidden, presses along the edge go to the content beneath it,
+            // unless the mouse is over it, in which case it's fading in.
+            if opacity > 0. || hovered {
+                cx.on_mouse_event({
+                    let hitbox = hitbox.clone();
+                    let drag_offset = state.drag_offset.clone();
+                    let scroll_to = scroll_to.clone();
+                    move |event: &MouseDownEvent, phase, cx| {
+                        if !phase.bubble()
+                            || event.button != MouseButton::Left
+                            || !hitbox.is_hovered(cx)
+                        {
+                            return;
+                        }
+
+                        let position = event.position.along(axis) - track_start;
+                        let grab_offset = if thumb_bounds.contains(&event.position) {
+                            position - thumb.start
+                        } else {
+                            let grab_offset = thumb_length / 2.;
+                            scroll_to(position - grab_offset);
+                            grab_offset
+                        };
+                        drag_offset.set(Some(grab_offset));
+                        cx.stop_propagation();
+                        cx.refresh();
+                    }
+                });
+            }
+            cx.on_mouse_event({
+                let drag_offset = state.drag_offset.clone();
+                move |event: &MouseMoveEvent, _, cx| match drag_offset.get() {
+                    Some(grab_offset) if event.dragging() => {
+                        scroll_to(event.position.along(axis) - track_start - grab_offset);
+                        cx.refresh();
+                    }
+                    Some(_) => drag_offset.set(None),
+                    None => {
+                        if hitbox.is_hovered(cx) != hovered {
+                            cx.refresh();
+                        }
+                    }
+                }
+            });
+            cx.on_mouse_event({
+                let drag_offset = state.drag_offset.clone();
+                move |_: &MouseUpEvent, phase, cx| {
+                    if phase.bubble() && drag_offset.take().is_some() {
+                        cx.refresh();
+                    }
+                }
+            });
+
+            ((), state)
+        })
+    }
+}
+
+impl IntoElement for Scrollbar {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, size, Modifiers, TestAppContext, VisualTestContext};
+    use settings::SettingsStore;
+
+    use super::*;
+
+    struct ScrollingView {
+        scroll_handle: ScrollHandle,
+    }
+
+    impl Render for ScrollingView {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            div()
+                .size_full()
+                .relative()
+                .child(
+                    div()
+                        .id("content")
+                        .size_full()
+                        .overflow_y_scroll()
+                        .track_scroll(&self.scroll_handle)
+                        .child(div().h(px(400.))),
+                )
+                .child(Scrollbar::vertical("scrollbar", self.scroll_handle.clone()))
+        }
+    }
+
+    /// Opens a 100px square window over content 400px tall, whose scrollbar's track and thumb
+    /// are painted 92px from its left edge.
+    fn add_scrolling_view(cx: &mut TestAppContext) -> (ScrollHandle, &mut VisualTestContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+        });
+        let scroll_handle = ScrollHandle::new();
+        let (_, cx) = cx.add_window_view({
+            let scroll_handle = scroll_handle.clone();
+            |_| ScrollingView { scroll_handle }
+        });
+        cx.simulate_resize(size(px(100.), px(100.)));
+        cx.run_until_parked();
+        (scroll_handle, cx)
+    }
+
+    fn scrollbar_painted(cx: &mut VisualTestContext) -> bool {
+        let scale_factor = cx.update(|cx| cx.scale_factor()) as f64;
+        cx.scene_snapshot()["quads"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|quad| {
+                quad["bounds"]["origin"]["x"] == 92. * scale_factor
+                    && quad["bounds"]["size"]["width"] == 8. * scale_factor
+            })
+    }
+
+    #[gpui::test]
+    fn test_scrollbar_fades_out_once_scrolling_stops(cx: &mut TestAppContext) {
+        let (scroll_handle, cx) = add_scrolling_view(cx);
+        assert!(!scrollbar_painted(cx));
+
+        // Scrolling fades the scrollbar in.
+        scroll_handle.set_offset(point(px(0.), px(-50.)));
+        cx.update(|cx| cx.refresh());
+        cx.executor().advance_clock(FADE_DURATION);
+        cx.update(|cx| cx.refresh());
+        assert!(scrollbar_painted(cx));
+
+        cx.executor().advance_clock(HIDE_DELAY / 2);
+        cx.update(|cx| cx.refresh());
+        assert!(scrollbar_painted(cx));
+
+        // The window is drawn again once the scrollbar is due to fade out, without being
+        // refreshed.
+        cx.executor().advance_clock(HIDE_DELAY);
+        cx.run_until_parked();
+        assert!(!scrollbar_painted(cx));
+    }
+
+    #[gpui::test]
+    fn test_dragging_the_thumb_scrolls(cx: &mut TestAppContext) {
+        let (scroll_handle, cx) = add_scrolling_view(cx);
+
+        // A quarter of the content is visible, so the thumb is 25px long and 75px of travel
+        // scrolls through the 300px the content overflows by.
+        let thumb = point(px(96.), px(10.));
+        cx.simulate_mouse_move(thumb, None, Modifiers::default());
+        cx.simulate_mouse_down(thumb, MouseButton::Left, Modifiers::default());
+        cx.simulate_mouse_move(
+            point(px(96.), px(35.)),
+            Some(MouseButton::Left),
+            Modifiers::default(),
+        );
+        assert_eq!(scroll_handle.offset(), point(px(0.), px(-100.)));
+
+        cx.simulate_mouse_up(
+            point(px(96.), px(35.)),
+            MouseButton::Left,
+            Modifiers::default(),
+        );
+        cx.simulate_mouse_move(point(px(96.), px(60.)), None, Modifiers::default());
+        assert_eq!(scroll_handle.offset(), point(px(0.), px(-100.)));
+    }
+
+    #[test]
+    fn test_thumb_follows_scroll_offset() {
+        // Half of the content is visible, so the thumb takes up half of the track.
+        assert_eq!(
+            thumb_range(px(100.), px(100.), px(100.), px(0.)),
+            px(0.)..px(50.)
+        );
+        assert_eq!(
+            thumb_range(px(100.), px(100.), px(100.), px(-50.)),
+            px(25.)..px(75.)
+        );
+        assert_eq!(
+            thumb_range(px(100.), px(100.), px(100.), px(-100.)),
+            px(50.)..px(100.)
+        );
+
+        // Very long content still has a thumb that can be grabbed.
+        assert_eq!(
+            thumb_range(px(100.), px(100.), px(9900.), px(-9900.)),
+            px(76.)..px(100.)
+        );
+
+        // Dragging the thumb is the inverse of positioning it.
+        assert_eq!(
+            offset_for_thumb_start(px(25.), px(50.), px(100.), px(100.)),
+            px(-50.)
+        );
+        assert_eq!(
+            offset_for_thumb_start(px(-10.), px(50.), px(100.), px(100.)),
+            px(0.)
+        );
+        assert_eq!(
+            offset_for_thumb_start(px(90.), px(50.), px(100.), px(100.)),
+            px(-100.)
+        );
+    }
+
+    #[test]
+    fn test_scrollbar_fades_out_after_inactivity() {
+        let start = Instant::now();
+        let mut state = ScrollbarState::default();
+        assert_eq!(state.opacity(start), 0.);
+
+        state.activate(start);
+        assert_eq!(state.opacity(start + FADE_DURATION), 1.);
+        assert_eq!(state.opacity(start + HIDE_DELAY), 1.);
+        assert_eq!(state.opacity(start + HIDE_DELAY + FADE_DURATION), 0.);
+        assert!(state.shown_at.is_none());
+
+        // Scrolling again fades the scrollbar back in.
+        let later = start + HIDE_DELAY * 2;
+        state.activate(later);
+        assert_eq!(state.opacity(later), 0.);
+        assert_eq!(state.opacity(later + FADE_DURATION), 1.);
+    }
+}
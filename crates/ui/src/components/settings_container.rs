@@ -1,29 +1,407 @@
-use gpui::AnyElement;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use gpui::{canvas, ease_in_out, transparent_black, AnyElement, Bounds};
 use smallvec::SmallVec;
 
-use crate::prelude::*;
+use crate::{prelude::*, SettingsGroup};
+
+/// How long the groups of a reorderable [`SettingsContainer`] take to slide into their new places.
+const SLIDE_DURATION: Duration = Duration::from_millis(150);
+
+/// A [`SettingsGroup`] being dragged by its header to a new place in a reorderable
+/// [`SettingsContainer`].
+#[derive(Clone)]
+pub struct DraggedSettingsGroup {
+    pub id: SharedString,
+    pub header: SharedString,
+}
+
+impl Render for DraggedSettingsGroup {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .px_2()
+            .py_1()
+            .elevation_2(cx)
+            .child(Label::new(self.header.clone()).size(LabelSize::Small))
+    }
+}
+
+struct SettingsGroupOrderState {
+    /// The IDs of the groups in the order they were last arranged in.
+    order: Vec<SharedString>,
+    /// Where each group was last painted.
+    bounds: HashMap<SharedString, Bounds<Pixels>>,
+    /// When the groups were last reordered, and how far each of them was from its new place.
+    slide: Option<(Instant, HashMap<SharedString, Pixels>)>,
+    on_reorder: Rc<dyn Fn(&[SharedString], &mut WindowContext)>,
+}
+
+/// The order of the groups in a reorderable [`SettingsContainer`], which outlives the container
+/// so that it's kept across renders.
+#[derive(Clone)]
+pub struct SettingsGroupOrder(Rc<RefCell<SettingsGroupOrderState>>);
+
+impl SettingsGroupOrder {
+    /// Creates an order that shows the groups in the order they're added to the container.
+    ///
+    /// Whenever a group is dropped in a new place, `on_reorder` is called with the IDs of all of
+    /// the groups in their new order, such as to persist it.
+    pub fn new(on_reorder: impl Fn(&[SharedString], &mut WindowContext) + 'static) -> Self {
+        Self(Rc::new(RefCell::new(SettingsGroupOrderState {
+            order: Vec::new(),
+            bounds: HashMap::default(),
+            slide: None,
+            on_reorder: Rc::new(on_reorder),
+        })))
+    }
+
+    /// Returns the IDs of the groups in their current order.
+    pub fn order(&self) -> Vec<SharedString> {
+        self.0.borrow().order.clone()
+    }
+
+    /// Sets the order of the groups, such as one that was persisted. Groups that aren't listed
+    /// follow the listed ones, in the order they're added to the container.
+    pub fn set_order(&self, order: Vec<SharedString>) {
+        self.0.borrow_mut().order = order;
+    }
+
+    fn position(&self, group_id: Option<&SharedString>) -> usize {
+        group_id
+            .and_then(|group_id| self.0.borrow().order.iter().position(|id| id == group_id))
+            .unwrap_or(usize::MAX)
+    }
+
+    /// Returns how far the group is from its place while it slides into it.
+    fn slide_offset(&self, group_id: &SharedString, now: Instant) -> Option<Pixels> {
+        let mut state = self.0.borrow_mut();
+        let started_at = state.slide.as_ref()?.0;
+        let progress =
+            now.saturating_duration_since(started_at).as_secs_f32() / SLIDE_DURATION.as_secs_f32();
+        if progress >= 1. {
+            state.slide = None;
+            return None;
+        }
+
+        let offset = *state.slide.as_ref()?.1.get(group_id)?;
+        Some(offset * (1. - ease_in_out(progress)))
+    }
+
+    fn set_bounds(&self, group_id: SharedString, bounds: Bounds<Pixels>) {
+        self.0.borrow_mut().bounds.insert(group_id, bounds);
+    }
+
+    /// Moves the dragged group in front of the group shown at `target`, out of the groups with
+    /// the given IDs in the order they're shown, or to the end if `target` is past the last one.
+    fn move_group(
+        &self,
+        dragged: &SharedString,
+        target: usize,
+        shown: &[SharedString],
+        cx: &mut WindowContext,
+    ) {
+        let Some(order) = reordered(shown, dragged, target) else {
+            return;
+        };
+
+        let mut state = self.0.borrow_mut();
+        let bounds = shown
+            .iter()
+            .map(|id| state.bounds.get(id).copied())
+            .collect::<Option<Vec<_>>>();
+        state.slide = bounds.map(|bounds| {
+            let offsets = slide_offsets(shown, &bounds, &order);
+            (Instant::now(), offsets)
+        });
+        state.order = order.clone();
+        let on_reorder = state.on_reorder.clone();
+        drop(state);
+
+        on_reorder(&order, cx);
+        cx.refresh();
+    }
+}
+
+/// Returns the group IDs with `dragged` moved in front of the one at `target`, or `None` if that
+/// leaves them in the same order.
+fn reordered(
+    ids: &[SharedString],
+    dragged: &SharedString,
+    target: usize,
+) -> Option<Vec<SharedString>> {
+    let from = ids.iter().position(|id| id == dragged)?;
+    // Removing the group shifts the ones after it up by one.
+    let to = if target > from { target - 1 } else { target };
+    if to == from {
+        return None;
+    }
+
+    let mut order = ids.to_vec();
+    let id = order.remove(from);
+    order.insert(to.min(order.len()), id);
+    Some(order)
+}
+
+/// Returns the distance from where each group was painted, in the order given by `ids`, to where
+/// it's placed in `order`.
+///
+/// The groups are placed one after the other from the top of the first one, keeping the gap there
+/// was between them.
+fn slide_offsets(
+    ids: &[SharedString],
+    bounds: &[Bounds<Pixels>],
+    order: &[SharedString],
+) -> HashMap<SharedString, Pixels> {
+    let bounds_by_id = ids.iter().zip(bounds).collect::<HashMap<_, _>>();
+    let gap = match bounds {
+        [first, second, ..] => second.top() - first.bottom(),
+        _ => px(0.),
+    };
+
+    let mut top = bounds.first().map_or(px(0.), |bounds| bounds.top());
+    let mut offsets = HashMap::default();
+    for id in order {
+        if let Some(bounds) = bounds_by_id.get(id) {
+            offsets.insert(id.clone(), bounds.top() - top);
+            top += bounds.size.height + gap;
+        }
+    }
+    offsets
+}
+
+/// A child of a [`SettingsContainer`].
+struct SettingsContainerChild {
+    /// The ID of the child, if it's a reorderable group.
+    group_id: Option<SharedString>,
+    /// How well the child matches a search query, if it's a group that has been matched against
+    /// one.
+    search_score: Option<f64>,
+    element: AnyElement,
+}
+
+/// Orders search scores from the best to the worst, followed by the children without one.
+fn best_match_first(a: Option<f64>, b: Option<f64>) -> Ordering {
+    let score = |score: Option<f64>| score.unwrap_or(f64::NEG_INFINITY);
+    score(b).total_cmp(&score(a))
+}
 
 #[derive(IntoElement)]
 pub struct SettingsContainer {
-    children: SmallVec<[AnyElement; 2]>,
+    children: SmallVec<[SettingsContainerChild; 2]>,
+    order: Option<SettingsGroupOrder>,
 }
 
 impl SettingsContainer {
     pub fn new() -> Self {
         Self {
             children: SmallVec::new(),
+            order: None,
         }
     }
+
+    /// Lets the groups added with [`child_group`](Self::child_group) be reordered by dragging
+    /// their headers, arranging them in the given order.
+    ///
+    /// While a group is dragged over another, a line is shown above that group, where the dragged
+    /// one is dropped. After a drop, the groups slide into their new places.
+    pub fn reorderable(mut self, order: SettingsGroupOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Adds a settings group, which can be dragged to a new place when the container is
+    /// [`reorderable`](Self::reorderable) and the group is
+    /// [`draggable`](SettingsGroup::draggable).
+    ///
+    /// Groups with a [`search_score`](SettingsGroup::search_score) are shown from the best
+    /// matching to the worst, with groups that score the same kept in the order they're added.
+    pub fn child_group(mut self, group: SettingsGroup) -> Self {
+        self.children.push(SettingsContainerChild {
+            group_id: group.drag_id().cloned(),
+            search_score: group.score(),
+            element: group.into_any_element(),
+        });
+        self
+    }
 }
 
 impl ParentElement for SettingsContainer {
     fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
-        self.children.extend(elements)
+        self.children
+            .extend(elements.into_iter().map(|element| SettingsContainerChild {
+                group_id: None,
+                search_score: None,
+                element,
+            }))
     }
 }
 
 impl RenderOnce for SettingsContainer {
-    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
-        v_flex().px_2().gap_1().children(self.children)
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let Some(order) = self.order else {
+            let mut children = self.children;
+            // The sort is stable, so groups that score the same keep the order they were added
+            // in, and children without a score stay after the ones with one.
+            children.sort_by(|a, b| best_match_first(a.search_score, b.search_score));
+            return v_flex()
+                .px_2()
+                .gap_1()
+                .children(children.into_iter().map(|child| child.element));
+        };
+
+        let mut children = self.children;
+        // The sort is stable, so the children that aren't in the order keep theirs, after the
+        // ones that are.
+        children.sort_by_key(|child| order.position(child.group_id.as_ref()));
+        let shown: Rc<[SharedString]> = children
+            .iter()
+            .filter_map(|child| child.group_id.clone())
+            .collect();
+        let drop_indicator = cx.theme().colors().border_focused;
+        let now = Instant::now();
+        let mut sliding = false;
+
+        let container = v_flex()
+            .px_2()
+            .gap_1()
+            .children(
+                children
+                    .into_iter()
+                    .enumerate()
+                    .map(|(ix, child)| {
+                        let Some(group_id) = child.group_id else {
+                            return child.element;
+                        };
+
+                        let target = shown.iter().position(|id| *id == group_id).unwrap_or(ix);
+                        let slide_offset = order.slide_offset(&group_id, now);
+                        sliding |= slide_offset.is_some();
+
+                        div()
+                            .id(("settings-group-slot", ix))
+                            .relative()
+                            .when_some(slide_offset, |this, offset| this.top(offset))
+                            .border_t_2()
+                            .border_color(transparent_black())
+                            .drag_over::<DraggedSettingsGroup>(move |style, _, _| {
+                                style.border_color(drop_indicator)
+                            })
+                            .on_drop({
+                                let order = order.clone();
+                                let shown = shown.clone();
+                                move |dragged: &DraggedSettingsGroup, cx| {
+                                    order.move_group(&dragged.id, target, &shown, cx)
+                                }
+                            })
+                            .child(
+                                canvas(
+                                    {
+                                        let order = order.clone();
+                                        move |bounds, _| order.set_bounds(group_id, bounds)
+                                    },
+                                    |_, _, _| {},
+                                )
+                                .absolute()
+                                .top_0()
+                                .left_0()
+                                .size_full(),
+                            )
+                            .child(child.element)
+                            .into_any_element()
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            // Groups dropped below the last one are moved to the end.
+            .child(
+                div()
+                    .id("settings-group-slot-end")
+                    .h_2()
+                    .border_t_2()
+                    .border_color(transparent_black())
+                    .drag_over::<DraggedSettingsGroup>(move |style, _, _| {
+                        style.border_color(drop_indicator)
+                    })
+                    .on_drop(move |dragged: &DraggedSettingsGroup, cx| {
+                        order.move_group(&dragged.id, shown.len(), &shown, cx)
+                    }),
+            );
+
+        if sliding {
+            cx.request_animation_frame();
+        }
+        container
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, size};
+
+    use super::*;
+
+    fn ids(ids: &[&'static str]) -> Vec<SharedString> {
+        ids.iter().copied().map(SharedString::from).collect()
+    }
+
+    #[test]
+    fn test_reordering_groups() {
+        let shown = ids(&["font", "editor", "gutter"]);
+
+        // Dropping a group in front of itself or the group after it doesn't move it.
+        assert_eq!(reordered(&shown, &"editor".into(), 1), None);
+        assert_eq!(reordered(&shown, &"editor".into(), 2), None);
+
+        assert_eq!(
+            reordered(&shown, &"gutter".into(), 0),
+            Some(ids(&["gutter", "font", "editor"]))
+        );
+        assert_eq!(
+            reordered(&shown, &"font".into(), 2),
+            Some(ids(&["editor", "font", "gutter"]))
+        );
+        assert_eq!(
+            reordered(&shown, &"font".into(), 3),
+            Some(ids(&["editor", "gutter", "font"]))
+        );
+        assert_eq!(reordered(&shown, &"scrollbar".into(), 0), None);
+    }
+
+    #[test]
+    fn test_groups_are_ranked_by_search_score() {
+        let mut groups = vec![
+            ("font", Some(0.2)),
+            ("editor", None),
+            ("gutter", Some(0.5)),
+            ("scrollbar", Some(0.2)),
+        ];
+        groups.sort_by(|a, b| best_match_first(a.1, b.1));
+
+        // Groups that score the same keep their order.
+        assert_eq!(
+            groups.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            ["gutter", "font", "scrollbar", "editor"]
+        );
+    }
+
+    #[test]
+    fn test_displaced_groups_slide_from_where_they_were_painted() {
+        let shown = ids(&["font", "editor", "gutter"]);
+        let group_bounds =
+            |top: f32, height: f32| Bounds::new(point(px(0.), px(top)), size(px(100.), px(height)));
+        // The groups are 10px apart.
+        let bounds = [
+            group_bounds(0., 30.),
+            group_bounds(40., 50.),
+            group_bounds(100., 20.),
+        ];
+
+        let offsets = slide_offsets(&shown, &bounds, &ids(&["gutter", "font", "editor"]));
+        assert_eq!(offsets[&SharedString::from("gutter")], px(100.));
+        assert_eq!(offsets[&SharedString::from("font")], px(-30.));
+        assert_eq!(offsets[&SharedString::from("editor")], px(-30.));
     }
 }
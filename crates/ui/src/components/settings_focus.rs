@@ -0,0 +1,435 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gpui::{canvas, point, FocusHandle, ScrollHandle};
+
+use crate::prelude::*;
+
+/// A [`SettingsItem`](crate::SettingsItem) or [`SettingsGroup`](crate::SettingsGroup) header that
+/// can be focused from the keyboard.
+#[derive(Clone)]
+struct FocusableSetting {
+    id: ElementId,
+    on_activate: Option<Rc<dyn Fn(&mut WindowContext)>>,
+}
+
+enum FocusEntry {
+    Setting(FocusableSetting),
+    /// The header of a group, which is followed by the items of the group.
+    Group(FocusableSetting),
+}
+
+#[derive(Default)]
+struct SettingsFocusState {
+    focused: Option<ElementId>,
+    /// The group headers, and the settings outside of groups, in the order they were last
+    /// rendered.
+    entries: Vec<FocusEntry>,
+    /// The items of each group by the ID of its header, as they were last rendered.
+    ///
+    /// They're kept between frames, as a group whose contents are reused from the previous frame
+    /// doesn't render its items again.
+    group_items: HashMap<ElementId, Vec<FocusableSetting>>,
+    /// The group whose items are being rendered.
+    current_group: Option<ElementId>,
+    /// The element the settings are scrolled in.
+    scroll_handle: Option<ScrollHandle>,
+    /// Whether the focused setting should be scrolled into view once it has been laid out.
+    reveal_focused: bool,
+}
+
+impl SettingsFocusState {
+    /// Returns the settings in the order they're shown.
+    fn settings(&self) -> Vec<&FocusableSetting> {
+        let mut settings = Vec::new();
+        for entry in &self.entries {
+            match entry {
+                FocusEntry::Setting(setting) => settings.push(setting),
+                FocusEntry::Group(header) => {
+                    settings.push(header);
+                    settings.extend(self.group_items.get(&header.id).into_iter().flatten());
+                }
+            }
+        }
+        settings
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.current_group = None;
+    }
+
+    fn register(&mut self, setting: FocusableSetting) {
+        match &self.current_group {
+            Some(group_id) => self
+                .group_items
+                .entry(group_id.clone())
+                .or_default()
+                .push(setting),
+            None => self.entries.push(FocusEntry::Setting(setting)),
+        }
+    }
+
+    fn register_group(&mut self, header: FocusableSetting) {
+        self.current_group = None;
+        self.entries.push(FocusEntry::Group(header));
+    }
+
+    fn render_group_items(&mut self, group_id: ElementId) {
+        self.group_items.insert(group_id.clone(), Vec::new());
+        self.current_group = Some(group_id);
+    }
+
+    /// Moves the focus to the setting at the index returned by `target`, which is given the
+    /// index of the focused setting and the number of settings, returning whether it moved.
+    fn move_focus(&mut self, target: impl FnOnce(Option<usize>, usize) -> usize) -> bool {
+        let settings = self.settings();
+        if settings.is_empty() {
+            return false;
+        }
+
+        let focused_ix = self
+            .focused
+            .as_ref()
+            .and_then(|id| settings.iter().position(|setting| setting.id == *id));
+        let target_ix = target(focused_ix, settings.len());
+        let target_id = settings[target_ix].id.clone();
+        // Even if the focus stays where it is, it's been moved to from the keyboard, so the
+        // setting should be visible.
+        self.reveal_focused = true;
+        if focused_ix == Some(target_ix) {
+            return false;
+        }
+        self.focused = Some(target_id);
+        true
+    }
+
+    fn focus_first(&mut self) -> bool {
+        self.move_focus(|_, _| 0)
+    }
+
+    fn focus_last(&mut self) -> bool {
+        self.move_focus(|_, len| len - 1)
+    }
+
+    fn focus_next(&mut self) -> bool {
+        self.move_focus(|ix, len| match ix {
+            Some(ix) => (ix + 1).min(len - 1),
+            None => 0,
+        })
+    }
+
+    fn focus_previous(&mut self) -> bool {
+        self.move_focus(|ix, len| match ix {
+            Some(ix) => ix.saturating_sub(1),
+            None => len - 1,
+        })
+    }
+
+    fn focus_next_wrapping(&mut self) -> bool {
+        self.move_focus(|ix, len| ix.map_or(0, |ix| (ix + 1) % len))
+    }
+
+    fn focus_previous_wrapping(&mut self) -> bool {
+        self.move_focus(|ix, len| ix.map_or(len - 1, |ix| (ix + len - 1) % len))
+    }
+
+    fn is_first_focused(&self) -> bool {
+        self.focused.is_some()
+            && self.settings().first().map(|setting| &setting.id) == self.focused.as_ref()
+    }
+}
+
+/// The keyboard focus among the settings of a page, which outlives the page's render so that it
+/// can be moved between them with the keyboard.
+///
+/// Items and group headers given the focus register themselves when they're rendered, in the
+/// order they're shown. The focused one draws a focus ring while the focus handle is focused.
+/// The items of a group whose contents are reused from the previous frame keep their place.
+#[derive(Clone)]
+pub struct SettingsFocus {
+    focus_handle: FocusHandle,
+    state: Rc<RefCell<SettingsFocusState>>,
+}
+
+impl SettingsFocus {
+    /// Creates the focus for the settings in an element tracking the given focus handle, which
+    /// holds the keyboard focus while a setting is focused.
+    pub fn new(focus_handle: FocusHandle) -> Self {
+        Self {
+            focus_handle,
+            state: Rc::default(),
+        }
+    }
+
+    pub fn focus_handle(&self) -> &FocusHandle {
+        &self.focus_handle
+    }
+
+    /// Returns the ID of the focused setting.
+    pub fn focused(&self) -> Option<ElementId> {
+        self.state.borrow().focused.clone()
+    }
+
+    /// Returns whether the setting with the given ID is focused, and the focus handle holds the
+    /// keyboard focus.
+    pub fn is_focused(&self, id: &ElementId, cx: &WindowContext) -> bool {
+        self.focus_handle.is_focused(cx) && self.state.borrow().focused.as_ref() == Some(id)
+    }
+
+    /// Focuses the setting with the given ID.
+    pub fn focus(&self, id: impl Into<ElementId>) {
+        self.state.borrow_mut().focused = Some(id.into());
+    }
+
+    /// Scrolls the given element, which the settings are shown in, to keep the setting that the
+    /// focus is moved to visible.
+    pub fn track_scroll(&self, scroll_handle: &ScrollHandle) {
+        self.state.borrow_mut().scroll_handle = Some(scroll_handle.clone());
+    }
+
+    /// Forgets the settings that have been registered, before they're rendered again.
+    pub fn clear_settings(&self) {
+        self.state.borrow_mut().clear();
+    }
+
+    /// Focuses the first of the settings, returning whether the focus moved.
+    pub fn focus_first(&self) -> bool {
+        self.state.borrow_mut().focus_first()
+    }
+
+    /// Focuses the last of the settings, returning whether the focus moved.
+    pub fn focus_last(&self) -> bool {
+        self.state.borrow_mut().focus_last()
+    }
+
+    /// Focuses the setting after the focused one, or the first one if none is, returning whether
+    /// the focus moved. The focus stays on the last setting.
+    pub fn focus_next(&self) -> bool {
+        self.state.borrow_mut().focus_next()
+    }
+
+    /// Focuses the setting before the focused one, or the last one if none is, returning whether
+    /// the focus moved. The focus stays on the first setting.
+    pub fn focus_previous(&self) -> bool {
+        self.state.borrow_mut().focus_previous()
+    }
+
+    /// Focuses the setting after the focused one, wrapping around to the first after the last.
+    pub fn focus_next_wrapping(&self) -> bool {
+        self.state.borrow_mut().focus_next_wrapping()
+    }
+
+    /// Focuses the setting before the focused one, wrapping around to the last before the first.
+    pub fn focus_previous_wrapping(&self) -> bool {
+        self.state.borrow_mut().focus_previous_wrapping()
+    }
+
+    /// Returns whether the first of the settings is focused.
+    pub fn is_first_focused(&self) -> bool {
+        self.state.borrow().is_first_focused()
+    }
+
+    /// Toggles or opens the focused setting's control, returning whether it has one.
+    pub fn activate(&self, cx: &mut WindowContext) -> bool {
+        let on_activate = {
+            let state = self.state.borrow();
+            state.focused.as_ref().and_then(|id| {
+                state
+                    .settings()
+                    .into_iter()
+                    .find(|setting| setting.id == *id)
+                    .and_then(|setting| setting.on_activate.clone())
+            })
+        };
+        match on_activate {
+            Some(on_activate) => {
+                on_activate(cx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn register(
+        &self,
+        id: ElementId,
+        on_activate: Option<Rc<dyn Fn(&mut WindowContext)>>,
+    ) {
+        self.state
+            .borrow_mut()
+            .register(FocusableSetting { id, on_activate });
+    }
+
+    /// Registers the header of a group, which is followed by the group's items.
+    pub(crate) fn register_group(
+        &self,
+        id: ElementId,
+        on_activate: Option<Rc<dyn Fn(&mut WindowContext)>>,
+    ) {
+        self.state
+            .borrow_mut()
+            .register_group(FocusableSetting { id, on_activate });
+    }
+
+    /// Replaces the items of the group with the given ID with those registered until the next
+    /// group, for when the group's items are rendered again.
+    pub(crate) fn render_group_items(&self, group_id: ElementId) {
+        self.state.borrow_mut().render_group_items(group_id);
+    }
+
+    /// Returns a focus ring to paint over the setting with the given ID, if it's focused.
+    pub(crate) fn render_focus_ring(
+        &self,
+        id: &ElementId,
+        cx: &WindowContext,
+    ) -> Option<impl IntoElement> {
+        if self.state.borrow().focused.as_ref() != Some(id) {
+            return None;
+        }
+        if !self.focus_handle.is_focused(cx) {
+            // The focus was moved away, such as by clicking, before the setting was revealed.
+            self.state.borrow_mut().reveal_focused = false;
+            return None;
+        }
+
+        let color = cx.theme().colors().focus_ring;
+        let width = cx.theme().focus_ring_width();
+        let corner_radius = rems(0.375).to_pixels(cx.rem_size());
+        let state = self.state.clone();
+        Some(
+            canvas(
+                move |bounds, cx| {
+                    let scroll_handle = {
+                        let mut state = state.borrow_mut();
+                        std::mem::take(&mut state.reveal_focused)
+                            .then(|| state.scroll_handle.clone())
+                            .flatten()
+                    };
+                    let Some(scroll_handle) = scroll_handle else {
+                        return;
+                    };
+
+                    // The setting's bounds already include the current scroll offset, and it
+                    // only needs to move far enough to be entirely visible.
+                    let viewport = scroll_handle.bounds();
+                    let offset = scroll_handle.offset();
+                    let delta = if bounds.top() < viewport.top() {
+                        viewport.top() - bounds.top()
+                    } else if bounds.bottom() > viewport.bottom() {
+                        (viewport.bottom() - bounds.bottom()).max(viewport.top() - bounds.top())
+                    } else {
+                        return;
+                    };
+                    // The children have already been positioned for this frame.
+                    scroll_handle.set_offset(point(offset.x, offset.y + delta));
+                    cx.request_animation_frame();
+                },
+                move |bounds, _, cx| cx.paint_focus_ring(bounds, corner_radius, width, color),
+            )
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setting(id: &'static str) -> FocusableSetting {
+        FocusableSetting {
+            id: id.into(),
+            on_activate: None,
+        }
+    }
+
+    fn state(ids: &[&'static str]) -> SettingsFocusState {
+        let mut state = SettingsFocusState::default();
+        for id in ids {
+            state.register(setting(*id));
+        }
+        state
+    }
+
+    fn setting_ids(state: &SettingsFocusState) -> Vec<ElementId> {
+        state
+            .settings()
+            .into_iter()
+            .map(|setting| setting.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_moving_the_settings_focus() {
+        let mut state = state(&["font", "font-size", "ligatures"]);
+
+        // Moving down starts at the first setting, and stops at the last.
+        assert!(state.focus_next());
+        assert_eq!(state.focused, Some("font".into()));
+        assert!(state.reveal_focused);
+        assert!(state.is_first_focused());
+        assert!(state.focus_next());
+        assert!(state.focus_next());
+        assert_eq!(state.focused, Some("ligatures".into()));
+        assert!(!state.focus_next());
+        assert_eq!(state.focused, Some("ligatures".into()));
+
+        assert!(state.focus_first());
+        assert_eq!(state.focused, Some("font".into()));
+        assert!(state.focus_last());
+        assert_eq!(state.focused, Some("ligatures".into()));
+
+        // Moving with wrapping goes around from the last setting to the first, and back.
+        assert!(state.focus_next_wrapping());
+        assert_eq!(state.focused, Some("font".into()));
+        assert!(state.focus_previous_wrapping());
+        assert_eq!(state.focused, Some("ligatures".into()));
+        assert!(state.focus_previous());
+        assert_eq!(state.focused, Some("font-size".into()));
+
+        // When the focused setting is no longer shown, such as when a search hides it, the focus
+        // moves back to the first one.
+        state.entries.remove(1);
+        assert!(state.focus_next());
+        assert_eq!(state.focused, Some("font".into()));
+    }
+
+    #[test]
+    fn test_reused_groups_keep_their_items() {
+        let mut state = SettingsFocusState::default();
+        let render = |state: &mut SettingsFocusState, font_items: Option<&[&'static str]>| {
+            state.clear();
+            state.register_group(setting("font"));
+            // Items are only rendered for a group whose contents aren't reused.
+            if let Some(items) = font_items {
+                state.render_group_items("font".into());
+                for item in items {
+                    state.register(setting(*item));
+                }
+            }
+            state.register_group(setting("gutter"));
+            state.render_group_items("gutter".into());
+            state.register(setting("line-numbers"));
+        };
+
+        render(&mut state, Some(&["font-size", "ligatures"]));
+        let ids = setting_ids(&state);
+        assert_eq!(
+            ids,
+            ["font", "font-size", "ligatures", "gutter", "line-numbers"].map(ElementId::from)
+        );
+
+        render(&mut state, None);
+        assert_eq!(setting_ids(&state), ids);
+
+        render(&mut state, Some(&[]));
+        assert_eq!(
+            setting_ids(&state),
+            ["font", "gutter", "line-numbers"].map(ElementId::from)
+        );
+    }
+}
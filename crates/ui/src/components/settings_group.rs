@@ -1,22 +1,222 @@
-use gpui::AnyElement;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use gpui::{
+    cached, canvas, point, quad, AnyElement, ClickEvent, Hsla, LinearGradient, ScrollHandle,
+};
 use smallvec::SmallVec;
 
-use crate::{prelude::*, ListHeader};
+use crate::{prelude::*, DraggedSettingsGroup, ListHeader, SettingsFocus};
+
+/// How a [`SettingsGroup`] is drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettingsGroupStyle {
+    /// No background or border.
+    #[default]
+    Flat,
+    /// A bordered card.
+    Card,
+    /// A card tinted with the theme's error colors, for settings that are dangerous to change.
+    Danger,
+}
+
+impl SettingsGroupStyle {
+    /// Returns the background and border colors of the card, if the style has one.
+    fn card_colors(self, cx: &WindowContext) -> Option<(Hsla, Hsla)> {
+        match self {
+            SettingsGroupStyle::Flat => None,
+            SettingsGroupStyle::Card => Some((
+                cx.theme().colors().element_background,
+                cx.theme().colors().border_variant,
+            )),
+            SettingsGroupStyle::Danger => Some((
+                cx.theme().status().error_background,
+                cx.theme().status().error_border,
+            )),
+        }
+    }
+
+    fn header_color(self) -> Color {
+        match self {
+            SettingsGroupStyle::Flat | SettingsGroupStyle::Card => Color::Muted,
+            SettingsGroupStyle::Danger => Color::Error,
+        }
+    }
+}
 
 /// A group of settings.
 #[derive(IntoElement)]
 pub struct SettingsGroup {
     header: SharedString,
+    header_highlights: Vec<usize>,
+    description: Option<SharedString>,
     children: SmallVec<[AnyElement; 2]>,
+    collapsible: bool,
+    collapsed: bool,
+    style: SettingsGroupStyle,
+    background: Option<LinearGradient>,
+    highlighted: bool,
+    reveal: Option<ScrollHandle>,
+    drag_id: Option<SharedString>,
+    search_score: Option<f64>,
+    focus: Option<(SettingsFocus, ElementId)>,
+    state_hash: Option<u64>,
+    on_toggle: Option<Arc<dyn Fn(&ClickEvent, &mut WindowContext) + 'static>>,
 }
 
 impl SettingsGroup {
     pub fn new(header: impl Into<SharedString>) -> Self {
         Self {
             header: header.into(),
+            header_highlights: Vec::new(),
+            description: None,
             children: SmallVec::new(),
+            collapsible: false,
+            collapsed: false,
+            style: SettingsGroupStyle::default(),
+            background: None,
+            highlighted: false,
+            reveal: None,
+            drag_id: None,
+            search_score: None,
+            focus: None,
+            state_hash: None,
+            on_toggle: None,
         }
     }
+
+    /// Highlights the characters of the header at the given byte positions, such as those
+    /// matching a search query.
+    pub fn header_highlights(mut self, header_highlights: Vec<usize>) -> Self {
+        self.header_highlights = header_highlights;
+        self
+    }
+
+    /// Sets the text shown beneath the header, explaining what the group's settings are for.
+    pub fn description(mut self, description: impl Into<SharedString>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets whether the group can be collapsed by clicking its header.
+    pub fn collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
+    }
+
+    /// Sets whether the group is collapsed.
+    ///
+    /// A collapsed group only renders its header, along with the number of entries it contains.
+    /// This has no effect unless the group is [`collapsible`](Self::collapsible).
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+
+    /// Sets how the group is drawn.
+    ///
+    /// Every style has the same padding, so the settings in groups with different styles line up.
+    pub fn style(mut self, style: SettingsGroupStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Paints the group as a card filled with the given gradient.
+    pub fn background(mut self, background: LinearGradient) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    /// Sets whether the group is highlighted, such as after it has been scrolled to.
+    pub fn highlighted(mut self, highlighted: bool) -> Self {
+        self.highlighted = highlighted;
+        self
+    }
+
+    /// Scrolls the element tracked by the given handle so that the group's header is at its top,
+    /// once the group has been laid out.
+    ///
+    /// The scroll position is only set for the frame in which the group is rendered with this, so
+    /// it should stop being set once the group has been scrolled to.
+    pub fn reveal(mut self, scroll_handle: ScrollHandle) -> Self {
+        self.reveal = Some(scroll_handle);
+        self
+    }
+
+    /// Lets the group be dragged by its header to a new place in a
+    /// [`reorderable`](crate::SettingsContainer::reorderable) container, identifying it by the
+    /// given ID.
+    pub fn draggable(mut self, id: impl Into<SharedString>) -> Self {
+        self.drag_id = Some(id.into());
+        self
+    }
+
+    pub(crate) fn drag_id(&self) -> Option<&SharedString> {
+        self.drag_id.as_ref()
+    }
+
+    /// Sets how well the group matches a search query, which a
+    /// [`SettingsContainer`](crate::SettingsContainer) uses to show the best matching groups first.
+    pub fn search_score(mut self, search_score: f64) -> Self {
+        self.search_score = Some(search_score);
+        self
+    }
+
+    pub(crate) fn score(&self) -> Option<f64> {
+        self.search_score
+    }
+
+    /// Lets the group's header be focused from the keyboard, identified by the given ID among the
+    /// other settings sharing the focus. Activating a focused collapsible header toggles it.
+    pub fn focus(mut self, focus: SettingsFocus, id: impl Into<ElementId>) -> Self {
+        self.focus = Some((focus, id.into()));
+        self
+    }
+
+    /// Reuses what the group drew in the previous frame, as long as `state_hash` and the group's
+    /// own properties are unchanged, instead of rendering its settings again.
+    ///
+    /// The hash should account for the state that the group's settings are rendered from, such as
+    /// the values of the settings, and for the group's [`background`](Self::background), which
+    /// isn't compared.
+    pub fn cache(mut self, state_hash: u64) -> Self {
+        self.state_hash = Some(state_hash);
+        self
+    }
+
+    /// Returns a hash of the properties of the group that change how it's drawn, combined with
+    /// the given hash of its settings.
+    fn hash(&self, state_hash: u64, cx: &WindowContext) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        state_hash.hash(&mut hasher);
+        self.header.hash(&mut hasher);
+        self.header_highlights.hash(&mut hasher);
+        self.description.hash(&mut hasher);
+        self.children.len().hash(&mut hasher);
+        self.collapsible.hash(&mut hasher);
+        self.collapsed.hash(&mut hasher);
+        self.style.hash(&mut hasher);
+        self.background.is_some().hash(&mut hasher);
+        self.highlighted.hash(&mut hasher);
+        self.reveal.is_some().hash(&mut hasher);
+        self.drag_id.hash(&mut hasher);
+        if let Some((focus, _)) = &self.focus {
+            // The focus ring of the focused setting is drawn by the setting itself.
+            focus.focused().hash(&mut hasher);
+            focus.focus_handle().is_focused(cx).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    pub fn on_toggle(
+        mut self,
+        on_toggle: impl Fn(&ClickEvent, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.on_toggle = Some(Arc::new(on_toggle));
+        self
+    }
 }
 
 impl ParentElement for SettingsGroup {
@@ -26,11 +226,172 @@ impl ParentElement for SettingsGroup {
 }
 
 impl RenderOnce for SettingsGroup {
-    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        // The header is registered in every frame, as the settings are registered again from
+        // scratch, but a reused group keeps the items it registered when it was last rendered.
+        self.register_header();
+        let Some(state_hash) = self.state_hash else {
+            if let Some((focus, id)) = &self.focus {
+                focus.render_group_items(id.clone());
+            }
+            return self.render_body(cx);
+        };
+
+        let hash = self.hash(state_hash, cx);
+        let id = match &self.focus {
+            Some((_, id)) => id.clone(),
+            None => ElementId::Name(self.header.clone()),
+        };
+        cached(id, hash, move |cx| {
+            if let Some((focus, id)) = &self.focus {
+                focus.render_group_items(id.clone());
+            }
+            self.render_body(cx)
+        })
+        .into_any_element()
+    }
+}
+
+impl SettingsGroup {
+    fn register_header(&self) {
+        let Some((focus, id)) = &self.focus else {
+            return;
+        };
+        let on_toggle = self.on_toggle.clone().filter(|_| self.collapsible);
+        focus.register_group(
+            id.clone(),
+            on_toggle.map(|on_toggle| {
+                Rc::new(move |cx: &mut WindowContext| on_toggle(&ClickEvent::default(), cx))
+                    as Rc<dyn Fn(&mut WindowContext)>
+            }),
+        );
+    }
+
+    fn render_body(self, cx: &mut WindowContext) -> AnyElement {
+        let collapsed = self.collapsible && self.collapsed;
+        let entry_count = self.children.len();
+        // The gradient fills the card inside its border, so its corners are rounded to match.
+        let corner_radius = rems(0.375).to_pixels(cx.rem_size()) - px(1.);
+        let card_colors = self.style.card_colors(cx);
+        let header_color = self.style.header_color();
+        let header_focus_ring = self
+            .focus
+            .and_then(|(focus, id)| focus.render_focus_ring(&id, cx));
+        let header = ListHeader::new(self.header.clone())
+            .label_color(header_color)
+            .label_highlights(self.header_highlights)
+            .when(self.collapsible, |this| {
+                this.toggle(!collapsed)
+                    .when_some(self.on_toggle, |this, on_toggle| {
+                        this.on_toggle(move |event, cx| on_toggle(event, cx))
+                    })
+            })
+            .when(collapsed, |this| {
+                this.end_slot(
+                    Label::new(entry_count.to_string())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            });
+
         v_flex()
+            .relative()
             .p_1()
             .gap_2()
-            .child(ListHeader::new(self.header))
-            .children(self.children)
+            .when(self.highlighted, |this| {
+                this.rounded_md().bg(cx.theme().colors().element_selected)
+            })
+            .when_some(self.reveal, |this, scroll_handle| {
+                this.child(
+                    canvas(
+                        move |bounds, cx| {
+                            // The group's bounds already include the current scroll offset.
+                            let offset = scroll_handle.offset();
+                            let top = scroll_handle.bounds().top() - (bounds.top() - offset.y);
+                            if top != offset.y {
+                                // The children have already been positioned for this frame.
+                                scroll_handle.set_offset(point(offset.x, top));
+                                cx.request_animation_frame();
+                            }
+                        },
+                        |_, _, _| {},
+                    )
+                    .absolute()
+                    .top_0()
+                    .left_0()
+                    .size_full(),
+                )
+            })
+            .when_some(card_colors, |this, (background, border)| {
+                // The card is painted behind the group rather than styled as its border, so that
+                // it doesn't move the group's contents.
+                let card_corner_radius = rems(0.375).to_pixels(cx.rem_size());
+                this.child(
+                    canvas(
+                        |_, _| {},
+                        move |bounds, _, cx| {
+                            cx.paint_quad(quad(bounds, card_corner_radius, background, 1., border))
+                        },
+                    )
+                    .absolute()
+                    .top_0()
+                    .left_0()
+                    .size_full(),
+                )
+            })
+            .when_some(self.background, |this, background| {
+                this.rounded_md()
+                    .border_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        canvas(
+                            |_, _| {},
+                            move |bounds, _, cx| {
+                                cx.paint_gradient(bounds, &background, corner_radius)
+                            },
+                        )
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .size_full(),
+                    )
+            })
+            .map(|this| match self.drag_id {
+                Some(drag_id) => {
+                    let dragged = DraggedSettingsGroup {
+                        id: drag_id.clone(),
+                        header: self.header,
+                    };
+                    this.child(
+                        div()
+                            // Derived from the group's ID without formatting a new string.
+                            .id(ElementId::NamedInteger(drag_id.clone(), 0))
+                            .relative()
+                            .cursor_grab()
+                            .on_drag(dragged, |dragged, cx| cx.new_view(|_| dragged.clone()))
+                            .child(header)
+                            .children(header_focus_ring),
+                    )
+                }
+                None => this.child(div().relative().child(header).children(header_focus_ring)),
+            })
+            .when(!collapsed, |this| {
+                this.when_some(self.description, |this, description| {
+                    this.child(
+                        div().px_2().child(
+                            Label::new(description)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                    )
+                })
+                // Settings scrolled out of view in a long page aren't painted.
+                .children(
+                    self.children
+                        .into_iter()
+                        .map(|child| div().cull_offscreen().child(child)),
+                )
+            })
+            .into_any_element()
     }
 }
@@ -0,0 +1,327 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gpui::{AnyElement, MouseButton, Point, Task, TextDirection};
+use smallvec::SmallVec;
+
+use crate::{prelude::*, SettingsFocus};
+
+/// How long a [`SettingsItem`] has to be held for a long press, unless it sets its own duration.
+const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+/// How far, in pixels, the pointer can move while an item is held before the press is cancelled.
+const LONG_PRESS_SLOP: f64 = 4.;
+
+/// A press on a [`SettingsItem`] that hasn't been released yet.
+struct Press {
+    position: Point<Pixels>,
+    /// Whether the press was held long enough to be a long press, in which case releasing it
+    /// doesn't click the control.
+    long: bool,
+    /// Calls the long press handler once the press has been held long enough. Dropping the press
+    /// cancels it.
+    _timer: Task<()>,
+}
+
+impl Press {
+    fn new(position: Point<Pixels>, timer: Task<()>) -> Self {
+        Self {
+            position,
+            long: false,
+            _timer: timer,
+        }
+    }
+
+    /// Returns whether moving the pointer to `position` cancels the press.
+    fn cancelled_by_move(&self, position: Point<Pixels>) -> bool {
+        !self.long && (position - self.position).magnitude() > LONG_PRESS_SLOP
+    }
+}
+
+struct SettingsItemAction {
+    label: SharedString,
+    icon: IconName,
+    handler: Rc<dyn Fn(&mut WindowContext)>,
+}
+
+/// A setting's control, followed by buttons for any extra actions it offers, such as opening its
+/// documentation.
+///
+/// The action buttons are aligned to the right of the row, in the order they were added. In a
+/// right-to-left item, the row is mirrored.
+///
+/// Holding the pointer on the item, away from its action buttons, calls its long press handler,
+/// and releasing it then doesn't click the control.
+///
+/// An item given a [`SettingsFocus`] can be focused from the keyboard, drawing a focus ring
+/// around itself, and its activation handler is called when it's activated with Space or Enter.
+#[derive(IntoElement)]
+pub struct SettingsItem {
+    id: ElementId,
+    control: AnyElement,
+    actions: SmallVec<[SettingsItemAction; 2]>,
+    direction: TextDirection,
+    on_long_press: Option<Rc<dyn Fn(&mut WindowContext)>>,
+    long_press_duration: Duration,
+    focus: Option<SettingsFocus>,
+    on_activate: Option<Rc<dyn Fn(&mut WindowContext)>>,
+}
+
+impl SettingsItem {
+    pub fn new(id: impl Into<ElementId>, control: impl IntoElement) -> Self {
+        Self {
+            id: id.into(),
+            control: control.into_any_element(),
+            actions: SmallVec::new(),
+            direction: TextDirection::LeftToRight,
+            on_long_press: None,
+            long_press_duration: LONG_PRESS_DURATION,
+            focus: None,
+            on_activate: None,
+        }
+    }
+
+    /// Sets the direction in which the item's label is read, such as the
+    /// [`TextDirection::of`] the label, so that right-to-left labels are aligned to the right
+    /// with the action buttons to their left.
+    pub fn direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Calls `handler` when the item is held without moving the pointer, for example to reveal the
+    /// setting's key.
+    pub fn on_long_press(mut self, handler: impl Fn(&mut WindowContext) + 'static) -> Self {
+        self.on_long_press = Some(Rc::new(handler));
+        self
+    }
+
+    /// Sets how long the item has to be held before its long press handler is called.
+    pub fn long_press_duration(mut self, duration: Duration) -> Self {
+        self.long_press_duration = duration;
+        self
+    }
+
+    /// Lets the item be focused from the keyboard among the other settings sharing the given
+    /// focus.
+    pub fn focus(mut self, focus: SettingsFocus) -> Self {
+        self.focus = Some(focus);
+        self
+    }
+
+    /// Calls `handler` when the item is activated while it's focused, such as to toggle its
+    /// checkbox or open its dropdown.
+    pub fn on_activate(mut self, handler: impl Fn(&mut WindowContext) + 'static) -> Self {
+        self.on_activate = Some(Rc::new(handler));
+        self
+    }
+
+    /// Adds a button with the given label and icon, which calls `handler` when clicked.
+    pub fn action(
+        mut self,
+        label: impl Into<SharedString>,
+        icon: IconName,
+        handler: impl Fn(&mut WindowContext) + 'static,
+    ) -> Self {
+        self.actions.push(SettingsItemAction {
+            label: label.into(),
+            icon,
+            handler: Rc::new(handler),
+        });
+        self
+    }
+}
+
+impl RenderOnce for SettingsItem {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let long_press_duration = self.long_press_duration;
+        let long_press = self.on_long_press.map(|handler| {
+            let press = cx.with_retained_state(
+                self.id.clone(),
+                |press: &mut Option<Rc<RefCell<Option<Press>>>>, _| {
+                    press.get_or_insert_with(Default::default).clone()
+                },
+            );
+            (press, handler)
+        });
+        let focus_ring = self.focus.and_then(|focus| {
+            focus.register(self.id.clone(), self.on_activate);
+            focus.render_focus_ring(&self.id, cx)
+        });
+
+        h_flex()
+            .id(self.id)
+            .relative()
+            .gap_2()
+            .justify_between()
+            .when(self.direction == TextDirection::RightToLeft, |this| {
+                this.flex_row_reverse()
+            })
+            .when_some(long_press, |this, (press, handler)| {
+                this.on_mouse_down(MouseButton::Left, {
+                    let press = press.clone();
+                    move |event, cx| {
+                        let timer = cx.spawn({
+                            let press = press.clone();
+                            let handler = handler.clone();
+                            |mut cx| async move {
+                                cx.background_executor().timer(long_press_duration).await;
+                                let held =
+                                    press.borrow_mut().as_mut().map(|press| press.long = true);
+                                if held.is_some() {
+                                    cx.update(|cx| handler(cx)).ok();
+                                }
+                            }
+                        });
+                        *press.borrow_mut() = Some(Press::new(event.position, timer));
+                    }
+                })
+                .on_mouse_move({
+                    let press = press.clone();
+                    move |event, _| {
+                        let mut press = press.borrow_mut();
+                        if press
+                            .as_ref()
+                            .is_some_and(|press| press.cancelled_by_move(event.position))
+                        {
+                            *press = None;
+                        }
+                    }
+                })
+                .on_hover({
+                    let press = press.clone();
+                    move |hovered, _| {
+                        let mut press = press.borrow_mut();
+                        if !hovered && press.as_ref().is_some_and(|press| !press.long) {
+                            *press = None;
+                        }
+                    }
+                })
+                // Releasing a long press is handled before the control sees it, so that it isn't
+                // also a click.
+                .capture_any_mouse_up({
+                    let press = press.clone();
+                    move |event, cx| {
+                        if event.button == MouseButton::Left
+                            && press.borrow_mut().take().is_some_and(|press| press.long)
+                        {
+                            cx.stop_propagation();
+                        }
+                    }
+                })
+                .on_mouse_up_out(MouseButton::Left, move |_, _| {
+                    press.borrow_mut().take();
+                })
+            })
+            .child(self.control)
+            .when(!self.actions.is_empty(), |this| {
+                this.child(
+                    h_flex()
+                        .gap_1()
+                        // Keep presses on the buttons from reaching the control behind them.
+                        .on_mouse_down(MouseButton::Left, |_, cx| cx.stop_propagation())
+                        .children(self.actions.into_iter().enumerate().map(|(ix, action)| {
+                            let handler = action.handler;
+
+                            Button::new(("settings-item-action", ix), action.label)
+                                .icon(action.icon)
+                                .icon_position(IconPosition::Start)
+                                .icon_size(IconSize::Small)
+                                .icon_color(Color::Muted)
+                                .label_size(LabelSize::Small)
+                                .on_click(move |_, cx| handler(cx))
+                        })),
+                )
+            })
+            .children(focus_ring)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use gpui::{point, px, Modifiers, TestAppContext, View, VisualTestContext};
+    use settings::SettingsStore;
+
+    use super::*;
+    use crate::{Checkbox, Selection};
+
+    #[derive(Default)]
+    struct CheckboxItem {
+        clicks: Rc<Cell<usize>>,
+        long_presses: Rc<Cell<usize>>,
+    }
+
+    impl Render for CheckboxItem {
+        fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+            let clicks = self.clicks.clone();
+            let long_presses = self.long_presses.clone();
+            SettingsItem::new(
+                "item",
+                Checkbox::new("checkbox", Selection::Unselected)
+                    .on_click(move |_, _| clicks.set(clicks.get() + 1)),
+            )
+            .on_long_press(move |_| long_presses.set(long_presses.get() + 1))
+        }
+    }
+
+    fn add_checkbox_item(cx: &mut TestAppContext) -> (View<CheckboxItem>, &mut VisualTestContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+        });
+        cx.add_window_view(|_| CheckboxItem::default())
+    }
+
+    fn counts(item: &View<CheckboxItem>, cx: &mut VisualTestContext) -> (usize, usize) {
+        cx.update(|cx| {
+            let item = item.read(cx);
+            (item.clicks.get(), item.long_presses.get())
+        })
+    }
+
+    #[gpui::test]
+    fn test_quick_press_clicks_the_control(cx: &mut TestAppContext) {
+        let (item, cx) = add_checkbox_item(cx);
+        let checkbox = point(px(5.), px(5.));
+
+        cx.simulate_mouse_down(checkbox, MouseButton::Left, Modifiers::default());
+        cx.executor().advance_clock(LONG_PRESS_DURATION / 5);
+        cx.simulate_mouse_up(checkbox, MouseButton::Left, Modifiers::default());
+        assert_eq!(counts(&item, cx), (1, 0));
+
+        // Releasing the press cancels the long press.
+        cx.executor().advance_clock(LONG_PRESS_DURATION);
+        cx.run_until_parked();
+        assert_eq!(counts(&item, cx), (1, 0));
+    }
+
+    #[gpui::test]
+    fn test_held_press_is_a_long_press_and_not_a_click(cx: &mut TestAppContext) {
+        let (item, cx) = add_checkbox_item(cx);
+        let checkbox = point(px(5.), px(5.));
+
+        cx.simulate_mouse_down(checkbox, MouseButton::Left, Modifiers::default());
+        cx.executor().advance_clock(LONG_PRESS_DURATION);
+        cx.run_until_parked();
+        assert_eq!(counts(&item, cx), (0, 1));
+
+        cx.simulate_mouse_up(checkbox, MouseButton::Left, Modifiers::default());
+        assert_eq!(counts(&item, cx), (0, 1));
+    }
+
+    #[test]
+    fn test_moving_the_pointer_cancels_a_press() {
+        let mut press = Press::new(point(px(10.), px(10.)), Task::ready(()));
+
+        // Small movements don't cancel the press.
+        assert!(!press.cancelled_by_move(point(px(12.), px(13.))));
+        assert!(press.cancelled_by_move(point(px(15.), px(10.))));
+
+        // Once the press is long, moving the pointer no longer cancels it.
+        press.long = true;
+        assert!(!press.cancelled_by_move(point(px(100.), px(10.))));
+    }
+}
@@ -0,0 +1,198 @@
+use gpui::{Bounds, DragMoveEvent, Render};
+
+use crate::prelude::*;
+
+/// The drag payload used while a [`Slider`] thumb is being dragged.
+#[derive(Clone, Render)]
+struct DraggedSlider(ElementId);
+
+/// # Slider
+///
+/// Sliders are used to pick a numeric value from a continuous range, such as a font size or a
+/// scroll sensitivity. Dragging along the track clamps the value to `[min, max]` and snaps it to
+/// the nearest `step`.
+#[derive(IntoElement)]
+pub struct Slider {
+    id: ElementId,
+    value: f32,
+    min: f32,
+    max: f32,
+    step: f32,
+    precision: usize,
+    disabled: bool,
+    on_change: Option<Box<dyn Fn(f32, &mut WindowContext) + 'static>>,
+}
+
+impl Slider {
+    pub fn new(id: impl Into<ElementId>, value: f32, min: f32, max: f32, step: f32) -> Self {
+        Self {
+            id: id.into(),
+            value,
+            min,
+            max,
+            step,
+            precision: 0,
+            disabled: false,
+            on_change: None,
+        }
+    }
+
+    /// Sets the number of decimal places used when displaying the current value.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    pub fn on_change(mut self, handler: impl Fn(f32, &mut WindowContext) + 'static) -> Self {
+        self.on_change = Some(Box::new(handler));
+        self
+    }
+
+    /// Returns the position of `value` along the track, from `0.` to `1.`.
+    fn fraction(&self) -> f32 {
+        if self.max <= self.min {
+            return 0.;
+        }
+
+        ((self.value - self.min) / (self.max - self.min)).clamp(0., 1.)
+    }
+}
+
+impl Disableable for Slider {
+    fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// Maps a horizontal position within the track `bounds` to a value in `[min, max]`,
+/// snapped to the nearest `step`.
+fn value_for_position(x: Pixels, bounds: Bounds<Pixels>, min: f32, max: f32, step: f32) -> f32 {
+    let width = bounds.size.width.0;
+    let fraction = if width > 0. {
+        ((x - bounds.left()).0 / width).clamp(0., 1.)
+    } else {
+        0.
+    };
+    let value = min + fraction * (max - min);
+    let value = if step > 0. {
+        min + ((value - min) / step).round() * step
+    } else {
+        value
+    };
+
+    value.clamp(min, max)
+}
+
+impl RenderOnce for Slider {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let fraction = self.fraction();
+        let thumb_size = px(12.);
+        let colors = cx.theme().colors();
+        let (fill_color, thumb_color) = if self.disabled {
+            (colors.border_disabled, colors.icon_disabled)
+        } else {
+            (colors.text_accent, colors.text)
+        };
+        let value_label = format!("{:.*}", self.precision, self.value);
+
+        let Slider {
+            id,
+            min,
+            max,
+            step,
+            disabled,
+            on_change,
+            ..
+        } = self;
+
+        h_flex()
+            .gap_2()
+            .child(
+                div()
+                    .id(id.clone())
+                    .relative()
+                    .flex()
+                    .items_center()
+                    .w(rems(10.))
+                    .h(thumb_size)
+                    .child(
+                        div()
+                            .absolute()
+                            .left_0()
+                            .right_0()
+                            .h(px(4.))
+                            .rounded_full()
+                            .bg(colors.border),
+                    )
+                    .child(
+                        div()
+                            .absolute()
+                            .left_0()
+                            .w(relative(fraction))
+                            .h(px(4.))
+                            .rounded_full()
+                            .bg(fill_color),
+                    )
+                    .child(
+                        div()
+                            .absolute()
+                            .left(relative(fraction))
+                            .ml(-thumb_size / 2.)
+                            .size(thumb_size)
+                            .rounded_full()
+                            .border_1()
+                            .border_color(colors.border)
+                            .bg(thumb_color),
+                    )
+                    .when(!disabled, |this| {
+                        this.cursor_ew_resize()
+                            .on_drag(DraggedSlider(id.clone()), |dragged, cx| {
+                                cx.stop_propagation();
+                                cx.new_view(|_| dragged.clone())
+                            })
+                            .when_some(on_change, |this, on_change| {
+                                this.on_drag_move(
+                                    move |event: &DragMoveEvent<DraggedSlider>, cx| {
+                                        if event.drag(cx).0 != id {
+                                            return;
+                                        }
+
+                                        let value = value_for_position(
+                                            event.event.position.x,
+                                            event.bounds,
+                                            min,
+                                            max,
+                                            step,
+                                        );
+                                        on_change(value, cx);
+                                    },
+                                )
+                            })
+                    }),
+            )
+            .child(Label::new(value_label).color(if disabled {
+                Color::Disabled
+            } else {
+                Color::Default
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, size};
+
+    use super::*;
+
+    #[test]
+    fn test_value_for_position_clamps_and_snaps() {
+        let bounds = Bounds::new(point(px(10.), px(0.)), size(px(100.), px(12.)));
+
+        assert_eq!(value_for_position(px(0.), bounds, 0., 10., 1.), 0.);
+        assert_eq!(value_for_position(px(500.), bounds, 0., 10., 1.), 10.);
+        assert_eq!(value_for_position(px(44.), bounds, 0., 10., 1.), 3.);
+        assert_eq!(value_for_position(px(60.), bounds, 0., 10., 2.5), 5.);
+        assert_eq!(value_for_position(px(60.), bounds, 0., 10., 0.), 5.);
+    }
+}
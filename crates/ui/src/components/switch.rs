@@ -0,0 +1,126 @@
+use std::time::{Duration, Instant};
+
+use gpui::ease_in_out;
+
+use crate::{prelude::*, Selection};
+
+const TRACK_WIDTH: Pixels = px(28.);
+const TRACK_HEIGHT: Pixels = px(16.);
+const KNOB_SIZE: Pixels = px(12.);
+const KNOB_INSET: Pixels = px(1.);
+const KNOB_SLIDE_DURATION: Duration = Duration::from_millis(150);
+
+/// Where a switch's knob is, kept across frames while it slides to the other end of the track.
+struct Knob {
+    toggled: bool,
+    left: Pixels,
+    /// Where the knob was when the switch was last toggled, and when that was, while it's sliding.
+    slide: Option<(Pixels, Instant)>,
+}
+
+/// # Switch
+///
+/// Switches toggle a single setting on or off, taking effect immediately. Unlike a
+/// [`Checkbox`](crate::Checkbox), a switch has no indeterminate state.
+#[derive(IntoElement)]
+pub struct Switch {
+    id: ElementId,
+    toggled: bool,
+    disabled: bool,
+    on_click: Option<Box<dyn Fn(&Selection, &mut WindowContext) + 'static>>,
+}
+
+impl Switch {
+    pub fn new(id: impl Into<ElementId>, toggled: bool) -> Self {
+        Self {
+            id: id.into(),
+            toggled,
+            disabled: false,
+            on_click: None,
+        }
+    }
+
+    pub fn on_click(mut self, handler: impl Fn(&Selection, &mut WindowContext) + 'static) -> Self {
+        self.on_click = Some(Box::new(handler));
+        self
+    }
+}
+
+impl Disableable for Switch {
+    fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+impl RenderOnce for Switch {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        // The knob slides between the two ends of the track, the border included.
+        let toggled = self.toggled;
+        let knob_left = if toggled {
+            TRACK_WIDTH - px(2.) - KNOB_SIZE - KNOB_INSET
+        } else {
+            KNOB_INSET
+        };
+        // Toggling the switch while the knob is sliding turns it around from where it is.
+        let knob_left = cx.with_retained_state(self.id.clone(), |knob: &mut Option<Knob>, cx| {
+            let knob = knob.get_or_insert_with(|| Knob {
+                toggled,
+                left: knob_left,
+                slide: None,
+            });
+            let now = Instant::now();
+            if knob.toggled != toggled {
+                knob.toggled = toggled;
+                knob.slide = Some((knob.left, now));
+            }
+
+            knob.left = knob_left;
+            if let Some((slide_from, started_at)) = knob.slide {
+                let progress = now.saturating_duration_since(started_at).as_secs_f32()
+                    / KNOB_SLIDE_DURATION.as_secs_f32();
+                if progress < 1. {
+                    knob.left = slide_from + (knob_left - slide_from) * ease_in_out(progress);
+                    cx.request_animation_frame();
+                } else {
+                    knob.slide = None;
+                }
+            }
+            knob.left
+        });
+
+        let colors = cx.theme().colors();
+        let (track_color, knob_color) = match (self.disabled, self.toggled) {
+            (true, _) => (colors.ghost_element_disabled, colors.icon_disabled),
+            (false, true) => (colors.text_accent, colors.text),
+            (false, false) => (colors.element_background, colors.icon_muted),
+        };
+
+        div()
+            .id(self.id)
+            .relative()
+            .flex_none()
+            .w(TRACK_WIDTH)
+            .h(TRACK_HEIGHT)
+            .rounded_full()
+            .border_1()
+            .border_color(colors.border)
+            .bg(track_color)
+            .child(
+                div()
+                    .absolute()
+                    .top(KNOB_INSET)
+                    .left(knob_left)
+                    .size(KNOB_SIZE)
+                    .rounded_full()
+                    .bg(knob_color),
+            )
+            .when_some(
+                self.on_click.filter(|_| !self.disabled),
+                |this, on_click| {
+                    this.cursor_pointer()
+                        .on_click(move |_, cx| on_click(&Selection::from(!toggled), cx))
+                },
+            )
+    }
+}
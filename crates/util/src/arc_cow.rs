@@ -13,6 +13,13 @@ pub enum ArcCow<'a, T: ?Sized> {
 
 impl<'a, T: ?Sized + PartialEq> PartialEq for ArcCow<'a, T> {
     fn eq(&self, other: &Self) -> bool {
+        // Values sharing an allocation, such as interned strings, are equal without comparing them.
+        if let (Self::Owned(a), Self::Owned(b)) = (self, other) {
+            if Arc::ptr_eq(a, b) {
+                return true;
+            }
+        }
+
         let a = self.as_ref();
         let b = other.as_ref();
         a == b
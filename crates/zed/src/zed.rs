@@ -115,6 +115,8 @@ pub fn build_window_options(display_uuid: Option<Uuid>, cx: &mut AppContext) ->
             width: px(360.0),
             height: px(240.0),
         }),
+        offscreen: false,
+        parent: None,
     }
 }
 